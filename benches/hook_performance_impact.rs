@@ -1,6 +1,9 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-use wasm::{hooks::HookSet, validate, RuntimeInstance};
+use wasm::{
+    hooks::{HookAction, HookSet, Location},
+    validate, RuntimeInstance,
+};
 
 fn criterion_benchmark(c: &mut Criterion) {
     let wat = r#"
@@ -23,6 +26,17 @@ fn criterion_benchmark(c: &mut Criterion) {
         (func (export "load_num") (result i32)
             i32.const 0
             i32.load)
+        (func (export "count_down") (param $n i32) (result i32)
+            loop
+                local.get $n
+                i32.const 1
+                i32.sub
+                local.tee $n
+                i32.const 0
+                i32.gt_s
+                br_if 0
+            end
+            local.get $n)
 
         (export "add_one" (func $add_one))
         (export "add" (func $add))
@@ -44,10 +58,16 @@ fn criterion_benchmark(c: &mut Criterion) {
     #[derive(Default)]
     struct MyCustomHookSet;
     impl HookSet for MyCustomHookSet {
-        fn instruction_hook(&mut self, bytecode: &[u8], pc: usize) {
+        fn instruction_hook(
+            &mut self,
+            bytecode: &[u8],
+            pc: usize,
+            _location: Location,
+        ) -> HookAction {
             if black_box(bytecode[pc]) == 0x20 {
                 eprintln!("First instruction is a local.get");
             }
+            HookAction::Continue
         }
     }
 
@@ -61,6 +81,11 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("invoke_func MyCustomHookSet", |b| {
         b.iter(|| instance_non_empty_hookset.invoke_func::<_, ()>(black_box(2), black_box(42_i32)))
     });
+
+    // Many instructions per invocation, so that any per-instruction overhead is visible
+    c.bench_function("count_down EmptyHookSet", |b| {
+        b.iter(|| instance_empty_hookset.invoke_func::<_, i32>(black_box(4), black_box(10_000_i32)))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,16 +1,88 @@
-use crate::core::indices::GlobalIdx;
+use crate::core::indices::{
+    DataIdx, ElemIdx, FuncIdx, GlobalIdx, LabelIdx, MemIdx, TableIdx, TypeIdx,
+};
+use alloc::boxed::Box;
+use alloc::string::String;
 use core::fmt::{Display, Formatter};
 use core::str::Utf8Error;
 
 use crate::core::reader::section_header::SectionTy;
-use crate::core::reader::types::ValType;
+use crate::core::reader::types::global::GlobalType;
+use crate::core::reader::types::{FuncType, Limits, ValType};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum RuntimeError {
     DivideBy0,
     UnrepresentableResult,
+    /// An `unreachable` instruction was executed
+    Unreachable,
+    /// A NaN was converted to an integer
+    BadConversionToInteger,
     FunctionNotFound,
     StackSmash,
+    /// An import of the module is not defined in the linker used for instantiation
+    UnknownImport {
+        module_name: String,
+        name: String,
+    },
+    /// An import of the module is defined in the linker, but with a different type
+    ImportTypeMismatch {
+        module_name: String,
+        name: String,
+        expected: Box<FuncType>,
+        actual: Box<FuncType>,
+    },
+    /// A global import of the module is defined in the linker, but with a different type
+    GlobalImportTypeMismatch {
+        module_name: String,
+        name: String,
+        expected: GlobalType,
+        actual: GlobalType,
+    },
+    /// A host function returned values which do not match its declared result types
+    InvalidHostFunctionResults,
+    /// A host function signaled a trap
+    HostTrap,
+    /// A table index is out of the bounds of a table
+    UndefinedTableElement,
+    /// An indirect call hit a table element that is a null reference
+    UninitializedTableElement,
+    /// The function referenced by a table element does not have the type expected by an indirect
+    /// call
+    IndirectCallTypeMismatch,
+    /// An access to a table exceeds the table's bounds, e.g. when copying a segment into it
+    TableAccessOutOfBounds,
+    /// An access to linear memory exceeds the memory's bounds
+    MemoryOutOfBounds,
+    /// A hook aborted execution with the given code
+    HookAbort(u32),
+    /// A hook paused an invocation which can not be resumed, as it was not started as a
+    /// [`Resumable`](crate::resumable::Resumable)
+    UnresumablePause,
+    /// The parameters or results of an invocation do not match the type of the invoked function
+    FunctionTypeMismatch,
+    /// An imported function was to be invoked as a
+    /// [`Resumable`](crate::resumable::Resumable), which requires a function defined in WASM
+    HostFunctionNotResumable,
+}
+
+/// Errors that can occur when restoring a paused invocation from a snapshot
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SnapshotError {
+    /// The snapshot does not start with the expected magic number
+    InvalidMagic,
+    /// The snapshot was created with an unsupported version of the snapshot format
+    UnsupportedVersion(u32),
+    /// The snapshot was taken from an instance of a different module
+    ModuleMismatch,
+    /// The snapshot ended unexpectedly
+    Eof,
+    /// There are bytes left after the end of the snapshot
+    TrailingBytes,
+    /// The snapshot is well-formed, but describes a state that is impossible for this module
+    InvalidState,
+    /// The results of the invoked function do not match the expected return types
+    FunctionTypeMismatch,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -31,7 +103,7 @@ pub enum Error {
     InvalidImportDesc(u8),
     ExprMissingEnd,
     InvalidInstr(u8),
-    InvalidMultiByteInstr(u8, u8),
+    InvalidMultiByteInstr(u8, u32),
     EndInvalidValueStack,
     InvalidLocalIdx,
     InvalidValueStackType(Option<ValType>),
@@ -40,6 +112,43 @@ pub enum Error {
     MoreThanOneMemory,
     InvalidGlobalIdx(GlobalIdx),
     GlobalIsConst,
+    InvalidBlockType,
+    InvalidLabelIdx(LabelIdx),
+    ElseWithoutMatchingIf,
+    IfWithoutMatchingElse,
+    InvalidBrTableArity,
+    InvalidElemSegmentType(u32),
+    InvalidElemKind,
+    /// An instruction which is not allowed in a constant expression was found
+    InvalidConstInstr(u8),
+    InvalidTableIdx(TableIdx),
+    InvalidFuncIdx(FuncIdx),
+    InvalidTypeIdx(TypeIdx),
+    /// A reference of a different type was expected, e.g. for the elements of a table
+    RefTypeMismatch,
+    InvalidDataSegmentType(u32),
+    InvalidMemIdx(MemIdx),
+    /// The number of data segments differs from the number announced in the data count section
+    DataCountMismatch,
+    /// `memory.init` and `data.drop` can only be used if the module has a data count section
+    DataCountMissing,
+    InvalidDataIdx(DataIdx),
+    InvalidElemIdx(ElemIdx),
+    /// `ref.func` in a function body refers to a function that is not referenced outside of
+    /// function bodies, e.g. in an element segment or an export
+    UndeclaredFuncRef(FuncIdx),
+    /// Constant expressions may only read immutable globals
+    MutableGlobalInConstExpr(GlobalIdx),
+    /// The alignment of a memory access is larger than the natural alignment of the accessed type
+    InvalidAlignment(u32),
+    /// The minimum of limits is larger than their maximum, or a memory's limits exceed 65536 pages
+    InvalidLimits(Limits),
+    /// A typed `select` must have exactly one result type, but this many were given
+    InvalidSelectArity(usize),
+    /// An untyped `select` can only choose between numeric values
+    InvalidSelectOperand(ValType),
+    /// A vector instruction refers to a lane that does not exist in the vector's shape
+    InvalidLaneIdx(u8),
     RuntimeError(RuntimeError),
 }
 
@@ -110,6 +219,79 @@ impl Display for Error {
                 "An invalid global index `{idx}` was specified"
             )),
             Error::GlobalIsConst => f.write_str("A const global cannot be written to"),
+            Error::InvalidBlockType => {
+                f.write_str("An invalid byte was read where a blocktype was expected")
+            }
+            Error::InvalidLabelIdx(idx) => f.write_fmt(format_args!(
+                "An invalid label index `{idx}` was used in a branch instruction"
+            )),
+            Error::ElseWithoutMatchingIf => {
+                f.write_str("An else instruction was found without a matching if")
+            }
+            Error::IfWithoutMatchingElse => f.write_str(
+                "An if block without an else branch must have the same parameter and result types",
+            ),
+            Error::InvalidBrTableArity => {
+                f.write_str("The labels of a br_table instruction have differing arities")
+            }
+            Error::InvalidElemSegmentType(ty) => f.write_fmt(format_args!(
+                "An invalid element segment type was found: {ty}"
+            )),
+            Error::InvalidElemKind => {
+                f.write_str("An invalid byte was read where an elemkind was expected")
+            }
+            Error::InvalidConstInstr(byte) => f.write_fmt(format_args!(
+                "The instruction `{byte:#x?}` is not allowed in a constant expression"
+            )),
+            Error::InvalidTableIdx(idx) => {
+                f.write_fmt(format_args!("An invalid table index `{idx}` was specified"))
+            }
+            Error::InvalidFuncIdx(idx) => f.write_fmt(format_args!(
+                "An invalid function index `{idx}` was specified"
+            )),
+            Error::InvalidTypeIdx(idx) => {
+                f.write_fmt(format_args!("An invalid type index `{idx}` was specified"))
+            }
+            Error::RefTypeMismatch => f.write_str("A reference of a different type was expected"),
+            Error::InvalidDataSegmentType(ty) => {
+                f.write_fmt(format_args!("An invalid data segment type was found: {ty}"))
+            }
+            Error::InvalidMemIdx(idx) => f.write_fmt(format_args!(
+                "An invalid memory index `{idx}` was specified"
+            )),
+            Error::DataCountMismatch => f.write_str(
+                "The number of data segments differs from the one given in the data count section",
+            ),
+            Error::DataCountMissing => f.write_str(
+                "Data segments can only be referenced by instructions if there is a data count section",
+            ),
+            Error::InvalidDataIdx(idx) => f.write_fmt(format_args!(
+                "An invalid data segment index `{idx}` was specified"
+            )),
+            Error::InvalidElemIdx(idx) => f.write_fmt(format_args!(
+                "An invalid element segment index `{idx}` was specified"
+            )),
+            Error::UndeclaredFuncRef(idx) => f.write_fmt(format_args!(
+                "The function `{idx}` is referenced without being declared outside of function bodies"
+            )),
+            Error::MutableGlobalInConstExpr(idx) => f.write_fmt(format_args!(
+                "The mutable global `{idx}` was read in a constant expression"
+            )),
+            Error::InvalidAlignment(align) => f.write_fmt(format_args!(
+                "The alignment `2^{align}` is larger than the natural alignment of the access"
+            )),
+            Error::InvalidLimits(limits) => {
+                f.write_fmt(format_args!("The limits `{limits:?}` are invalid"))
+            }
+            Error::InvalidSelectArity(arity) => f.write_fmt(format_args!(
+                "A typed select must have exactly one result type, but {arity} were given"
+            )),
+            Error::InvalidSelectOperand(ty) => f.write_fmt(format_args!(
+                "An untyped select can not choose between operands of type `{ty:?}`"
+            )),
+            Error::InvalidLaneIdx(lane_idx) => f.write_fmt(format_args!(
+                "The lane index `{lane_idx}` is out of range for the vector's shape"
+            )),
             Error::RuntimeError(err) => err.fmt(f),
         }
     }
@@ -120,8 +302,82 @@ impl Display for RuntimeError {
         match self {
             RuntimeError::DivideBy0 => f.write_str("Divide by zero is not permitted"),
             RuntimeError::UnrepresentableResult => f.write_str("Result is unrepresentable"),
+            RuntimeError::Unreachable => f.write_str("An unreachable instruction was executed"),
+            RuntimeError::BadConversionToInteger => {
+                f.write_str("Bad conversion to integer: NaN can not be represented by an integer")
+            }
             RuntimeError::FunctionNotFound => f.write_str("Function not found"),
             RuntimeError::StackSmash => f.write_str("Stack smashed"),
+            RuntimeError::UnknownImport { module_name, name } => f.write_fmt(format_args!(
+                "The import `{module_name}`.`{name}` is not defined in the linker"
+            )),
+            RuntimeError::ImportTypeMismatch {
+                module_name,
+                name,
+                expected,
+                actual,
+            } => f.write_fmt(format_args!(
+                "The import `{module_name}`.`{name}` is expected to be of type {expected:?}, but is defined with type {actual:?}"
+            )),
+            RuntimeError::GlobalImportTypeMismatch {
+                module_name,
+                name,
+                expected,
+                actual,
+            } => f.write_fmt(format_args!(
+                "The global import `{module_name}`.`{name}` is expected to be of type {expected:?}, but is defined with type {actual:?}"
+            )),
+            RuntimeError::InvalidHostFunctionResults => {
+                f.write_str("A host function returned values which do not match its result types")
+            }
+            RuntimeError::HostTrap => f.write_str("A host function trapped"),
+            RuntimeError::UndefinedTableElement => f.write_str("Undefined element in a table"),
+            RuntimeError::UninitializedTableElement => {
+                f.write_str("Uninitialized element in a table")
+            }
+            RuntimeError::IndirectCallTypeMismatch => f.write_str("Indirect call type mismatch"),
+            RuntimeError::TableAccessOutOfBounds => f.write_str("Out of bounds table access"),
+            RuntimeError::MemoryOutOfBounds => f.write_str("Out of bounds memory access"),
+            RuntimeError::HookAbort(code) => {
+                f.write_fmt(format_args!("A hook aborted execution with code {code}"))
+            }
+            RuntimeError::UnresumablePause => {
+                f.write_str("A hook paused an invocation which can not be resumed")
+            }
+            RuntimeError::FunctionTypeMismatch => {
+                f.write_str("The given parameters or results do not match the function type")
+            }
+            RuntimeError::HostFunctionNotResumable => {
+                f.write_str("An imported function can not be invoked as a resumable invocation")
+            }
+        }
+    }
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SnapshotError::InvalidMagic => {
+                f.write_str("The magic number at the very start of the snapshot is invalid")
+            }
+            SnapshotError::UnsupportedVersion(version) => f.write_fmt(format_args!(
+                "The snapshot format version {version} is not supported"
+            )),
+            SnapshotError::ModuleMismatch => {
+                f.write_str("The snapshot was taken from an instance of a different module")
+            }
+            SnapshotError::Eof => f.write_str(
+                "A value was expected in the snapshot but the end of it was reached instead",
+            ),
+            SnapshotError::TrailingBytes => {
+                f.write_str("There are unexpected bytes after the end of the snapshot")
+            }
+            SnapshotError::InvalidState => {
+                f.write_str("The snapshot describes a state that is invalid for this module")
+            }
+            SnapshotError::FunctionTypeMismatch => f.write_str(
+                "The expected return types do not match the results of the invoked function",
+            ),
         }
     }
 }
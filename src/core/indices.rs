@@ -20,10 +20,7 @@ pub type FuncIdx = usize;
 pub type TableIdx = usize;
 pub type MemIdx = usize;
 pub type GlobalIdx = usize;
-#[allow(dead_code)]
 pub type ElemIdx = usize;
-#[allow(dead_code)]
 pub type DataIdx = usize;
 pub type LocalIdx = usize;
-#[allow(dead_code)]
 pub type LabelIdx = usize;
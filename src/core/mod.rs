@@ -2,3 +2,5 @@ pub mod error;
 
 pub mod indices;
 pub mod reader;
+pub mod sha256;
+pub mod sidetable;
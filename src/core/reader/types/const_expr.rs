@@ -0,0 +1,138 @@
+//! Constant expressions, which are evaluated during instantiation.
+//!
+//! They are used to initialize globals and as the offsets and elements of segments. During
+//! validation only their [`Span`] is recorded, which is evaluated with [`eval_const_expr`] later on.
+//!
+//! See: <https://webassembly.github.io/spec/core/valid/instructions.html#constant-expressions>
+
+use alloc::vec::Vec;
+
+use crate::core::indices::{FuncIdx, GlobalIdx};
+use crate::core::reader::span::Span;
+use crate::core::reader::types::global::GlobalType;
+use crate::core::reader::types::{NumType, RefType, ValType};
+use crate::core::reader::{WasmReadable, WasmReader};
+use crate::execution::assert_validated::UnwrapValidatedExt;
+use crate::execution::value::{Ref, Value, F32, F64};
+use crate::{unreachable_validated, Error, Result};
+
+/// The parts of a module that constant expressions can refer to
+pub struct ConstExprContext<'a> {
+    /// Only imported globals can be read by constant expressions
+    pub imported_globals: &'a [GlobalType],
+    /// The number of functions in the function index space
+    pub num_funcs: usize,
+}
+
+/// Validate a constant expression resulting in a single value of type `expected_ty`
+///
+/// Returns the span of the expression, including its final `end`.
+pub fn read_const_expr(
+    wasm: &mut WasmReader,
+    ctx: &ConstExprContext,
+    expected_ty: ValType,
+) -> Result<Span> {
+    use crate::core::reader::types::opcode::*;
+
+    let start = wasm.pc;
+    let mut stack: Vec<ValType> = Vec::new();
+    loop {
+        match wasm.read_u8()? {
+            I32_CONST => {
+                let _ = wasm.read_var_i32()?;
+                stack.push(ValType::NumType(NumType::I32));
+            }
+            I64_CONST => {
+                let _ = wasm.read_var_i64()?;
+                stack.push(ValType::NumType(NumType::I64));
+            }
+            F32_CONST => {
+                let _ = wasm.read_var_f32()?;
+                stack.push(ValType::NumType(NumType::F32));
+            }
+            F64_CONST => {
+                let _ = wasm.read_var_f64()?;
+                stack.push(ValType::NumType(NumType::F64));
+            }
+            FD_EXTENSIONS => {
+                if wasm.read_var_u32()? != fd_extensions::V128_CONST {
+                    return Err(Error::InvalidConstInstr(FD_EXTENSIONS));
+                }
+                let _ = wasm.strip_bytes::<16>()?;
+                stack.push(ValType::VecType);
+            }
+            REF_NULL => {
+                let ty = RefType::read(wasm)?;
+                stack.push(ValType::RefType(ty));
+            }
+            REF_FUNC => {
+                let func_idx = wasm.read_var_u32()? as FuncIdx;
+                if func_idx >= ctx.num_funcs {
+                    return Err(Error::InvalidFuncIdx(func_idx));
+                }
+                stack.push(ValType::RefType(RefType::FuncRef));
+            }
+            GLOBAL_GET => {
+                let global_idx = wasm.read_var_u32()? as GlobalIdx;
+                let global = ctx
+                    .imported_globals
+                    .get(global_idx)
+                    .ok_or(Error::InvalidGlobalIdx(global_idx))?;
+                if global.is_mut {
+                    return Err(Error::MutableGlobalInConstExpr(global_idx));
+                }
+                stack.push(global.ty);
+            }
+            END => break,
+            other => return Err(Error::InvalidConstInstr(other)),
+        }
+    }
+
+    if stack != [expected_ty] {
+        return Err(Error::EndInvalidValueStack);
+    }
+
+    Ok(Span::new(start, wasm.pc - start))
+}
+
+/// Returns the function that a constant expression validated by [`read_const_expr`] refers to, if
+/// it is a `ref.func` instruction
+pub fn const_expr_func_ref(wasm: &WasmReader, expr: Span) -> Option<FuncIdx> {
+    use crate::core::reader::types::opcode::REF_FUNC;
+
+    let [REF_FUNC, func_idx @ ..] = &wasm[expr] else {
+        return None;
+    };
+    let func_idx = WasmReader::new(func_idx).read_var_u32().ok()?;
+    Some(func_idx as FuncIdx)
+}
+
+/// Evaluate a constant expression that was validated by [`read_const_expr`]
+///
+/// `globals` are the values of the globals that the expression may refer to.
+pub fn eval_const_expr(wasm_bytecode: &[u8], expr: Span, globals: &[Value]) -> Value {
+    use crate::core::reader::types::opcode::*;
+
+    let mut wasm = WasmReader::new(wasm_bytecode);
+    wasm.move_start_to(expr).unwrap_validated();
+
+    // Validation guarantees that there is exactly one instruction before the `end`
+    match wasm.read_u8().unwrap_validated() {
+        I32_CONST => Value::I32(wasm.read_var_i32().unwrap_validated() as u32),
+        I64_CONST => Value::I64(wasm.read_var_i64().unwrap_validated() as u64),
+        F32_CONST => Value::F32(F32(f32::from_bits(wasm.read_var_f32().unwrap_validated()))),
+        F64_CONST => Value::F64(F64(f64::from_bits(wasm.read_var_f64().unwrap_validated()))),
+        // the only vector instruction allowed in constant expressions is `v128.const`
+        FD_EXTENSIONS => {
+            let _v128_const = wasm.read_var_u32().unwrap_validated();
+            Value::V128(wasm.strip_bytes::<16>().unwrap_validated())
+        }
+        REF_NULL => Value::Ref(Ref::Null(RefType::read_unvalidated(&mut wasm))),
+        REF_FUNC => Value::Ref(Ref::Func(wasm.read_var_u32().unwrap_validated() as FuncIdx)),
+        GLOBAL_GET => {
+            let global_idx = wasm.read_var_u32().unwrap_validated() as GlobalIdx;
+            *globals.get(global_idx).unwrap_validated()
+        }
+        _ => unreachable_validated!(),
+    }
+}
@@ -0,0 +1,50 @@
+use crate::core::indices::MemIdx;
+use crate::core::reader::span::Span;
+use crate::core::reader::types::const_expr::{read_const_expr, ConstExprContext};
+use crate::core::reader::types::{NumType, ValType};
+use crate::core::reader::WasmReader;
+use crate::{Error, Result};
+
+/// <https://webassembly.github.io/spec/core/syntax/modules.html#data-segments>
+#[derive(Debug, Clone)]
+pub struct DataSegment {
+    /// The bytes of the segment within the WASM binary
+    pub init: Span,
+    pub mode: DataMode,
+}
+
+#[derive(Debug, Clone)]
+pub enum DataMode {
+    Passive,
+    /// The segment is copied into a memory during instantiation
+    Active {
+        mem_idx: MemIdx,
+        /// A constant expression of type `i32`
+        offset: Span,
+    },
+}
+
+impl DataSegment {
+    /// See: <https://webassembly.github.io/spec/core/binary/modules.html#data-section>
+    pub fn read(wasm: &mut WasmReader, ctx: &ConstExprContext) -> Result<Self> {
+        let offset_ty = ValType::NumType(NumType::I32);
+        let mode = match wasm.read_var_u32()? {
+            0 => DataMode::Active {
+                mem_idx: 0,
+                offset: read_const_expr(wasm, ctx, offset_ty)?,
+            },
+            1 => DataMode::Passive,
+            2 => DataMode::Active {
+                mem_idx: wasm.read_var_u32()? as MemIdx,
+                offset: read_const_expr(wasm, ctx, offset_ty)?,
+            },
+            other => return Err(Error::InvalidDataSegmentType(other)),
+        };
+
+        let len = wasm.read_var_u32()? as usize;
+        let init = wasm.make_span(len)?;
+        wasm.skip(len)?;
+
+        Ok(Self { init, mode })
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::vec::Vec;
+
+use crate::core::indices::{FuncIdx, TableIdx};
+use crate::core::reader::span::Span;
+use crate::core::reader::types::const_expr::{read_const_expr, ConstExprContext};
+use crate::core::reader::types::{NumType, RefType, ValType};
+use crate::core::reader::{WasmReadable, WasmReader};
+use crate::{Error, Result};
+
+/// <https://webassembly.github.io/spec/core/syntax/modules.html#element-segments>
+#[derive(Debug, Clone)]
+pub struct ElemSegment {
+    pub ty: RefType,
+    pub items: ElemItems,
+    pub mode: ElemMode,
+}
+
+#[derive(Debug, Clone)]
+pub enum ElemItems {
+    /// References to the functions with these indices
+    Funcs(Vec<FuncIdx>),
+    /// Constant expressions resulting in references of the segment's type
+    Exprs(Vec<Span>),
+}
+
+#[derive(Debug, Clone)]
+pub enum ElemMode {
+    Passive,
+    /// The segment is copied into a table during instantiation
+    Active {
+        table_idx: TableIdx,
+        /// A constant expression of type `i32`
+        offset: Span,
+    },
+    Declarative,
+}
+
+impl ElemSegment {
+    /// See: <https://webassembly.github.io/spec/core/binary/modules.html#element-section>
+    pub fn read(wasm: &mut WasmReader, ctx: &ConstExprContext) -> Result<Self> {
+        let flags = wasm.read_var_u32()?;
+        if flags > 7 {
+            return Err(Error::InvalidElemSegmentType(flags));
+        }
+
+        // bit 0: passive or declarative, bit 1: explicit table index (if active) or declarative,
+        // bit 2: elements are expressions instead of function indices
+        let is_active = flags & 0b001 == 0;
+        let has_table_idx = flags & 0b010 != 0;
+        let uses_exprs = flags & 0b100 != 0;
+
+        let mode = if is_active {
+            let table_idx = if has_table_idx {
+                wasm.read_var_u32()? as TableIdx
+            } else {
+                0
+            };
+            let offset = read_const_expr(wasm, ctx, ValType::NumType(NumType::I32))?;
+            ElemMode::Active { table_idx, offset }
+        } else if has_table_idx {
+            ElemMode::Declarative
+        } else {
+            ElemMode::Passive
+        };
+
+        // Segments in the original, active format only contain funcrefs and omit their type
+        let has_explicit_ty = !is_active || has_table_idx;
+
+        let (ty, items) = if uses_exprs {
+            let ty = if has_explicit_ty {
+                RefType::read(wasm)?
+            } else {
+                RefType::FuncRef
+            };
+            let exprs = wasm.read_vec(|wasm| read_const_expr(wasm, ctx, ValType::RefType(ty)))?;
+            (ty, ElemItems::Exprs(exprs))
+        } else {
+            if has_explicit_ty {
+                let 0x00 = wasm.read_u8()? else {
+                    return Err(Error::InvalidElemKind);
+                };
+            }
+            let funcs = wasm.read_vec(|wasm| {
+                let func_idx = wasm.read_var_u32()? as FuncIdx;
+                if func_idx >= ctx.num_funcs {
+                    return Err(Error::InvalidFuncIdx(func_idx));
+                }
+                Ok(func_idx)
+            })?;
+            (RefType::FuncRef, ElemItems::Funcs(funcs))
+        };
+
+        Ok(Self { ty, items, mode })
+    }
+}
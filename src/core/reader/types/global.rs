@@ -1,29 +1,14 @@
+use crate::core::reader::span::Span;
 use crate::core::reader::types::ValType;
 use crate::core::reader::{WasmReadable, WasmReader};
 use crate::execution::assert_validated::UnwrapValidatedExt;
 use crate::{unreachable_validated, Error, Result};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone)]
 pub struct Global {
     pub ty: GlobalType,
-    // TODO validate init_expr during validation and execute during instantiation
-    pub init_expr: (),
-}
-
-impl WasmReadable for Global {
-    fn read(wasm: &mut WasmReader) -> Result<Self> {
-        Ok(Self {
-            ty: GlobalType::read(wasm)?,
-            init_expr: (), /* todo!("read constant expression") */
-        })
-    }
-
-    fn read_unvalidated(wasm: &mut WasmReader) -> Self {
-        Self {
-            ty: GlobalType::read_unvalidated(wasm),
-            init_expr: (), /* todo!("read constant expression") */
-        }
-    }
+    /// A constant expression computing the initial value, which is evaluated during instantiation
+    pub init_expr: Span,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -2,6 +2,7 @@ use alloc::borrow::ToOwned;
 use alloc::string::String;
 
 use crate::core::indices::TypeIdx;
+use crate::core::reader::types::global::GlobalType;
 use crate::core::reader::{WasmReadable, WasmReader};
 use crate::execution::assert_validated::UnwrapValidatedExt;
 use crate::{unreachable_validated, Error, Result};
@@ -52,8 +53,7 @@ pub enum ImportDesc {
     #[allow(dead_code)]
     Mem(()),
     // TODO MemType
-    #[allow(dead_code)]
-    Global(()), // TODO GlobalType
+    Global(GlobalType),
 }
 
 impl WasmReadable for ImportDesc {
@@ -62,7 +62,7 @@ impl WasmReadable for ImportDesc {
             0x00 => Self::Func(wasm.read_var_u32()? as TypeIdx),
             0x01 => todo!("read TableType"),
             0x02 => todo!("read MemType"),
-            0x03 => todo!("read GlobalType"),
+            0x03 => Self::Global(GlobalType::read(wasm)?),
             other => return Err(Error::InvalidImportDesc(other)),
         };
 
@@ -74,7 +74,7 @@ impl WasmReadable for ImportDesc {
             0x00 => Self::Func(wasm.read_var_u32().unwrap_validated() as TypeIdx),
             0x01 => todo!("read TableType"),
             0x02 => todo!("read MemType"),
-            0x03 => todo!("read GlobalType"),
+            0x03 => Self::Global(GlobalType::read_unvalidated(wasm)),
             _ => unreachable_validated!(),
         }
     }
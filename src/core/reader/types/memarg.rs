@@ -3,24 +3,25 @@ use core::fmt::Debug;
 use crate::core::reader::{WasmReadable, WasmReader};
 use crate::execution::assert_validated::UnwrapValidatedExt;
 
+/// <https://webassembly.github.io/spec/core/binary/instructions.html#memory-instructions>
 #[derive(Debug)]
 pub struct MemArg {
     pub offset: u32,
-    #[allow(dead_code)]
+    /// The exponent of the alignment hint, i.e. the access is assumed to be aligned to `2^align`
     pub align: u32,
 }
 
 impl WasmReadable for MemArg {
     fn read(wasm: &mut WasmReader) -> crate::Result<Self> {
-        let offset = wasm.read_var_u32()?;
-        #[allow(dead_code)]
+        // the alignment precedes the offset in the binary format
         let align = wasm.read_var_u32()?;
+        let offset = wasm.read_var_u32()?;
         Ok(Self { offset, align })
     }
 
     fn read_unvalidated(wasm: &mut WasmReader) -> Self {
-        let offset = wasm.read_var_u32().unwrap_validated();
         let align = wasm.read_var_u32().unwrap_validated();
+        let offset = wasm.read_var_u32().unwrap_validated();
         Self { offset, align }
     }
 }
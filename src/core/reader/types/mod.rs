@@ -5,11 +5,15 @@
 use alloc::vec::Vec;
 use core::fmt::{Debug, Formatter};
 
+use crate::core::indices::TypeIdx;
 use crate::core::reader::{WasmReadable, WasmReader};
 use crate::execution::assert_validated::UnwrapValidatedExt;
 use crate::Result;
 use crate::{unreachable_validated, Error};
 
+pub mod const_expr;
+pub mod data;
+pub mod element;
 pub mod export;
 pub mod function_code_header;
 pub mod global;
@@ -116,34 +120,36 @@ pub enum ValType {
 }
 
 impl ValType {
+    /// The size of a value of this type in bytes
+    ///
+    /// References are opaque, so their size is that of the indices and handles they consist of.
     pub const fn size(&self) -> usize {
         match self {
             Self::NumType(NumType::I32 | NumType::F32) => 4,
             Self::NumType(NumType::I64 | NumType::F64) => 8,
             Self::VecType => 16,
-            Self::RefType(_) => todo!(),
+            Self::RefType(_) => core::mem::size_of::<usize>(),
         }
     }
 }
 
 impl WasmReadable for ValType {
     fn read(wasm: &mut WasmReader) -> Result<Self> {
-        let numtype = NumType::read(wasm).map(ValType::NumType);
-        let vectype = VecType::read(wasm).map(|_ty| ValType::VecType);
-        let reftype = RefType::read(wasm).map(ValType::RefType);
-
-        numtype
-            .or(vectype)
-            .or(reftype)
+        // Only try the next kind of type if the previous one did not match, as each successful
+        // read consumes a byte
+        NumType::read(wasm)
+            .map(ValType::NumType)
+            .or_else(|_| VecType::read(wasm).map(|_ty| ValType::VecType))
+            .or_else(|_| RefType::read(wasm).map(ValType::RefType))
             .map_err(|_| Error::InvalidValType)
     }
 
     fn read_unvalidated(wasm: &mut WasmReader) -> Self {
-        let numtype = NumType::read(wasm).map(ValType::NumType);
-        let vectype = VecType::read(wasm).map(|_ty| ValType::VecType);
-        let reftype = RefType::read(wasm).map(ValType::RefType);
-
-        numtype.or(vectype).or(reftype).unwrap_validated()
+        NumType::read(wasm)
+            .map(ValType::NumType)
+            .or_else(|_| VecType::read(wasm).map(|_ty| ValType::VecType))
+            .or_else(|_| RefType::read(wasm).map(ValType::RefType))
+            .unwrap_validated()
     }
 }
 
@@ -200,6 +206,76 @@ impl WasmReadable for FuncType {
     }
 }
 
+/// <https://webassembly.github.io/spec/core/binary/instructions.html#binary-blocktype>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlockType {
+    Empty,
+    Returns(ValType),
+    /// The block has the function type with this index, which may have parameters and multiple
+    /// results
+    Type(TypeIdx),
+}
+
+impl BlockType {
+    /// The function type `[t1*] -> [t2*]` that a block of this type has
+    pub fn as_func_type(&self, fn_types: &[FuncType]) -> Result<FuncType> {
+        let returns = match self {
+            BlockType::Empty => Vec::new(),
+            BlockType::Returns(ty) => alloc::vec![*ty],
+            BlockType::Type(idx) => {
+                return fn_types
+                    .get(*idx)
+                    .cloned()
+                    .ok_or(Error::InvalidTypeIdx(*idx))
+            }
+        };
+
+        Ok(FuncType {
+            params: ResultType {
+                valtypes: Vec::new(),
+            },
+            returns: ResultType { valtypes: returns },
+        })
+    }
+}
+
+impl WasmReadable for BlockType {
+    fn read(wasm: &mut WasmReader) -> Result<Self> {
+        let first_byte = wasm.peek_u8()?;
+        if first_byte == 0x40 {
+            let _ = wasm.read_u8();
+            return Ok(BlockType::Empty);
+        }
+
+        // Value types are encoded as single-byte negative numbers, type indices as non-negative
+        // signed 33-bit integers
+        if first_byte & 0xC0 == 0x40 {
+            return ValType::read(wasm)
+                .map(BlockType::Returns)
+                .map_err(|_| Error::InvalidBlockType);
+        }
+
+        let idx = wasm.read_var_i64()?;
+        u32::try_from(idx)
+            .map(|idx| BlockType::Type(idx as TypeIdx))
+            .map_err(|_| Error::InvalidBlockType)
+    }
+
+    fn read_unvalidated(wasm: &mut WasmReader) -> Self {
+        let first_byte = wasm.peek_u8().unwrap_validated();
+        if first_byte == 0x40 {
+            let _ = wasm.read_u8();
+            return BlockType::Empty;
+        }
+
+        if first_byte & 0xC0 == 0x40 {
+            return BlockType::Returns(ValType::read_unvalidated(wasm));
+        }
+
+        BlockType::Type(wasm.read_var_i64().unwrap_validated() as TypeIdx)
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct Limits {
     pub min: u32,
@@ -265,6 +341,10 @@ impl WasmReadable for TableType {
     fn read(wasm: &mut WasmReader) -> Result<Self> {
         let et = RefType::read(wasm)?;
         let lim = Limits::read(wasm)?;
+        if lim.max.is_some_and(|max| lim.min > max) {
+            return Err(Error::InvalidLimits(lim));
+        }
+
         Ok(Self { et, lim })
     }
 
@@ -281,11 +361,20 @@ pub struct MemType {
     pub limits: Limits,
 }
 
+impl MemType {
+    /// The number of pages that are addressable with 32 bits
+    pub const MAX_PAGES: u32 = 1 << 16;
+}
+
 impl WasmReadable for MemType {
     fn read(wasm: &mut WasmReader) -> Result<Self> {
-        Ok(Self {
-            limits: Limits::read(wasm)?,
-        })
+        let limits = Limits::read(wasm)?;
+        let max = limits.max.unwrap_or(Self::MAX_PAGES);
+        if limits.min > max || max > Self::MAX_PAGES {
+            return Err(Error::InvalidLimits(limits));
+        }
+
+        Ok(Self { limits })
     }
 
     fn read_unvalidated(wasm: &mut WasmReader) -> Self {
@@ -1,18 +1,51 @@
+pub const UNREACHABLE: u8 = 0x00;
 pub const NOP: u8 = 0x01;
+pub const BLOCK: u8 = 0x02;
+pub const LOOP: u8 = 0x03;
+pub const IF: u8 = 0x04;
+pub const ELSE: u8 = 0x05;
 pub const END: u8 = 0x0B;
+pub const BR: u8 = 0x0C;
+pub const BR_IF: u8 = 0x0D;
+pub const BR_TABLE: u8 = 0x0E;
 pub const RETURN: u8 = 0x0F;
 pub const CALL: u8 = 0x10;
+pub const CALL_INDIRECT: u8 = 0x11;
+pub const DROP: u8 = 0x1A;
+pub const SELECT: u8 = 0x1B;
+pub const SELECT_T: u8 = 0x1C;
 pub const LOCAL_GET: u8 = 0x20;
 pub const LOCAL_SET: u8 = 0x21;
 pub const LOCAL_TEE: u8 = 0x22;
 pub const GLOBAL_GET: u8 = 0x23;
 pub const GLOBAL_SET: u8 = 0x24;
+pub const TABLE_GET: u8 = 0x25;
+pub const TABLE_SET: u8 = 0x26;
 pub const I32_LOAD: u8 = 0x28;
+pub const I64_LOAD: u8 = 0x29;
 pub const F32_LOAD: u8 = 0x2A;
 pub const F64_LOAD: u8 = 0x2B;
+pub const I32_LOAD8_S: u8 = 0x2C;
+pub const I32_LOAD8_U: u8 = 0x2D;
+pub const I32_LOAD16_S: u8 = 0x2E;
+pub const I32_LOAD16_U: u8 = 0x2F;
+pub const I64_LOAD8_S: u8 = 0x30;
+pub const I64_LOAD8_U: u8 = 0x31;
+pub const I64_LOAD16_S: u8 = 0x32;
+pub const I64_LOAD16_U: u8 = 0x33;
+pub const I64_LOAD32_S: u8 = 0x34;
+pub const I64_LOAD32_U: u8 = 0x35;
 pub const I32_STORE: u8 = 0x36;
+pub const I64_STORE: u8 = 0x37;
 pub const F32_STORE: u8 = 0x38;
 pub const F64_STORE: u8 = 0x39;
+pub const I32_STORE8: u8 = 0x3A;
+pub const I32_STORE16: u8 = 0x3B;
+pub const I64_STORE8: u8 = 0x3C;
+pub const I64_STORE16: u8 = 0x3D;
+pub const I64_STORE32: u8 = 0x3E;
+pub const MEMORY_SIZE: u8 = 0x3F;
+pub const MEMORY_GROW: u8 = 0x40;
 pub const I32_CONST: u8 = 0x41;
 pub const I64_CONST: u8 = 0x42;
 pub const F32_CONST: u8 = 0x43;
@@ -115,8 +148,300 @@ pub const F64_DIV: u8 = 0xA3;
 pub const F64_MIN: u8 = 0xA4;
 pub const F64_MAX: u8 = 0xA5;
 pub const F64_COPYSIGN: u8 = 0xA6;
+pub const I32_WRAP_I64: u8 = 0xA7;
+pub const I32_TRUNC_F32_S: u8 = 0xA8;
+pub const I32_TRUNC_F32_U: u8 = 0xA9;
+pub const I32_TRUNC_F64_S: u8 = 0xAA;
+pub const I32_TRUNC_F64_U: u8 = 0xAB;
+pub const I64_EXTEND_I32_S: u8 = 0xAC;
+pub const I64_EXTEND_I32_U: u8 = 0xAD;
+pub const I64_TRUNC_F32_S: u8 = 0xAE;
+pub const I64_TRUNC_F32_U: u8 = 0xAF;
+pub const I64_TRUNC_F64_S: u8 = 0xB0;
+pub const I64_TRUNC_F64_U: u8 = 0xB1;
 pub const F32_CONVERT_I32_S: u8 = 0xB2;
 pub const F32_CONVERT_I32_U: u8 = 0xB3;
 pub const F32_CONVERT_I64_S: u8 = 0xB4;
 pub const F32_CONVERT_I64_U: u8 = 0xB5;
+pub const F32_DEMOTE_F64: u8 = 0xB6;
+pub const F64_CONVERT_I32_S: u8 = 0xB7;
+pub const F64_CONVERT_I32_U: u8 = 0xB8;
+pub const F64_CONVERT_I64_S: u8 = 0xB9;
+pub const F64_CONVERT_I64_U: u8 = 0xBA;
+pub const F64_PROMOTE_F32: u8 = 0xBB;
+pub const I32_REINTERPRET_F32: u8 = 0xBC;
+pub const I64_REINTERPRET_F64: u8 = 0xBD;
 pub const F32_REINTERPRET_I32: u8 = 0xBE;
+pub const F64_REINTERPRET_I64: u8 = 0xBF;
+pub const I32_EXTEND8_S: u8 = 0xC0;
+pub const I32_EXTEND16_S: u8 = 0xC1;
+pub const I64_EXTEND8_S: u8 = 0xC2;
+pub const I64_EXTEND16_S: u8 = 0xC3;
+pub const I64_EXTEND32_S: u8 = 0xC4;
+pub const REF_NULL: u8 = 0xD0;
+pub const REF_IS_NULL: u8 = 0xD1;
+pub const REF_FUNC: u8 = 0xD2;
+pub const FC_EXTENSIONS: u8 = 0xFC;
+pub const FD_EXTENSIONS: u8 = 0xFD;
+
+/// Sub-opcodes of the instructions with the `0xFC` prefix, which are encoded as a `u32`
+pub mod fc_extensions {
+    pub const I32_TRUNC_SAT_F32_S: u32 = 0x00;
+    pub const I32_TRUNC_SAT_F32_U: u32 = 0x01;
+    pub const I32_TRUNC_SAT_F64_S: u32 = 0x02;
+    pub const I32_TRUNC_SAT_F64_U: u32 = 0x03;
+    pub const I64_TRUNC_SAT_F32_S: u32 = 0x04;
+    pub const I64_TRUNC_SAT_F32_U: u32 = 0x05;
+    pub const I64_TRUNC_SAT_F64_S: u32 = 0x06;
+    pub const I64_TRUNC_SAT_F64_U: u32 = 0x07;
+    pub const MEMORY_INIT: u32 = 0x08;
+    pub const DATA_DROP: u32 = 0x09;
+    pub const MEMORY_COPY: u32 = 0x0A;
+    pub const MEMORY_FILL: u32 = 0x0B;
+    pub const TABLE_INIT: u32 = 0x0C;
+    pub const ELEM_DROP: u32 = 0x0D;
+    pub const TABLE_COPY: u32 = 0x0E;
+    pub const TABLE_GROW: u32 = 0x0F;
+    pub const TABLE_SIZE: u32 = 0x10;
+    pub const TABLE_FILL: u32 = 0x11;
+}
+
+/// Sub-opcodes of the vector instructions with the `0xFD` prefix, which are encoded as a `u32`
+pub mod fd_extensions {
+    pub const V128_LOAD: u32 = 0x00;
+    pub const V128_LOAD8X8_S: u32 = 0x01;
+    pub const V128_LOAD8X8_U: u32 = 0x02;
+    pub const V128_LOAD16X4_S: u32 = 0x03;
+    pub const V128_LOAD16X4_U: u32 = 0x04;
+    pub const V128_LOAD32X2_S: u32 = 0x05;
+    pub const V128_LOAD32X2_U: u32 = 0x06;
+    pub const V128_LOAD8_SPLAT: u32 = 0x07;
+    pub const V128_LOAD16_SPLAT: u32 = 0x08;
+    pub const V128_LOAD32_SPLAT: u32 = 0x09;
+    pub const V128_LOAD64_SPLAT: u32 = 0x0A;
+    pub const V128_STORE: u32 = 0x0B;
+    pub const V128_CONST: u32 = 0x0C;
+    pub const I8X16_SHUFFLE: u32 = 0x0D;
+    pub const I8X16_SWIZZLE: u32 = 0x0E;
+    pub const I8X16_SPLAT: u32 = 0x0F;
+    pub const I16X8_SPLAT: u32 = 0x10;
+    pub const I32X4_SPLAT: u32 = 0x11;
+    pub const I64X2_SPLAT: u32 = 0x12;
+    pub const F32X4_SPLAT: u32 = 0x13;
+    pub const F64X2_SPLAT: u32 = 0x14;
+    pub const I8X16_EXTRACT_LANE_S: u32 = 0x15;
+    pub const I8X16_EXTRACT_LANE_U: u32 = 0x16;
+    pub const I8X16_REPLACE_LANE: u32 = 0x17;
+    pub const I16X8_EXTRACT_LANE_S: u32 = 0x18;
+    pub const I16X8_EXTRACT_LANE_U: u32 = 0x19;
+    pub const I16X8_REPLACE_LANE: u32 = 0x1A;
+    pub const I32X4_EXTRACT_LANE: u32 = 0x1B;
+    pub const I32X4_REPLACE_LANE: u32 = 0x1C;
+    pub const I64X2_EXTRACT_LANE: u32 = 0x1D;
+    pub const I64X2_REPLACE_LANE: u32 = 0x1E;
+    pub const F32X4_EXTRACT_LANE: u32 = 0x1F;
+    pub const F32X4_REPLACE_LANE: u32 = 0x20;
+    pub const F64X2_EXTRACT_LANE: u32 = 0x21;
+    pub const F64X2_REPLACE_LANE: u32 = 0x22;
+    pub const I8X16_EQ: u32 = 0x23;
+    pub const I8X16_NE: u32 = 0x24;
+    pub const I8X16_LT_S: u32 = 0x25;
+    pub const I8X16_LT_U: u32 = 0x26;
+    pub const I8X16_GT_S: u32 = 0x27;
+    pub const I8X16_GT_U: u32 = 0x28;
+    pub const I8X16_LE_S: u32 = 0x29;
+    pub const I8X16_LE_U: u32 = 0x2A;
+    pub const I8X16_GE_S: u32 = 0x2B;
+    pub const I8X16_GE_U: u32 = 0x2C;
+    pub const I16X8_EQ: u32 = 0x2D;
+    pub const I16X8_NE: u32 = 0x2E;
+    pub const I16X8_LT_S: u32 = 0x2F;
+    pub const I16X8_LT_U: u32 = 0x30;
+    pub const I16X8_GT_S: u32 = 0x31;
+    pub const I16X8_GT_U: u32 = 0x32;
+    pub const I16X8_LE_S: u32 = 0x33;
+    pub const I16X8_LE_U: u32 = 0x34;
+    pub const I16X8_GE_S: u32 = 0x35;
+    pub const I16X8_GE_U: u32 = 0x36;
+    pub const I32X4_EQ: u32 = 0x37;
+    pub const I32X4_NE: u32 = 0x38;
+    pub const I32X4_LT_S: u32 = 0x39;
+    pub const I32X4_LT_U: u32 = 0x3A;
+    pub const I32X4_GT_S: u32 = 0x3B;
+    pub const I32X4_GT_U: u32 = 0x3C;
+    pub const I32X4_LE_S: u32 = 0x3D;
+    pub const I32X4_LE_U: u32 = 0x3E;
+    pub const I32X4_GE_S: u32 = 0x3F;
+    pub const I32X4_GE_U: u32 = 0x40;
+    pub const F32X4_EQ: u32 = 0x41;
+    pub const F32X4_NE: u32 = 0x42;
+    pub const F32X4_LT: u32 = 0x43;
+    pub const F32X4_GT: u32 = 0x44;
+    pub const F32X4_LE: u32 = 0x45;
+    pub const F32X4_GE: u32 = 0x46;
+    pub const F64X2_EQ: u32 = 0x47;
+    pub const F64X2_NE: u32 = 0x48;
+    pub const F64X2_LT: u32 = 0x49;
+    pub const F64X2_GT: u32 = 0x4A;
+    pub const F64X2_LE: u32 = 0x4B;
+    pub const F64X2_GE: u32 = 0x4C;
+    pub const V128_NOT: u32 = 0x4D;
+    pub const V128_AND: u32 = 0x4E;
+    pub const V128_ANDNOT: u32 = 0x4F;
+    pub const V128_OR: u32 = 0x50;
+    pub const V128_XOR: u32 = 0x51;
+    pub const V128_BITSELECT: u32 = 0x52;
+    pub const V128_ANY_TRUE: u32 = 0x53;
+    pub const V128_LOAD8_LANE: u32 = 0x54;
+    pub const V128_LOAD16_LANE: u32 = 0x55;
+    pub const V128_LOAD32_LANE: u32 = 0x56;
+    pub const V128_LOAD64_LANE: u32 = 0x57;
+    pub const V128_STORE8_LANE: u32 = 0x58;
+    pub const V128_STORE16_LANE: u32 = 0x59;
+    pub const V128_STORE32_LANE: u32 = 0x5A;
+    pub const V128_STORE64_LANE: u32 = 0x5B;
+    pub const V128_LOAD32_ZERO: u32 = 0x5C;
+    pub const V128_LOAD64_ZERO: u32 = 0x5D;
+    pub const F32X4_DEMOTE_F64X2_ZERO: u32 = 0x5E;
+    pub const F64X2_PROMOTE_LOW_F32X4: u32 = 0x5F;
+    pub const I8X16_ABS: u32 = 0x60;
+    pub const I8X16_NEG: u32 = 0x61;
+    pub const I8X16_POPCNT: u32 = 0x62;
+    pub const I8X16_ALL_TRUE: u32 = 0x63;
+    pub const I8X16_BITMASK: u32 = 0x64;
+    pub const I8X16_NARROW_I16X8_S: u32 = 0x65;
+    pub const I8X16_NARROW_I16X8_U: u32 = 0x66;
+    pub const F32X4_CEIL: u32 = 0x67;
+    pub const F32X4_FLOOR: u32 = 0x68;
+    pub const F32X4_TRUNC: u32 = 0x69;
+    pub const F32X4_NEAREST: u32 = 0x6A;
+    pub const I8X16_SHL: u32 = 0x6B;
+    pub const I8X16_SHR_S: u32 = 0x6C;
+    pub const I8X16_SHR_U: u32 = 0x6D;
+    pub const I8X16_ADD: u32 = 0x6E;
+    pub const I8X16_ADD_SAT_S: u32 = 0x6F;
+    pub const I8X16_ADD_SAT_U: u32 = 0x70;
+    pub const I8X16_SUB: u32 = 0x71;
+    pub const I8X16_SUB_SAT_S: u32 = 0x72;
+    pub const I8X16_SUB_SAT_U: u32 = 0x73;
+    pub const F64X2_CEIL: u32 = 0x74;
+    pub const F64X2_FLOOR: u32 = 0x75;
+    pub const I8X16_MIN_S: u32 = 0x76;
+    pub const I8X16_MIN_U: u32 = 0x77;
+    pub const I8X16_MAX_S: u32 = 0x78;
+    pub const I8X16_MAX_U: u32 = 0x79;
+    pub const F64X2_TRUNC: u32 = 0x7A;
+    pub const I8X16_AVGR_U: u32 = 0x7B;
+    pub const I16X8_EXTADD_PAIRWISE_I8X16_S: u32 = 0x7C;
+    pub const I16X8_EXTADD_PAIRWISE_I8X16_U: u32 = 0x7D;
+    pub const I32X4_EXTADD_PAIRWISE_I16X8_S: u32 = 0x7E;
+    pub const I32X4_EXTADD_PAIRWISE_I16X8_U: u32 = 0x7F;
+    pub const I16X8_ABS: u32 = 0x80;
+    pub const I16X8_NEG: u32 = 0x81;
+    pub const I16X8_Q15MULR_SAT_S: u32 = 0x82;
+    pub const I16X8_ALL_TRUE: u32 = 0x83;
+    pub const I16X8_BITMASK: u32 = 0x84;
+    pub const I16X8_NARROW_I32X4_S: u32 = 0x85;
+    pub const I16X8_NARROW_I32X4_U: u32 = 0x86;
+    pub const I16X8_EXTEND_LOW_I8X16_S: u32 = 0x87;
+    pub const I16X8_EXTEND_HIGH_I8X16_S: u32 = 0x88;
+    pub const I16X8_EXTEND_LOW_I8X16_U: u32 = 0x89;
+    pub const I16X8_EXTEND_HIGH_I8X16_U: u32 = 0x8A;
+    pub const I16X8_SHL: u32 = 0x8B;
+    pub const I16X8_SHR_S: u32 = 0x8C;
+    pub const I16X8_SHR_U: u32 = 0x8D;
+    pub const I16X8_ADD: u32 = 0x8E;
+    pub const I16X8_ADD_SAT_S: u32 = 0x8F;
+    pub const I16X8_ADD_SAT_U: u32 = 0x90;
+    pub const I16X8_SUB: u32 = 0x91;
+    pub const I16X8_SUB_SAT_S: u32 = 0x92;
+    pub const I16X8_SUB_SAT_U: u32 = 0x93;
+    pub const F64X2_NEAREST: u32 = 0x94;
+    pub const I16X8_MUL: u32 = 0x95;
+    pub const I16X8_MIN_S: u32 = 0x96;
+    pub const I16X8_MIN_U: u32 = 0x97;
+    pub const I16X8_MAX_S: u32 = 0x98;
+    pub const I16X8_MAX_U: u32 = 0x99;
+    pub const I16X8_AVGR_U: u32 = 0x9B;
+    pub const I16X8_EXTMUL_LOW_I8X16_S: u32 = 0x9C;
+    pub const I16X8_EXTMUL_HIGH_I8X16_S: u32 = 0x9D;
+    pub const I16X8_EXTMUL_LOW_I8X16_U: u32 = 0x9E;
+    pub const I16X8_EXTMUL_HIGH_I8X16_U: u32 = 0x9F;
+    pub const I32X4_ABS: u32 = 0xA0;
+    pub const I32X4_NEG: u32 = 0xA1;
+    pub const I32X4_ALL_TRUE: u32 = 0xA3;
+    pub const I32X4_BITMASK: u32 = 0xA4;
+    pub const I32X4_EXTEND_LOW_I16X8_S: u32 = 0xA7;
+    pub const I32X4_EXTEND_HIGH_I16X8_S: u32 = 0xA8;
+    pub const I32X4_EXTEND_LOW_I16X8_U: u32 = 0xA9;
+    pub const I32X4_EXTEND_HIGH_I16X8_U: u32 = 0xAA;
+    pub const I32X4_SHL: u32 = 0xAB;
+    pub const I32X4_SHR_S: u32 = 0xAC;
+    pub const I32X4_SHR_U: u32 = 0xAD;
+    pub const I32X4_ADD: u32 = 0xAE;
+    pub const I32X4_SUB: u32 = 0xB1;
+    pub const I32X4_MUL: u32 = 0xB5;
+    pub const I32X4_MIN_S: u32 = 0xB6;
+    pub const I32X4_MIN_U: u32 = 0xB7;
+    pub const I32X4_MAX_S: u32 = 0xB8;
+    pub const I32X4_MAX_U: u32 = 0xB9;
+    pub const I32X4_DOT_I16X8_S: u32 = 0xBA;
+    pub const I32X4_EXTMUL_LOW_I16X8_S: u32 = 0xBC;
+    pub const I32X4_EXTMUL_HIGH_I16X8_S: u32 = 0xBD;
+    pub const I32X4_EXTMUL_LOW_I16X8_U: u32 = 0xBE;
+    pub const I32X4_EXTMUL_HIGH_I16X8_U: u32 = 0xBF;
+    pub const I64X2_ABS: u32 = 0xC0;
+    pub const I64X2_NEG: u32 = 0xC1;
+    pub const I64X2_ALL_TRUE: u32 = 0xC3;
+    pub const I64X2_BITMASK: u32 = 0xC4;
+    pub const I64X2_EXTEND_LOW_I32X4_S: u32 = 0xC7;
+    pub const I64X2_EXTEND_HIGH_I32X4_S: u32 = 0xC8;
+    pub const I64X2_EXTEND_LOW_I32X4_U: u32 = 0xC9;
+    pub const I64X2_EXTEND_HIGH_I32X4_U: u32 = 0xCA;
+    pub const I64X2_SHL: u32 = 0xCB;
+    pub const I64X2_SHR_S: u32 = 0xCC;
+    pub const I64X2_SHR_U: u32 = 0xCD;
+    pub const I64X2_ADD: u32 = 0xCE;
+    pub const I64X2_SUB: u32 = 0xD1;
+    pub const I64X2_MUL: u32 = 0xD5;
+    pub const I64X2_EQ: u32 = 0xD6;
+    pub const I64X2_NE: u32 = 0xD7;
+    pub const I64X2_LT_S: u32 = 0xD8;
+    pub const I64X2_GT_S: u32 = 0xD9;
+    pub const I64X2_LE_S: u32 = 0xDA;
+    pub const I64X2_GE_S: u32 = 0xDB;
+    pub const I64X2_EXTMUL_LOW_I32X4_S: u32 = 0xDC;
+    pub const I64X2_EXTMUL_HIGH_I32X4_S: u32 = 0xDD;
+    pub const I64X2_EXTMUL_LOW_I32X4_U: u32 = 0xDE;
+    pub const I64X2_EXTMUL_HIGH_I32X4_U: u32 = 0xDF;
+    pub const F32X4_ABS: u32 = 0xE0;
+    pub const F32X4_NEG: u32 = 0xE1;
+    pub const F32X4_SQRT: u32 = 0xE3;
+    pub const F32X4_ADD: u32 = 0xE4;
+    pub const F32X4_SUB: u32 = 0xE5;
+    pub const F32X4_MUL: u32 = 0xE6;
+    pub const F32X4_DIV: u32 = 0xE7;
+    pub const F32X4_MIN: u32 = 0xE8;
+    pub const F32X4_MAX: u32 = 0xE9;
+    pub const F32X4_PMIN: u32 = 0xEA;
+    pub const F32X4_PMAX: u32 = 0xEB;
+    pub const F64X2_ABS: u32 = 0xEC;
+    pub const F64X2_NEG: u32 = 0xED;
+    pub const F64X2_SQRT: u32 = 0xEF;
+    pub const F64X2_ADD: u32 = 0xF0;
+    pub const F64X2_SUB: u32 = 0xF1;
+    pub const F64X2_MUL: u32 = 0xF2;
+    pub const F64X2_DIV: u32 = 0xF3;
+    pub const F64X2_MIN: u32 = 0xF4;
+    pub const F64X2_MAX: u32 = 0xF5;
+    pub const F64X2_PMIN: u32 = 0xF6;
+    pub const F64X2_PMAX: u32 = 0xF7;
+    pub const I32X4_TRUNC_SAT_F32X4_S: u32 = 0xF8;
+    pub const I32X4_TRUNC_SAT_F32X4_U: u32 = 0xF9;
+    pub const F32X4_CONVERT_I32X4_S: u32 = 0xFA;
+    pub const F32X4_CONVERT_I32X4_U: u32 = 0xFB;
+    pub const I32X4_TRUNC_SAT_F64X2_S_ZERO: u32 = 0xFC;
+    pub const I32X4_TRUNC_SAT_F64X2_U_ZERO: u32 = 0xFD;
+    pub const F64X2_CONVERT_LOW_I32X4_S: u32 = 0xFE;
+    pub const F64X2_CONVERT_LOW_I32X4_U: u32 = 0xFF;
+}
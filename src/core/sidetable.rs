@@ -0,0 +1,50 @@
+//! The side-table, which enables in-place interpretation of structured control flow.
+//!
+//! While validating a function, an entry is emitted for every branch site (`br`, `br_if`, every
+//! label of `br_table`, `if` and `else`), in the order they appear in the bytecode. At runtime the
+//! interpreter keeps a side-table pointer (`stp`) that always points to the entry of the next
+//! branch site. Taking a branch is then a constant-time operation that only applies the entry's
+//! adjustments, without ever scanning the bytecode for the matching `else`/`end`.
+//!
+//! See: `A fast in-place interpreter` by Ben L. Titzer: <https://arxiv.org/abs/2205.01183>
+
+use alloc::vec::Vec;
+
+/// All side-table entries of a module, the entries of each function are stored consecutively
+pub type Sidetable = Vec<SidetableEntry>;
+
+/// The adjustments to make when a branch is taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SidetableEntry {
+    /// Amount to add to the PC of the branch instruction to get the PC of the branch target
+    pub delta_pc: isize,
+
+    /// Amount to add to the index of this entry to get the side-table pointer at the branch target
+    pub delta_stp: isize,
+
+    /// Number of values that are carried over to the branch target
+    pub valcnt: usize,
+
+    /// Number of values below the carried values that are discarded when taking the branch
+    pub popcnt: usize,
+}
+
+/// A branch to the end of a block, which can only be turned into a [`SidetableEntry`] once the
+/// block's end is reached during validation
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IncompleteSidetableEntry {
+    /// PC of the branch instruction
+    pub branch_pc: usize,
+
+    /// Index of the placeholder entry in the side-table
+    pub stp: usize,
+}
+
+impl IncompleteSidetableEntry {
+    /// Fill in the placeholder entry now that the branch target is known
+    pub fn complete(self, sidetable: &mut Sidetable, target_pc: usize, target_stp: usize) {
+        let entry = &mut sidetable[self.stp];
+        entry.delta_pc = target_pc as isize - self.branch_pc as isize;
+        entry.delta_stp = target_stp as isize - self.stp as isize;
+    }
+}
@@ -0,0 +1,652 @@
+//! Coverage instrumentation, implemented as [`HookSet`]s.
+//!
+//! [`StatementCoverage`] records which instructions were executed, and how often. The resulting
+//! [`CoverageMap`] is keyed by the index of a function and the offset of an instruction relative to
+//! the function's body, so maps recorded by different instances of the same module, e.g. one for
+//! each test case, can be merged into a single map.
+//!
+//! [`DecisionCoverage`] records the outcomes of every decision, i.e. of every `if`, `br_if`,
+//! `br_table` and `select`. Comparing the resulting [`DecisionMap`] to the decisions found in a
+//! module by [`RuntimeInstance::decisions`] yields a [`DecisionReport`] listing the outcomes that
+//! were never taken.
+//!
+//! [`McdcCoverage`] additionally records the conditions evaluated for each decision, i.e. the
+//! operands and results of `i32.eqz`, `i64.eqz` and scalar comparison instructions. The resulting
+//! [`McdcMap`] shows which conditions independently affect the outcome of their decision, as
+//! required by modified condition/decision coverage (MC/DC).
+//!
+//! See: REQ-8, REQ-9, REQ-10
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::core::indices::FuncIdx;
+use crate::execution::hooks::{BranchOutcome, HookAction, HookSet, Location};
+use crate::execution::value::Value;
+use crate::execution::RuntimeInstance;
+use crate::RuntimeError;
+
+/// The number of times each executed instruction was executed, per function
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CoverageMap {
+    funcs: BTreeMap<FuncIdx, BTreeMap<usize, u64>>,
+}
+
+impl CoverageMap {
+    /// Create an empty coverage map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one execution of the instruction at `location`
+    pub fn record(&mut self, location: Location) {
+        self.record_hits(location, 1);
+    }
+
+    /// Returns how often the instruction at `location` was executed
+    pub fn hits(&self, location: Location) -> u64 {
+        self.funcs
+            .get(&location.func_idx)
+            .and_then(|offsets| offsets.get(&location.offset))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns whether the instruction at `location` was executed at least once
+    pub fn is_covered(&self, location: Location) -> bool {
+        self.hits(location) > 0
+    }
+
+    /// Returns the offsets of all executed instructions of a function, with their number of hits
+    pub fn func(&self, func_idx: FuncIdx) -> Option<&BTreeMap<usize, u64>> {
+        self.funcs.get(&func_idx)
+    }
+
+    /// Returns the executed instructions of all functions, with their number of hits
+    pub fn funcs(&self) -> &BTreeMap<FuncIdx, BTreeMap<usize, u64>> {
+        &self.funcs
+    }
+
+    /// Iterate over the locations of all executed instructions with their number of hits, ordered
+    /// by function and offset
+    pub fn iter(&self) -> impl Iterator<Item = (Location, u64)> + '_ {
+        self.funcs.iter().flat_map(|(&func_idx, offsets)| {
+            offsets
+                .iter()
+                .map(move |(&offset, &hits)| (Location { func_idx, offset }, hits))
+        })
+    }
+
+    /// Returns the number of distinct instructions that were executed
+    pub fn len(&self) -> usize {
+        self.funcs.values().map(BTreeMap::len).sum()
+    }
+
+    /// Returns whether no instruction was executed
+    pub fn is_empty(&self) -> bool {
+        self.funcs.is_empty()
+    }
+
+    /// Add the hits recorded in `other` to this map
+    ///
+    /// Both maps must have been recorded for the same module.
+    pub fn merge(&mut self, other: &CoverageMap) {
+        for (location, hits) in other.iter() {
+            self.record_hits(location, hits);
+        }
+    }
+
+    fn record_hits(&mut self, location: Location, hits: u64) {
+        let entry = self
+            .funcs
+            .entry(location.func_idx)
+            .or_default()
+            .entry(location.offset)
+            .or_default();
+        *entry = entry.saturating_add(hits);
+    }
+}
+
+/// Maps can be collected from the pairs of locations and hits yielded by [`CoverageMap::iter`],
+/// e.g. to restore a map that was exported before
+impl FromIterator<(Location, u64)> for CoverageMap {
+    fn from_iter<T: IntoIterator<Item = (Location, u64)>>(hits: T) -> Self {
+        let mut map = Self::new();
+        for (location, hits) in hits {
+            map.record_hits(location, hits);
+        }
+        map
+    }
+}
+
+/// A hook set recording statement coverage, i.e. which instructions were executed
+///
+/// Every instruction except `end` and `else` counts as a statement. These only delimit blocks and
+/// are skipped by branches, so they would appear to be missed even if their block was executed
+/// completely.
+#[derive(Debug, Default, Clone)]
+pub struct StatementCoverage {
+    map: CoverageMap,
+}
+
+impl StatementCoverage {
+    /// Returns the coverage recorded so far
+    pub fn map(&self) -> &CoverageMap {
+        &self.map
+    }
+
+    /// Returns the coverage recorded so far, and starts recording a new map
+    pub fn take_map(&mut self) -> CoverageMap {
+        core::mem::take(&mut self.map)
+    }
+}
+
+impl HookSet for StatementCoverage {
+    fn instruction_hook(&mut self, bytecode: &[u8], pc: usize, location: Location) -> HookAction {
+        use crate::core::reader::types::opcode::{ELSE, END};
+        if !matches!(bytecode[pc], END | ELSE) {
+            self.map.record(location);
+        }
+        HookAction::Continue
+    }
+}
+
+/// The kind of a decision, which determines its possible outcomes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecisionKind {
+    If,
+    BrIf,
+    /// A `br_table` with the given number of labels, not counting its default label
+    BrTable {
+        num_labels: u32,
+    },
+    Select,
+}
+
+impl DecisionKind {
+    /// Returns all outcomes a decision of this kind can have
+    pub fn outcomes(&self) -> Vec<BranchOutcome> {
+        match self {
+            Self::If | Self::BrIf | Self::Select => {
+                alloc::vec![BranchOutcome::Taken, BranchOutcome::NotTaken]
+            }
+            Self::BrTable { num_labels } => (0..=*num_labels).map(BranchOutcome::Table).collect(),
+        }
+    }
+
+    /// Returns the name of the instruction making the decision
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::If => "if",
+            Self::BrIf => "br_if",
+            Self::BrTable { .. } => "br_table",
+            Self::Select => "select",
+        }
+    }
+}
+
+/// A decision in the code of a function
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Decision {
+    pub location: Location,
+    pub kind: DecisionKind,
+}
+
+impl<'b, H> RuntimeInstance<'b, H>
+where
+    H: HookSet,
+{
+    /// Returns all decisions in the code of the instance's functions, ordered by function and offset
+    ///
+    /// The decisions are found while the module is validated. Imported functions do not contain
+    /// any decisions.
+    pub fn decisions(&self) -> Vec<Decision> {
+        self.decisions.clone()
+    }
+}
+
+/// The number of times each outcome of each executed decision was taken
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DecisionMap {
+    decisions: BTreeMap<Location, BTreeMap<BranchOutcome, u64>>,
+}
+
+impl DecisionMap {
+    /// Create an empty decision map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that the decision at `location` had the given outcome once
+    pub fn record(&mut self, location: Location, outcome: BranchOutcome) {
+        self.record_hits(location, outcome, 1);
+    }
+
+    /// Returns how often the decision at `location` had the given outcome
+    pub fn hits(&self, location: Location, outcome: BranchOutcome) -> u64 {
+        self.decisions
+            .get(&location)
+            .and_then(|outcomes| outcomes.get(&outcome))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the outcomes the decision at `location` had, with their number of hits
+    pub fn outcomes(&self, location: Location) -> Option<&BTreeMap<BranchOutcome, u64>> {
+        self.decisions.get(&location)
+    }
+
+    /// Iterate over all outcomes of all executed decisions with their number of hits, ordered by
+    /// function, offset and outcome
+    pub fn iter(&self) -> impl Iterator<Item = (Location, BranchOutcome, u64)> + '_ {
+        self.decisions.iter().flat_map(|(&location, outcomes)| {
+            outcomes
+                .iter()
+                .map(move |(&outcome, &hits)| (location, outcome, hits))
+        })
+    }
+
+    /// Returns the number of distinct decisions that were executed
+    pub fn len(&self) -> usize {
+        self.decisions.len()
+    }
+
+    /// Returns whether no decision was executed
+    pub fn is_empty(&self) -> bool {
+        self.decisions.is_empty()
+    }
+
+    /// Add the hits recorded in `other` to this map
+    ///
+    /// Both maps must have been recorded for the same module.
+    pub fn merge(&mut self, other: &DecisionMap) {
+        for (location, outcome, hits) in other.iter() {
+            self.record_hits(location, outcome, hits);
+        }
+    }
+
+    /// Compare this map to all `decisions` of a module, as returned by
+    /// [`RuntimeInstance::decisions`], listing the outcomes which were never taken
+    pub fn report(&self, decisions: &[Decision]) -> DecisionReport {
+        let mut report = DecisionReport::default();
+
+        for decision in decisions {
+            let outcomes = decision.kind.outcomes();
+            let missing: Vec<BranchOutcome> = outcomes
+                .iter()
+                .copied()
+                .filter(|&outcome| self.hits(decision.location, outcome) == 0)
+                .collect();
+
+            report.num_outcomes += outcomes.len();
+            report.num_covered_outcomes += outcomes.len() - missing.len();
+            if !missing.is_empty() {
+                report
+                    .funcs
+                    .entry(decision.location.func_idx)
+                    .or_default()
+                    .push(UncoveredDecision {
+                        decision: *decision,
+                        missing,
+                    });
+            }
+        }
+
+        report
+    }
+
+    fn record_hits(&mut self, location: Location, outcome: BranchOutcome, hits: u64) {
+        let entry = self
+            .decisions
+            .entry(location)
+            .or_default()
+            .entry(outcome)
+            .or_default();
+        *entry = entry.saturating_add(hits);
+    }
+}
+
+/// Maps can be collected from the triples of locations, outcomes and hits yielded by
+/// [`DecisionMap::iter`], e.g. to restore a map that was exported before
+impl FromIterator<(Location, BranchOutcome, u64)> for DecisionMap {
+    fn from_iter<T: IntoIterator<Item = (Location, BranchOutcome, u64)>>(hits: T) -> Self {
+        let mut map = Self::new();
+        for (location, outcome, hits) in hits {
+            map.record_hits(location, outcome, hits);
+        }
+        map
+    }
+}
+
+/// A decision for which not all outcomes were taken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UncoveredDecision {
+    pub decision: Decision,
+    /// The outcomes which were never taken
+    pub missing: Vec<BranchOutcome>,
+}
+
+/// The decisions of a module which were not fully covered, per function
+///
+/// The report is displayed as one line per uncovered decision, grouped by function.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DecisionReport {
+    funcs: BTreeMap<FuncIdx, Vec<UncoveredDecision>>,
+    num_outcomes: usize,
+    num_covered_outcomes: usize,
+}
+
+impl DecisionReport {
+    /// Returns the uncovered decisions of all functions that have any, ordered by offset
+    pub fn funcs(&self) -> &BTreeMap<FuncIdx, Vec<UncoveredDecision>> {
+        &self.funcs
+    }
+
+    /// Returns the uncovered decisions of a function, ordered by offset
+    pub fn func(&self, func_idx: FuncIdx) -> &[UncoveredDecision] {
+        self.funcs.get(&func_idx).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns whether every outcome of every decision was taken
+    pub fn is_complete(&self) -> bool {
+        self.funcs.is_empty()
+    }
+
+    /// Returns the number of possible outcomes of all decisions
+    pub fn num_outcomes(&self) -> usize {
+        self.num_outcomes
+    }
+
+    /// Returns the number of outcomes of all decisions that were taken at least once
+    pub fn num_covered_outcomes(&self) -> usize {
+        self.num_covered_outcomes
+    }
+}
+
+impl Display for DecisionReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} decision outcomes covered",
+            self.num_covered_outcomes, self.num_outcomes
+        )?;
+        for (func_idx, uncovered) in &self.funcs {
+            writeln!(f, "function {func_idx}:")?;
+            for UncoveredDecision { decision, missing } in uncovered {
+                writeln!(
+                    f,
+                    "  {} at offset {}: missing {missing:?}",
+                    decision.kind.name(),
+                    decision.location.offset
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A hook set recording decision coverage, i.e. which outcomes every executed decision had
+#[derive(Debug, Default, Clone)]
+pub struct DecisionCoverage {
+    map: DecisionMap,
+}
+
+impl DecisionCoverage {
+    /// Returns the coverage recorded so far
+    pub fn map(&self) -> &DecisionMap {
+        &self.map
+    }
+
+    /// Returns the coverage recorded so far, and starts recording a new map
+    pub fn take_map(&mut self) -> DecisionMap {
+        core::mem::take(&mut self.map)
+    }
+}
+
+impl HookSet for DecisionCoverage {
+    fn branch_hook(&mut self, location: Location, outcome: BranchOutcome) {
+        self.map.record(location, outcome);
+    }
+}
+
+/// The evaluation of a condition, i.e. of an `i32.eqz`, `i64.eqz` or scalar comparison instruction
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub location: Location,
+    pub operands: Vec<Value>,
+    pub result: bool,
+}
+
+/// A distinct combination of condition results with the outcome of the decision they fed
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestVector {
+    /// The conditions in the order they were evaluated, with the operands of the first evaluation
+    /// that produced this test vector
+    pub conditions: Vec<Condition>,
+    pub outcome: BranchOutcome,
+    /// The number of evaluations that produced this test vector
+    pub hits: u64,
+}
+
+impl TestVector {
+    /// Returns the result of the condition at `location`, if it was evaluated
+    ///
+    /// If it was evaluated more than once, the last result is returned.
+    pub fn result_of(&self, location: Location) -> Option<bool> {
+        self.conditions
+            .iter()
+            .rev()
+            .find(|condition| condition.location == location)
+            .map(|condition| condition.result)
+    }
+
+    /// Returns whether this test vector has the same condition results and outcome as another,
+    /// regardless of the operands
+    fn matches(&self, conditions: &[Condition], outcome: BranchOutcome) -> bool {
+        self.outcome == outcome
+            && self.conditions.len() == conditions.len()
+            && self
+                .conditions
+                .iter()
+                .zip(conditions)
+                .all(|(a, b)| a.location == b.location && a.result == b.result)
+    }
+}
+
+/// The test vectors of every executed decision
+///
+/// The conditions of a test vector are those evaluated by the decision's function since its
+/// previous decision, so conditions whose result is stored and only used by a later decision are
+/// attributed to the next one.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct McdcMap {
+    decisions: BTreeMap<Location, Vec<TestVector>>,
+}
+
+impl McdcMap {
+    /// Create an empty MC/DC map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one evaluation of the decision at `location`, fed by the given conditions
+    pub fn record(
+        &mut self,
+        location: Location,
+        conditions: Vec<Condition>,
+        outcome: BranchOutcome,
+    ) {
+        self.record_hits(location, conditions, outcome, 1);
+    }
+
+    /// Returns the distinct test vectors of the decision at `location`, in the order in which they
+    /// were first recorded
+    pub fn test_vectors(&self, location: Location) -> &[TestVector] {
+        self.decisions.get(&location).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over all executed decisions with their test vectors, ordered by function and offset
+    pub fn iter(&self) -> impl Iterator<Item = (Location, &[TestVector])> + '_ {
+        self.decisions
+            .iter()
+            .map(|(&location, test_vectors)| (location, test_vectors.as_slice()))
+    }
+
+    /// Returns the locations of all conditions evaluated for the decision at `location`
+    pub fn conditions(&self, location: Location) -> BTreeSet<Location> {
+        self.test_vectors(location)
+            .iter()
+            .flat_map(|test_vector| &test_vector.conditions)
+            .map(|condition| condition.location)
+            .collect()
+    }
+
+    /// Returns a pair of test vectors showing that the condition at `condition` independently
+    /// affects the outcome of the decision at `decision`
+    ///
+    /// In such a pair, the condition has different results and the decision has different
+    /// outcomes, while every other condition evaluated in both test vectors has the same result.
+    /// Conditions evaluated in only one of them are considered masked, e.g. by short-circuiting.
+    pub fn independence_pair(
+        &self,
+        decision: Location,
+        condition: Location,
+    ) -> Option<(&TestVector, &TestVector)> {
+        let test_vectors = self.test_vectors(decision);
+
+        test_vectors.iter().enumerate().find_map(|(i, a)| {
+            let a_result = a.result_of(condition)?;
+            test_vectors[i + 1..]
+                .iter()
+                .find(|b| {
+                    b.result_of(condition) == Some(!a_result)
+                        && b.outcome != a.outcome
+                        && a.conditions.iter().all(|other| {
+                            other.location == condition
+                                || b.result_of(other.location).map_or(true, |result| {
+                                    Some(result) == a.result_of(other.location)
+                                })
+                        })
+                })
+                .map(|b| (a, b))
+        })
+    }
+
+    /// Returns whether the condition at `condition` was shown to independently affect the outcome
+    /// of the decision at `decision`, see [`McdcMap::independence_pair`]
+    pub fn is_independent(&self, decision: Location, condition: Location) -> bool {
+        self.independence_pair(decision, condition).is_some()
+    }
+
+    /// Returns the number of distinct decisions that were executed
+    pub fn len(&self) -> usize {
+        self.decisions.len()
+    }
+
+    /// Returns whether no decision was executed
+    pub fn is_empty(&self) -> bool {
+        self.decisions.is_empty()
+    }
+
+    /// Add the test vectors recorded in `other` to this map
+    ///
+    /// Both maps must have been recorded for the same module.
+    pub fn merge(&mut self, other: &McdcMap) {
+        for (location, test_vectors) in other.iter() {
+            for test_vector in test_vectors {
+                self.record_hits(
+                    location,
+                    test_vector.conditions.clone(),
+                    test_vector.outcome,
+                    test_vector.hits,
+                );
+            }
+        }
+    }
+
+    fn record_hits(
+        &mut self,
+        location: Location,
+        conditions: Vec<Condition>,
+        outcome: BranchOutcome,
+        hits: u64,
+    ) {
+        let test_vectors = self.decisions.entry(location).or_default();
+        match test_vectors
+            .iter_mut()
+            .find(|test_vector| test_vector.matches(&conditions, outcome))
+        {
+            Some(test_vector) => test_vector.hits = test_vector.hits.saturating_add(hits),
+            None => test_vectors.push(TestVector {
+                conditions,
+                outcome,
+                hits,
+            }),
+        }
+    }
+}
+
+/// A hook set recording the data for modified condition/decision coverage (MC/DC), i.e. the test
+/// vectors of every executed decision
+#[derive(Debug, Default, Clone)]
+pub struct McdcCoverage {
+    map: McdcMap,
+    /// The conditions evaluated since the last decision, for each function on the call stack
+    pending_conditions: Vec<Vec<Condition>>,
+}
+
+impl McdcCoverage {
+    /// Returns the coverage recorded so far
+    pub fn map(&self) -> &McdcMap {
+        &self.map
+    }
+
+    /// Returns the coverage recorded so far, and starts recording a new map
+    pub fn take_map(&mut self) -> McdcMap {
+        core::mem::take(&mut self.map)
+    }
+}
+
+impl HookSet for McdcCoverage {
+    fn call_hook(
+        &mut self,
+        location: Option<Location>,
+        _callee: FuncIdx,
+        _args: &[Value],
+    ) -> HookAction {
+        // a new invocation starts with an empty call stack
+        if location.is_none() {
+            self.pending_conditions.clear();
+        }
+        self.pending_conditions.push(Vec::new());
+        HookAction::Continue
+    }
+
+    fn return_hook(&mut self, _location: Location, _callee: FuncIdx, _results: &[Value]) {
+        self.pending_conditions.pop();
+    }
+
+    fn trap_hook(&mut self, _location: Location, _error: &RuntimeError) {
+        self.pending_conditions.clear();
+    }
+
+    fn branch_hook(&mut self, location: Location, outcome: BranchOutcome) {
+        let conditions = self
+            .pending_conditions
+            .last_mut()
+            .map(core::mem::take)
+            .unwrap_or_default();
+        self.map.record(location, conditions, outcome);
+    }
+
+    fn condition_hook(&mut self, location: Location, operands: &[Value], result: bool) {
+        if let Some(conditions) = self.pending_conditions.last_mut() {
+            conditions.push(Condition {
+                location,
+                operands: operands.to_vec(),
+                result,
+            });
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Fuel bounded execution.
+//!
+//! Every instruction consumes an amount of fuel before it is executed, as defined by a
+//! [`FuelCostTable`]. Once the remaining fuel does not suffice for the next instruction, the
+//! interpreter yields back to the host with [`ExecutionOutcome::OutOfFuel`].
+//!
+//! See: REQ-5 and REQ-6
+
+use crate::core::reader::types::opcode::{FC_EXTENSIONS, FD_EXTENSIONS};
+use crate::core::reader::WasmReader;
+use crate::execution::assert_validated::UnwrapValidatedExt;
+use crate::execution::value_stack::Stack;
+use crate::{NumType, ValType};
+
+/// The result of an invocation which may be bounded by fuel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionOutcome<T> {
+    /// The invoked function returned
+    Finished(T),
+    /// Execution was halted before the next instruction, as there was not enough fuel left for it
+    OutOfFuel,
+    /// Execution was paused by a hook before the next instruction
+    Paused,
+}
+
+/// The number of sub-opcodes after the `0xFC` prefix
+const FC_SUB_OPCODES: usize = 18;
+
+/// The number of sub-opcodes after the `0xFD` prefix
+const FD_SUB_OPCODES: usize = 256;
+
+/// The amount of fuel consumed by each instruction
+///
+/// Instructions are identified by their first byte, or by their prefix byte and sub-opcode for
+/// instructions with a prefix (`0xFC` and `0xFD`). Bulk memory and table instructions additionally
+/// consume fuel for each byte or table element they access.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuelCostTable {
+    costs: [u64; 256],
+    fc_costs: [u64; FC_SUB_OPCODES],
+    fd_costs: [u64; FD_SUB_OPCODES],
+    bulk_cost_per_unit: u64,
+}
+
+impl FuelCostTable {
+    /// Create a table in which every instruction costs the same amount of fuel
+    ///
+    /// Bulk memory and table instructions do not consume any additional fuel per byte or element.
+    pub const fn uniform(cost: u64) -> Self {
+        Self {
+            costs: [cost; 256],
+            fc_costs: [cost; FC_SUB_OPCODES],
+            fd_costs: [cost; FD_SUB_OPCODES],
+            bulk_cost_per_unit: 0,
+        }
+    }
+
+    /// Set the fuel cost of the instruction `opcode`
+    ///
+    /// If `opcode` is a prefix byte (`0xFC` or `0xFD`), the cost of all instructions with that
+    /// prefix is set. Use [`Self::set_prefixed_cost`] to set their costs individually.
+    pub fn set_cost(&mut self, opcode: u8, cost: u64) {
+        self.costs[opcode as usize] = cost;
+        match opcode {
+            FC_EXTENSIONS => self.fc_costs = [cost; FC_SUB_OPCODES],
+            FD_EXTENSIONS => self.fd_costs = [cost; FD_SUB_OPCODES],
+            _ => {}
+        }
+    }
+
+    /// Returns the fuel cost of the instruction `opcode`
+    ///
+    /// For a prefix byte, this is the cost last set for all instructions with that prefix by
+    /// [`Self::set_cost`].
+    pub const fn cost(&self, opcode: u8) -> u64 {
+        self.costs[opcode as usize]
+    }
+
+    /// Set the fuel cost of the instruction with the given prefix byte and sub-opcode
+    ///
+    /// # Panics
+    /// If `prefix` is neither `0xFC` nor `0xFD`, or if there is no such sub-opcode.
+    pub fn set_prefixed_cost(&mut self, prefix: u8, sub_opcode: u32, cost: u64) {
+        let costs = match prefix {
+            FC_EXTENSIONS => &mut self.fc_costs[..],
+            FD_EXTENSIONS => &mut self.fd_costs[..],
+            _ => panic!("{prefix:#X} is not a prefix byte"),
+        };
+        *costs
+            .get_mut(sub_opcode as usize)
+            .unwrap_or_else(|| panic!("{sub_opcode:#X} is not a sub-opcode of {prefix:#X}")) = cost;
+    }
+
+    /// Returns the fuel cost of the instruction with the given prefix byte and sub-opcode
+    ///
+    /// # Panics
+    /// If `prefix` is neither `0xFC` nor `0xFD`, or if there is no such sub-opcode.
+    pub fn prefixed_cost(&self, prefix: u8, sub_opcode: u32) -> u64 {
+        let costs = match prefix {
+            FC_EXTENSIONS => &self.fc_costs[..],
+            FD_EXTENSIONS => &self.fd_costs[..],
+            _ => panic!("{prefix:#X} is not a prefix byte"),
+        };
+        *costs
+            .get(sub_opcode as usize)
+            .unwrap_or_else(|| panic!("{sub_opcode:#X} is not a sub-opcode of {prefix:#X}"))
+    }
+
+    /// Set the fuel consumed for each byte or table element accessed by a bulk memory or table
+    /// instruction, i.e. `memory.init`, `memory.copy`, `memory.fill`, `table.init`, `table.copy`
+    /// and `table.fill`, in addition to the cost of the instruction itself
+    pub fn set_bulk_cost_per_unit(&mut self, cost: u64) {
+        self.bulk_cost_per_unit = cost;
+    }
+
+    /// Returns the fuel consumed for each byte or table element accessed by a bulk memory or table
+    /// instruction
+    pub const fn bulk_cost_per_unit(&self) -> u64 {
+        self.bulk_cost_per_unit
+    }
+
+    /// Returns the fuel cost of the validated instruction at the reader's PC, given the operands on
+    /// top of the `stack`
+    pub(crate) fn instruction_cost(&self, wasm: &WasmReader, stack: &Stack) -> u64 {
+        let opcode = wasm.peek_u8().unwrap_validated();
+        if opcode != FC_EXTENSIONS && opcode != FD_EXTENSIONS {
+            return self.cost(opcode);
+        }
+
+        let mut sub_opcode_reader = wasm.clone();
+        sub_opcode_reader.pc += 1;
+        let sub_opcode = sub_opcode_reader.read_var_u32().unwrap_validated();
+        let cost = self.prefixed_cost(opcode, sub_opcode);
+
+        use crate::core::reader::types::opcode::fc_extensions::*;
+        match (opcode, sub_opcode) {
+            (
+                FC_EXTENSIONS,
+                MEMORY_INIT | MEMORY_COPY | MEMORY_FILL | TABLE_INIT | TABLE_COPY | TABLE_FILL,
+            ) => {
+                // the number of bytes or elements is the topmost operand of all these instructions
+                let n: u32 = stack.peek_value(ValType::NumType(NumType::I32)).into();
+                cost.saturating_add(self.bulk_cost_per_unit.saturating_mul(n.into()))
+            }
+            _ => cost,
+        }
+    }
+}
+
+impl Default for FuelCostTable {
+    /// Every instruction costs one unit of fuel
+    fn default() -> Self {
+        Self::uniform(1)
+    }
+}
@@ -1,6 +1,66 @@
+use crate::core::indices::{FuncIdx, GlobalIdx};
+use crate::execution::store::{FuncInst, MemInst};
+use crate::execution::value::{InteropValue, Value};
+use crate::execution::value_stack::Stack;
+use crate::RuntimeError;
+
+/// The position of an instruction within the function that contains it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    /// The function containing the instruction
+    pub func_idx: FuncIdx,
+    /// The byte offset of the instruction, relative to the start of the function's body expression
+    pub offset: usize,
+}
+
+/// The outcome of a conditional branch or `select`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BranchOutcome {
+    /// A `br_if` branched, an `if` continued with its then-branch, or a `select` selected its first
+    /// operand
+    Taken,
+    /// A `br_if` fell through, an `if` continued with its else-branch or behind its `end`, or a
+    /// `select` selected its second operand
+    NotTaken,
+    /// A `br_table` branched to the label at the given index of its label vector, where the default
+    /// label has the index of the vector's length
+    Table(u32),
+}
+
+impl From<bool> for BranchOutcome {
+    fn from(taken: bool) -> Self {
+        if taken {
+            Self::Taken
+        } else {
+            Self::NotTaken
+        }
+    }
+}
+
+/// The decision of a hook on how to continue execution
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HookAction {
+    /// Execution continues normally
+    Continue,
+    /// Execution is paused before the instruction that triggered the hook, yielding
+    /// [`ExecutionOutcome::Paused`](crate::fuel::ExecutionOutcome::Paused) to the host
+    ///
+    /// The instruction is not executed and no fuel is consumed for it. Once the invocation is
+    /// resumed, the instruction and call hooks are not called again for it, so a hook may pause
+    /// unconditionally without pausing the same invocation forever.
+    Pause,
+    /// Execution traps with [`RuntimeError::HookAbort`], carrying the given code
+    Abort(u32),
+}
+
 /// Trait that allows user specified hooks for various events during interpretation
 ///
 /// The default implementation of all trait methods are empty, i. e. can be optimized out fully.
+///
+/// The hook set is owned by the [`RuntimeInstance`](crate::RuntimeInstance), so any state it
+/// records is kept across invocations. Every hook receives the [`Location`] of the instruction that
+/// triggered it. Some hooks may steer execution by returning a [`HookAction`], e.g. to implement
+/// breakpoints or watchdogs.
 // It mus always be checked that there is no performance penalty for an empty hook!
 pub trait HookSet: Default {
     /// A hook which is called before every wasm instruction
@@ -8,7 +68,60 @@ pub trait HookSet: Default {
     /// This allows the most intricate insight into the interpreters behavior, at the cost of a
     /// hefty performance penalty
     #[allow(unused_variables)]
-    fn instruction_hook(&mut self, bytecode: &[u8], pc: usize) {}
+    fn instruction_hook(&mut self, bytecode: &[u8], pc: usize, location: Location) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called whenever a function is entered, with the arguments passed to it
+    ///
+    /// `location` is the `call` or `call_indirect` instruction, or `None` for the function invoked
+    /// by the host. In the latter case, the hook is called when the invocation starts executing.
+    #[allow(unused_variables)]
+    fn call_hook(
+        &mut self,
+        location: Option<Location>,
+        callee: FuncIdx,
+        args: &[Value],
+    ) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called whenever a function returns, with the results it returns
+    ///
+    /// `location` is the instruction by which `callee` returns. For host functions, which do not
+    /// have any instructions, it is the `call` or `call_indirect` instruction instead.
+    #[allow(unused_variables)]
+    fn return_hook(&mut self, location: Location, callee: FuncIdx, results: &[Value]) {}
+
+    /// A hook which is called when execution traps, before the error is returned to the host
+    #[allow(unused_variables)]
+    fn trap_hook(&mut self, location: Location, error: &RuntimeError) {}
+
+    /// A hook which is called after bytes were read from linear memory at the effective `address`
+    ///
+    /// The width of the access is the length of `bytes`.
+    #[allow(unused_variables)]
+    fn memory_load_hook(&mut self, location: Location, address: usize, bytes: &[u8]) {}
+
+    /// A hook which is called after bytes were written to linear memory at the effective `address`
+    ///
+    /// The width of the access is the length of `bytes`.
+    #[allow(unused_variables)]
+    fn memory_store_hook(&mut self, location: Location, address: usize, bytes: &[u8]) {}
+
+    /// A hook which is called after a global was written by `global.set`
+    #[allow(unused_variables)]
+    fn global_write_hook(&mut self, location: Location, global_idx: GlobalIdx, value: Value) {}
+
+    /// A hook which is called when a decision, i. e. an `if`, `br_if`, `br_table` or `select`, is
+    /// executed
+    #[allow(unused_variables)]
+    fn branch_hook(&mut self, location: Location, outcome: BranchOutcome) {}
+
+    /// A hook which is called after a condition, i. e. an `i32.eqz`, `i64.eqz` or scalar comparison
+    /// instruction, was executed, with its operands and its result
+    #[allow(unused_variables)]
+    fn condition_hook(&mut self, location: Location, operands: &[Value], result: bool) {}
 }
 
 /// Default implementation of a hookset, with all hooks empty
@@ -16,3 +129,109 @@ pub trait HookSet: Default {
 pub struct EmptyHookSet;
 
 impl HookSet for EmptyHookSet {}
+
+/// Locates the instruction at `pc` within the function of the current stack frame
+///
+/// This is only called where a hook is invoked, not for every instruction. It only borrows the
+/// store's functions, so that it can be called while a memory is borrowed mutably.
+#[inline(always)]
+pub(crate) fn locate(funcs: &[FuncInst], stack: &Stack, pc: usize) -> Location {
+    let func_idx = stack.frames().last().map_or(0, |frame| frame.func_idx);
+    let func_start = match funcs.get(func_idx) {
+        Some(FuncInst::Local(func)) => func.code_expr.from(),
+        _ => 0,
+    };
+
+    Location {
+        func_idx,
+        offset: pc.wrapping_sub(func_start),
+    }
+}
+
+/// Reads `N` bytes from linear memory like [`MemInst::load`], calling the memory load hook
+///
+/// The instruction is only located if the `hooks` feature is enabled.
+#[inline(always)]
+#[cfg_attr(not(feature = "hooks"), allow(unused_variables))]
+pub(crate) fn hooked_load<const N: usize, H: HookSet>(
+    mem: &MemInst,
+    hooks: &mut H,
+    location: impl FnOnce() -> Location,
+    offset: u32,
+    address: u32,
+) -> Result<[u8; N], RuntimeError> {
+    let bytes = mem.load(offset, address)?;
+
+    #[cfg(feature = "hooks")]
+    hooks.memory_load_hook(location(), offset as usize + address as usize, &bytes);
+    Ok(bytes)
+}
+
+/// Writes `N` bytes to linear memory like [`MemInst::store`], calling the memory store hook
+///
+/// The instruction is only located if the `hooks` feature is enabled.
+#[inline(always)]
+#[cfg_attr(not(feature = "hooks"), allow(unused_variables))]
+pub(crate) fn hooked_store<const N: usize, H: HookSet>(
+    mem: &mut MemInst,
+    hooks: &mut H,
+    location: impl FnOnce() -> Location,
+    offset: u32,
+    address: u32,
+    bytes: [u8; N],
+) -> Result<(), RuntimeError> {
+    mem.store(offset, address, bytes)?;
+
+    #[cfg(feature = "hooks")]
+    hooks.memory_store_hook(location(), offset as usize + address as usize, &bytes);
+    Ok(())
+}
+
+/// Executes a comparison of two operands of type `T`, calling the condition hook with its result
+///
+/// The operands are popped from and the result is pushed to the stack. Both operands and the
+/// result are returned for tracing. The instruction at `pc` is only located if the `hooks` feature
+/// is enabled.
+#[inline(always)]
+#[cfg_attr(not(feature = "hooks"), allow(unused_variables))]
+pub(crate) fn hooked_relop<T: InteropValue + Copy, H: HookSet>(
+    stack: &mut Stack,
+    hooks: &mut H,
+    funcs: &[FuncInst],
+    pc: usize,
+    relop: impl FnOnce(T, T) -> bool,
+) -> (T, T, u32) {
+    let v2 = T::from_value(stack.pop_value(T::TY));
+    let v1 = T::from_value(stack.pop_value(T::TY));
+    let result = relop(v1, v2);
+
+    #[cfg(feature = "hooks")]
+    hooks.condition_hook(
+        locate(funcs, stack, pc),
+        &[v1.into_value(), v2.into_value()],
+        result,
+    );
+
+    stack.push_value(Value::I32(result.into()));
+    (v1, v2, result.into())
+}
+
+/// Like [`hooked_relop`], but for a test of a single operand, i.e. an `eqz` instruction
+#[inline(always)]
+#[cfg_attr(not(feature = "hooks"), allow(unused_variables))]
+pub(crate) fn hooked_testop<T: InteropValue + Copy, H: HookSet>(
+    stack: &mut Stack,
+    hooks: &mut H,
+    funcs: &[FuncInst],
+    pc: usize,
+    testop: impl FnOnce(T) -> bool,
+) -> (T, u32) {
+    let v1 = T::from_value(stack.pop_value(T::TY));
+    let result = testop(v1);
+
+    #[cfg(feature = "hooks")]
+    hooks.condition_hook(locate(funcs, stack, pc), &[v1.into_value()], result);
+
+    stack.push_value(Value::I32(result.into()));
+    (v1, result.into())
+}
@@ -15,52 +15,167 @@ use alloc::vec::Vec;
 use crate::{
     assert_validated::UnwrapValidatedExt,
     core::{
-        indices::{FuncIdx, GlobalIdx, LocalIdx},
+        indices::{DataIdx, ElemIdx, FuncIdx, GlobalIdx, LabelIdx, LocalIdx, TableIdx, TypeIdx},
         reader::{
-            types::{memarg::MemArg, FuncType},
+            types::{memarg::MemArg, BlockType, FuncType, RefType},
             WasmReadable, WasmReader,
         },
+        sidetable::Sidetable,
     },
+    linker::HostCallContext,
     locals::Locals,
-    store::Store,
-    value,
+    simd::{self, V128},
+    store::{FuncInst, Store},
+    unreachable_validated,
+    value::{self, Ref},
     value_stack::Stack,
     NumType, RuntimeError, ValType, Value,
 };
 
+use crate::execution::do_sidetable_control_transfer;
+use crate::execution::fuel::{ExecutionOutcome, FuelCostTable};
+use crate::execution::hooks::{
+    hooked_load, hooked_relop, hooked_store, hooked_testop, locate, HookSet,
+};
 #[cfg(feature = "hooks")]
-use crate::execution::hooks::HookSet;
+use crate::execution::hooks::{BranchOutcome, HookAction};
 
 /// Interprets a functions. Parameters and return values are passed on the stack.
+///
+/// Execution starts at `pc` with the side-table pointer `stp`, which are updated to the point where
+/// execution was halted, if it runs out of fuel.
+///
+/// If `fuel` is `Some`, each instruction consumes fuel according to the `fuel_cost_table` before
+/// it is executed, including the fuel for the bytes or elements accessed by bulk instructions.
+/// Execution halts with [`ExecutionOutcome::OutOfFuel`] as soon as the remaining fuel is
+/// insufficient for the next instruction.
+///
+/// The `hooks` are borrowed from the instance, so any state they record persists across
+/// invocations. Before each instruction is executed, its PC is stored in `trap_pc`, so that a trap
+/// can be located. If a hook pauses execution, [`ExecutionOutcome::Paused`] is returned and `pc` points to the
+/// instruction that triggered the hook. `paused_by_hook` is then set, so that the hooks which are
+/// called before that instruction are skipped once execution continues with it.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(not(feature = "hooks"), allow(unused_variables))]
 pub(super) fn run<H: HookSet>(
     wasm_bytecode: &[u8],
     types: &[FuncType],
+    sidetable: &Sidetable,
     store: &mut Store,
     stack: &mut Stack,
-    mut hooks: H,
-) -> Result<(), RuntimeError> {
-    let func_inst = store
-        .funcs
-        .get(stack.current_stackframe().func_idx)
-        .unwrap_validated();
-
-    // Start reading the function's instructions
+    pc: &mut usize,
+    stp: &mut usize,
+    fuel: &mut Option<u64>,
+    fuel_cost_table: &FuelCostTable,
+    hooks: &mut H,
+    trap_pc: &mut usize,
+    paused_by_hook: &mut bool,
+) -> Result<ExecutionOutcome<()>, RuntimeError> {
+    // Continue reading instructions where the last execution stopped
     let mut wasm = WasmReader::new(wasm_bytecode);
-
-    // unwrap is sound, because the validation assures that the function points to valid subslice of the WASM binary
-    wasm.move_start_to(func_inst.code_expr).unwrap();
+    wasm.pc = *pc;
 
     use crate::core::reader::types::opcode::*;
     loop {
-        // call the instruction hook
+        if let Some(fuel) = fuel {
+            let cost = fuel_cost_table.instruction_cost(&wasm, stack);
+            if *fuel < cost {
+                *pc = wasm.pc;
+                return Ok(ExecutionOutcome::OutOfFuel);
+            }
+            *fuel -= cost;
+        }
+
+        let instr_pc = wasm.pc;
+        #[cfg(feature = "hooks")]
+        {
+            *trap_pc = instr_pc;
+        }
+
+        // Locates this instruction, which is only done where a hook is called
+        macro_rules! location {
+            () => {
+                locate(&store.funcs, stack, instr_pc)
+            };
+        }
+
+        // the hooks before this instruction were already called if they paused execution here
         #[cfg(feature = "hooks")]
-        hooks.instruction_hook(wasm_bytecode, wasm.pc);
+        let skip_hooks = core::mem::take(paused_by_hook);
+
+        // call the instruction hook, which may pause or abort execution before this instruction
+        #[cfg(feature = "hooks")]
+        if !skip_hooks {
+            match hooks.instruction_hook(wasm_bytecode, instr_pc, location!()) {
+                HookAction::Continue => {}
+                HookAction::Pause => {
+                    // the instruction is executed upon resumption, so its fuel is refunded
+                    if let Some(fuel) = fuel {
+                        *fuel += fuel_cost_table.instruction_cost(&wasm, stack);
+                    }
+                    *pc = instr_pc;
+                    *paused_by_hook = true;
+                    return Ok(ExecutionOutcome::Paused);
+                }
+                HookAction::Abort(code) => return Err(RuntimeError::HookAbort(code)),
+            }
+        }
 
         let first_instr_byte = wasm.read_u8().unwrap_validated();
 
         match first_instr_byte {
+            UNREACHABLE => {
+                trace!("Instruction: unreachable");
+                return Err(RuntimeError::Unreachable);
+            }
+            NOP => {
+                trace!("Instruction: NOP");
+            }
+            BLOCK | LOOP => {
+                let _block_ty = BlockType::read_unvalidated(&mut wasm);
+
+                trace!("Instruction: block/loop");
+            }
+            IF => {
+                let _block_ty = BlockType::read_unvalidated(&mut wasm);
+                let condition: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                trace!("Instruction: if [{condition}]");
+                #[cfg(feature = "hooks")]
+                hooks.branch_hook(location!(), BranchOutcome::from(condition != 0));
+                if condition == 0 {
+                    // continue in the else branch, or behind the `end` if there is none
+                    do_sidetable_control_transfer(&mut wasm, stack, stp, sidetable, instr_pc);
+                } else {
+                    *stp += 1;
+                }
+            }
+            ELSE => {
+                // the then branch was executed, skip the else branch
+                trace!("Instruction: else");
+                do_sidetable_control_transfer(&mut wasm, stack, stp, sidetable, instr_pc);
+            }
             END => {
-                let maybe_return_address = stack.pop_stackframe();
+                // only the `end` of the function body has an effect, the `end` of any other block
+                // is a no-op, as there are no labels at runtime
+                let current_func = store
+                    .funcs
+                    .get(stack.current_stackframe().func_idx)
+                    .and_then(FuncInst::try_into_local)
+                    .unwrap_validated();
+                if wasm.pc != current_func.code_expr.from() + current_func.code_expr.len() {
+                    trace!("end of block reached");
+                    continue;
+                }
+
+                #[cfg(feature = "hooks")]
+                hooks.return_hook(
+                    location!(),
+                    stack.current_stackframe().func_idx,
+                    stack.peek_tail(stack.current_stackframe().return_value_count),
+                );
+                let (maybe_return_address, maybe_return_stp) = stack.pop_stackframe();
 
                 // We finished this entire invocation if there is no stackframe left. If there are
                 // one or more stack frames, we need to continue from where the callee was called
@@ -71,46 +186,182 @@ pub(super) fn run<H: HookSet>(
 
                 trace!("end of function reached, returning to previous stack frame");
                 wasm.pc = maybe_return_address;
+                *stp = maybe_return_stp;
             }
-            RETURN => {
-                trace!("returning from function");
+            BR => {
+                let label_idx = wasm.read_var_u32().unwrap_validated() as LabelIdx;
 
-                let func_to_call_idx = stack.current_stackframe().func_idx;
+                trace!("Instruction: br [{label_idx}]");
+                do_sidetable_control_transfer(&mut wasm, stack, stp, sidetable, instr_pc);
+            }
+            BR_IF => {
+                let label_idx = wasm.read_var_u32().unwrap_validated() as LabelIdx;
+                let condition: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
-                let func_to_call_inst = store.funcs.get(func_to_call_idx).unwrap_validated();
-                let func_to_call_ty = types.get(func_to_call_inst.ty).unwrap_validated();
+                trace!("Instruction: br_if [{condition}] ({label_idx})");
+                #[cfg(feature = "hooks")]
+                hooks.branch_hook(location!(), BranchOutcome::from(condition != 0));
+                if condition != 0 {
+                    do_sidetable_control_transfer(&mut wasm, stack, stp, sidetable, instr_pc);
+                } else {
+                    *stp += 1;
+                }
+            }
+            BR_TABLE => {
+                let label_count = wasm
+                    .read_vec(|wasm| wasm.read_var_u32())
+                    .unwrap_validated()
+                    .len();
+                let _default_label_idx = wasm.read_var_u32().unwrap_validated();
+                let case: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
-                let ret_vals = stack
-                    .pop_tail_iter(func_to_call_ty.returns.valtypes.len())
-                    .collect::<Vec<_>>();
-                stack.clear_callframe_values();
+                // the side-table entries of all labels are stored consecutively, followed by the
+                // entry of the default label
+                let entry_offset = (case as usize).min(label_count);
 
-                for val in ret_vals {
-                    stack.push_value(val);
-                }
+                trace!("Instruction: br_table [{case}]");
+                #[cfg(feature = "hooks")]
+                hooks.branch_hook(location!(), BranchOutcome::Table(entry_offset as u32));
+                *stp += entry_offset;
+                do_sidetable_control_transfer(&mut wasm, stack, stp, sidetable, instr_pc);
+            }
+            RETURN => {
+                trace!("returning from function");
 
-                if stack.callframe_count() == 1 {
+                #[cfg(feature = "hooks")]
+                hooks.return_hook(
+                    location!(),
+                    stack.current_stackframe().func_idx,
+                    stack.peek_tail(stack.current_stackframe().return_value_count),
+                );
+                let (maybe_return_address, maybe_return_stp) = stack.pop_stackframe();
+
+                if stack.callframe_count() == 0 {
                     break;
                 }
 
                 trace!("end of function reached, returning to previous stack frame");
-                wasm.pc = stack.pop_stackframe();
-            }
-            CALL => {
-                let func_to_call_idx = wasm.read_var_u32().unwrap_validated() as FuncIdx;
+                wasm.pc = maybe_return_address;
+                *stp = maybe_return_stp;
+            }
+            CALL | CALL_INDIRECT => {
+                let func_to_call_idx = if first_instr_byte == CALL {
+                    wasm.read_var_u32().unwrap_validated() as FuncIdx
+                } else {
+                    let type_idx = wasm.read_var_u32().unwrap_validated() as TypeIdx;
+                    let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+                    // the element index is only popped once the call hook let the call proceed
+                    let elem_idx: u32 = stack.peek_value(ValType::NumType(NumType::I32)).into();
+
+                    let table = store.tables.get(table_idx).unwrap_validated();
+                    let reference = table
+                        .elem
+                        .get(elem_idx as usize)
+                        .ok_or(RuntimeError::UndefinedTableElement)?;
+                    let Ref::Func(func_idx) = *reference else {
+                        return Err(RuntimeError::UninitializedTableElement);
+                    };
+
+                    // Function types are compared structurally, not by their index
+                    let expected_ty = types.get(type_idx).unwrap_validated();
+                    let actual_ty_idx = store.funcs.get(func_idx).unwrap_validated().ty();
+                    if types.get(actual_ty_idx).unwrap_validated() != expected_ty {
+                        return Err(RuntimeError::IndirectCallTypeMismatch);
+                    }
+
+                    trace!("Instruction: call_indirect [{elem_idx}] -> [{func_idx:?}]");
+                    func_idx
+                };
 
                 let func_to_call_inst = store.funcs.get(func_to_call_idx).unwrap_validated();
-                let func_to_call_ty = types.get(func_to_call_inst.ty).unwrap_validated();
+                let func_to_call_ty = types.get(func_to_call_inst.ty()).unwrap_validated();
+
+                #[cfg(feature = "hooks")]
+                if !skip_hooks {
+                    // the arguments of indirect calls are below the element index
+                    let num_args = func_to_call_ty.params.valtypes.len();
+                    let args = stack.peek_tail(num_args + usize::from(first_instr_byte != CALL));
+                    match hooks.call_hook(Some(location!()), func_to_call_idx, &args[..num_args]) {
+                        HookAction::Continue => {}
+                        HookAction::Pause => {
+                            // the call is executed upon resumption, so its fuel is refunded
+                            if let Some(fuel) = fuel {
+                                *fuel += fuel_cost_table.cost(first_instr_byte);
+                            }
+                            *pc = instr_pc;
+                            *paused_by_hook = true;
+                            return Ok(ExecutionOutcome::Paused);
+                        }
+                        HookAction::Abort(code) => return Err(RuntimeError::HookAbort(code)),
+                    }
+                }
+                if first_instr_byte == CALL_INDIRECT {
+                    let _elem_idx = stack.pop_value(ValType::NumType(NumType::I32));
+                }
+
+                let func_to_call_inst = match func_to_call_inst {
+                    FuncInst::Local(func) => func,
+                    FuncInst::Imported(func) => {
+                        trace!("Instruction: call [{func_to_call_idx:?}] (host function)");
+                        let params = stack
+                            .pop_tail_iter(func_to_call_ty.params.valtypes.len())
+                            .collect::<Vec<Value>>();
+
+                        let Store {
+                            mems, host_funcs, ..
+                        } = store;
+                        let host_func = host_funcs.get_mut(func.host_func_idx).unwrap_validated();
+                        let returns = host_func(&mut HostCallContext { mems }, &params)?;
+
+                        let return_types = returns.iter().map(Value::to_ty);
+                        if !return_types.eq(func_to_call_ty.returns.valtypes.iter().copied()) {
+                            return Err(RuntimeError::InvalidHostFunctionResults);
+                        }
+                        returns
+                            .into_iter()
+                            .for_each(|value| stack.push_value(value));
+
+                        #[cfg(feature = "hooks")]
+                        hooks.return_hook(
+                            location!(),
+                            func_to_call_idx,
+                            stack.peek_tail(func_to_call_ty.returns.valtypes.len()),
+                        );
+                        continue;
+                    }
+                };
 
                 let params = stack.pop_tail_iter(func_to_call_ty.params.valtypes.len());
                 let remaining_locals = func_to_call_inst.locals.iter().cloned();
 
                 trace!("Instruction: call [{func_to_call_idx:?}]");
                 let locals = Locals::new(params, remaining_locals);
-                stack.push_stackframe(func_to_call_idx, func_to_call_ty, locals, wasm.pc);
+                stack.push_stackframe(func_to_call_idx, func_to_call_ty, locals, wasm.pc, *stp);
 
                 wasm.move_start_to(func_to_call_inst.code_expr)
                     .unwrap_validated();
+                *stp = func_to_call_inst.stp;
+            }
+            DROP => {
+                let _dropped = stack.pop_any_value();
+                trace!("Instruction: drop [{_dropped:?}] -> []");
+            }
+            SELECT | SELECT_T => {
+                if first_instr_byte == SELECT_T {
+                    // validation guarantees that there is exactly one type
+                    let _num_tys = wasm.read_var_u32().unwrap_validated();
+                    let _ty = ValType::read_unvalidated(&mut wasm);
+                }
+
+                let condition: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                let v2 = stack.pop_any_value();
+                let v1 = stack.pop_any_value();
+                let res = if condition != 0 { v1 } else { v2 };
+
+                trace!("Instruction: select [{v1:?} {v2:?} {condition}] -> [{res:?}]");
+                #[cfg(feature = "hooks")]
+                hooks.branch_hook(location!(), BranchOutcome::from(condition != 0));
+                stack.push_value(res);
             }
             LOCAL_GET => {
                 stack.get_local(wasm.read_var_u32().unwrap_validated() as LocalIdx);
@@ -127,7 +378,38 @@ pub(super) fn run<H: HookSet>(
                 let global_idx = wasm.read_var_u32().unwrap_validated() as GlobalIdx;
                 let global = store.globals.get_mut(global_idx).unwrap_validated();
 
-                global.value = stack.pop_value(global.global.ty.ty)
+                global.value = stack.pop_value(global.ty.ty);
+
+                #[cfg(feature = "hooks")]
+                hooks.global_write_hook(location!(), global_idx, global.value);
+            }
+            TABLE_GET => {
+                let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+                let elem_idx: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                let table = store.tables.get(table_idx).unwrap_validated();
+                let reference = *table
+                    .elem
+                    .get(elem_idx as usize)
+                    .ok_or(RuntimeError::TableAccessOutOfBounds)?;
+
+                trace!("Instruction: table.get {table_idx} [{elem_idx}] -> [{reference:?}]");
+                stack.push_value(Value::Ref(reference));
+            }
+            TABLE_SET => {
+                let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+                let table = store.tables.get_mut(table_idx).unwrap_validated();
+                let Value::Ref(reference) = stack.pop_value(ValType::RefType(table.ty.et)) else {
+                    unreachable_validated!()
+                };
+                let elem_idx: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                *table
+                    .elem
+                    .get_mut(elem_idx as usize)
+                    .ok_or(RuntimeError::TableAccessOutOfBounds)? = reference;
+
+                trace!("Instruction: table.set {table_idx} [{elem_idx} {reference:?}] -> []");
             }
             I32_LOAD => {
                 let memarg = MemArg::read_unvalidated(&mut wasm);
@@ -135,52 +417,155 @@ pub(super) fn run<H: HookSet>(
 
                 let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
 
-                let data: u32 = {
-                    // The spec states that this should be a 33 bit integer
-                    // See: https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions
-                    let _address = memarg.offset.checked_add(relative_address);
-                    let data = memarg
-                        .offset
-                        .checked_add(relative_address)
-                        .and_then(|address| {
-                            let address = address as usize;
-                            mem.data.get(address..(address + 4))
-                        })
-                        .expect("TODO trap here");
-
-                    let data: [u8; 4] = data.try_into().expect("this to be exactly 4 bytes");
-                    u32::from_le_bytes(data)
-                };
+                let data = u32::from_le_bytes(hooked_load(
+                    mem,
+                    hooks,
+                    || location!(),
+                    memarg.offset,
+                    relative_address,
+                )?);
 
                 stack.push_value(Value::I32(data));
                 trace!("Instruction: i32.load [{relative_address}] -> [{data}]");
             }
+            I64_LOAD => {
+                let memarg = MemArg::read_unvalidated(&mut wasm);
+                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
+
+                let data = u64::from_le_bytes(hooked_load(
+                    mem,
+                    hooks,
+                    || location!(),
+                    memarg.offset,
+                    relative_address,
+                )?);
+
+                stack.push_value(Value::I64(data));
+                trace!("Instruction: i64.load [{relative_address}] -> [{data}]");
+            }
             F32_LOAD => {
                 let memarg = MemArg::read_unvalidated(&mut wasm);
                 let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
 
-                let data: f32 = {
-                    // The spec states that this should be a 33 bit integer
-                    // See: https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions
-                    let _address = memarg.offset.checked_add(relative_address);
-                    let data = memarg
-                        .offset
-                        .checked_add(relative_address)
-                        .and_then(|address| {
-                            let address = address as usize;
-                            mem.data
-                                .get(address..(address + 4))
-                                .map(|slice| slice.try_into().expect("this to be exactly 4 bytes"))
-                        })
-                        .expect("TODO trap here");
-                    f32::from_le_bytes(data)
-                };
+                let data = f32::from_le_bytes(hooked_load(
+                    mem,
+                    hooks,
+                    || location!(),
+                    memarg.offset,
+                    relative_address,
+                )?);
 
                 stack.push_value(Value::F32(value::F32(data)));
                 trace!("Instruction: f32.load [{relative_address}] -> [{data}]");
             }
+            F64_LOAD => {
+                let memarg = MemArg::read_unvalidated(&mut wasm);
+                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
+
+                let data = f64::from_le_bytes(hooked_load(
+                    mem,
+                    hooks,
+                    || location!(),
+                    memarg.offset,
+                    relative_address,
+                )?);
+
+                stack.push_value(Value::F64(value::F64(data)));
+                trace!("Instruction: f64.load [{relative_address}] -> [{data}]");
+            }
+            I32_LOAD8_S | I32_LOAD8_U | I32_LOAD16_S | I32_LOAD16_U => {
+                let memarg = MemArg::read_unvalidated(&mut wasm);
+                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
+
+                let (offset, address) = (memarg.offset, relative_address);
+                let data: u32 = match first_instr_byte {
+                    I32_LOAD8_S => {
+                        i8::from_le_bytes(hooked_load(mem, hooks, || location!(), offset, address)?)
+                            as u32
+                    }
+                    I32_LOAD8_U => {
+                        u8::from_le_bytes(hooked_load(mem, hooks, || location!(), offset, address)?)
+                            as u32
+                    }
+                    I32_LOAD16_S => i16::from_le_bytes(hooked_load(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                    )?) as u32,
+                    I32_LOAD16_U => u16::from_le_bytes(hooked_load(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                    )?) as u32,
+                    _ => unreachable!(),
+                };
+
+                stack.push_value(Value::I32(data));
+                trace!("Instruction: i32.load{{8,16}}_{{s,u}} [{relative_address}] -> [{data}]");
+            }
+            I64_LOAD8_S | I64_LOAD8_U | I64_LOAD16_S | I64_LOAD16_U | I64_LOAD32_S
+            | I64_LOAD32_U => {
+                let memarg = MemArg::read_unvalidated(&mut wasm);
+                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
+
+                let (offset, address) = (memarg.offset, relative_address);
+                let data: u64 = match first_instr_byte {
+                    I64_LOAD8_S => {
+                        i8::from_le_bytes(hooked_load(mem, hooks, || location!(), offset, address)?)
+                            as u64
+                    }
+                    I64_LOAD8_U => {
+                        u8::from_le_bytes(hooked_load(mem, hooks, || location!(), offset, address)?)
+                            as u64
+                    }
+                    I64_LOAD16_S => i16::from_le_bytes(hooked_load(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                    )?) as u64,
+                    I64_LOAD16_U => u16::from_le_bytes(hooked_load(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                    )?) as u64,
+                    I64_LOAD32_S => i32::from_le_bytes(hooked_load(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                    )?) as u64,
+                    I64_LOAD32_U => u32::from_le_bytes(hooked_load(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                    )?) as u64,
+                    _ => unreachable!(),
+                };
+
+                stack.push_value(Value::I64(data));
+                trace!("Instruction: i64.load{{8,16,32}}_{{s,u}} [{relative_address}] -> [{data}]");
+            }
             I32_STORE => {
                 let memarg = MemArg::read_unvalidated(&mut wasm);
 
@@ -189,19 +574,34 @@ pub(super) fn run<H: HookSet>(
 
                 let mem = store.mems.get_mut(0).unwrap_validated(); // there is only one memory allowed as of now
 
-                // The spec states that this should be a 33 bit integer
-                // See: https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions
-                let address = memarg.offset.checked_add(relative_address);
-                let memory_location = address
-                    .and_then(|address| {
-                        let address = address as usize;
-                        mem.data.get_mut(address..(address + 4))
-                    })
-                    .expect("TODO trap here");
-
-                memory_location.copy_from_slice(&data_to_store.to_le_bytes());
+                hooked_store(
+                    mem,
+                    hooks,
+                    || location!(),
+                    memarg.offset,
+                    relative_address,
+                    data_to_store.to_le_bytes(),
+                )?;
                 trace!("Instruction: i32.store [{relative_address} {data_to_store}] -> []");
             }
+            I64_STORE => {
+                let memarg = MemArg::read_unvalidated(&mut wasm);
+
+                let data_to_store: u64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                let mem = store.mems.get_mut(0).unwrap_validated(); // there is only one memory allowed as of now
+
+                hooked_store(
+                    mem,
+                    hooks,
+                    || location!(),
+                    memarg.offset,
+                    relative_address,
+                    data_to_store.to_le_bytes(),
+                )?;
+                trace!("Instruction: i64.store [{relative_address} {data_to_store}] -> []");
+            }
             F32_STORE => {
                 let memarg = MemArg::read_unvalidated(&mut wasm);
 
@@ -210,17 +610,14 @@ pub(super) fn run<H: HookSet>(
 
                 let mem = store.mems.get_mut(0).unwrap_validated(); // there is only one memory allowed as of now
 
-                // The spec states that this should be a 33 bit integer
-                // See: https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions
-                let address = memarg.offset.checked_add(relative_address);
-                let memory_location = address
-                    .and_then(|address| {
-                        let address = address as usize;
-                        mem.data.get_mut(address..(address + 4))
-                    })
-                    .expect("TODO trap here");
-
-                memory_location.copy_from_slice(&data_to_store.to_le_bytes());
+                hooked_store(
+                    mem,
+                    hooks,
+                    || location!(),
+                    memarg.offset,
+                    relative_address,
+                    data_to_store.to_le_bytes(),
+                )?;
                 trace!("Instruction: f32.store [{relative_address} {data_to_store}] -> []");
             }
             F64_STORE => {
@@ -231,19 +628,119 @@ pub(super) fn run<H: HookSet>(
 
                 let mem = store.mems.get_mut(0).unwrap_validated(); // there is only one memory allowed as of now
 
-                // The spec states that this should be a 33 bit integer
-                // See: https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions
-                let address = memarg.offset.checked_add(relative_address);
-                let memory_location = address
-                    .and_then(|address| {
-                        let address = address as usize;
-                        mem.data.get_mut(address..(address + 4))
-                    })
-                    .expect("TODO trap here");
-
-                memory_location.copy_from_slice(&data_to_store.to_le_bytes());
+                hooked_store(
+                    mem,
+                    hooks,
+                    || location!(),
+                    memarg.offset,
+                    relative_address,
+                    data_to_store.to_le_bytes(),
+                )?;
                 trace!("Instruction: f64.store [{relative_address} {data_to_store}] -> []");
             }
+            I32_STORE8 | I32_STORE16 => {
+                let memarg = MemArg::read_unvalidated(&mut wasm);
+
+                let data_to_store: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                let mem = store.mems.get_mut(0).unwrap_validated(); // there is only one memory allowed as of now
+
+                // The value is wrapped to the width of the store
+                let (offset, address) = (memarg.offset, relative_address);
+                match first_instr_byte {
+                    I32_STORE8 => hooked_store(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                        (data_to_store as u8).to_le_bytes(),
+                    ),
+                    I32_STORE16 => hooked_store(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                        (data_to_store as u16).to_le_bytes(),
+                    ),
+                    _ => unreachable!(),
+                }?;
+                trace!("Instruction: i32.store{{8,16}} [{relative_address} {data_to_store}] -> []");
+            }
+            I64_STORE8 | I64_STORE16 | I64_STORE32 => {
+                let memarg = MemArg::read_unvalidated(&mut wasm);
+
+                let data_to_store: u64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                let mem = store.mems.get_mut(0).unwrap_validated(); // there is only one memory allowed as of now
+
+                // The value is wrapped to the width of the store
+                let (offset, address) = (memarg.offset, relative_address);
+                match first_instr_byte {
+                    I64_STORE8 => hooked_store(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                        (data_to_store as u8).to_le_bytes(),
+                    ),
+                    I64_STORE16 => hooked_store(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                        (data_to_store as u16).to_le_bytes(),
+                    ),
+                    I64_STORE32 => hooked_store(
+                        mem,
+                        hooks,
+                        || location!(),
+                        offset,
+                        address,
+                        (data_to_store as u32).to_le_bytes(),
+                    ),
+                    _ => unreachable!(),
+                }?;
+                trace!(
+                    "Instruction: i64.store{{8,16,32}} [{relative_address} {data_to_store}] -> []"
+                );
+            }
+            MEMORY_SIZE => {
+                let _mem_idx = wasm.read_var_u32().unwrap_validated();
+
+                let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
+                let size = mem.size();
+
+                stack.push_value(Value::I32(size));
+                trace!("Instruction: memory.size [] -> [{size}]");
+            }
+            MEMORY_GROW => {
+                let _mem_idx = wasm.read_var_u32().unwrap_validated();
+                let delta: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                let Store {
+                    mems,
+                    memory_growth_policy,
+                    ..
+                } = store;
+                let mem = mems.first_mut().unwrap_validated(); // there is only one memory allowed as of now
+
+                // Failure is not a trap, but signaled to the program by returning -1
+                let old_size = mem
+                    .grow(delta, memory_growth_policy.as_deref_mut())
+                    .unwrap_or(u32::MAX);
+
+                stack.push_value(Value::I32(old_size));
+                trace!(
+                    "Instruction: memory.grow [{delta}] -> [{}]",
+                    old_size as i32
+                );
+            }
             I32_CONST => {
                 let constant = wasm.read_var_i32().unwrap_validated();
                 trace!("Instruction: i32.const [] -> [{constant}]");
@@ -255,311 +752,277 @@ pub(super) fn run<H: HookSet>(
                 stack.push_value(constant.into());
             }
             I32_EQZ => {
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if v1 == 0 { 1 } else { 0 };
-
+                let (v1, res) =
+                    hooked_testop(stack, hooks, &store.funcs, instr_pc, |v1: i32| v1 == 0);
                 trace!("Instruction: i32.eqz [{v1}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I32_EQ => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if v1 == v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        v1 == v2
+                    });
                 trace!("Instruction: i32.eq [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I32_NE => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if v1 != v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        v1 != v2
+                    });
                 trace!("Instruction: i32.ne [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I32_LT_S => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if v1 < v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        v1 < v2
+                    });
                 trace!("Instruction: i32.lt_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
 
             I32_LT_U => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if (v1 as u32) < (v2 as u32) { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        (v1 as u32) < (v2 as u32)
+                    });
                 trace!("Instruction: i32.lt_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I32_GT_S => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if v1 > v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        v1 > v2
+                    });
                 trace!("Instruction: i32.gt_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I32_GT_U => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if (v1 as u32) > (v2 as u32) { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        (v1 as u32) > (v2 as u32)
+                    });
                 trace!("Instruction: i32.gt_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I32_LE_S => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if v1 <= v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        v1 <= v2
+                    });
                 trace!("Instruction: i32.le_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I32_LE_U => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if (v1 as u32) <= (v2 as u32) { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        (v1 as u32) <= (v2 as u32)
+                    });
                 trace!("Instruction: i32.le_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I32_GE_S => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if v1 >= v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        v1 >= v2
+                    });
                 trace!("Instruction: i32.ge_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I32_GE_U => {
-                let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let res = if (v1 as u32) >= (v2 as u32) { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i32, v2: i32| {
+                        (v1 as u32) >= (v2 as u32)
+                    });
                 trace!("Instruction: i32.ge_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_EQZ => {
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if v1 == 0 { 1 } else { 0 };
-
+                let (v1, res) =
+                    hooked_testop(stack, hooks, &store.funcs, instr_pc, |v1: i64| v1 == 0);
                 trace!("Instruction: i64.eqz [{v1}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_EQ => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if v1 == v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        v1 == v2
+                    });
                 trace!("Instruction: i64.eq [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_NE => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if v1 != v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        v1 != v2
+                    });
                 trace!("Instruction: i64.ne [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_LT_S => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if v1 < v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        v1 < v2
+                    });
                 trace!("Instruction: i64.lt_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
 
             I64_LT_U => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if (v1 as u64) < (v2 as u64) { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        (v1 as u64) < (v2 as u64)
+                    });
                 trace!("Instruction: i64.lt_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_GT_S => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if v1 > v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        v1 > v2
+                    });
                 trace!("Instruction: i64.gt_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_GT_U => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if (v1 as u64) > (v2 as u64) { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        (v1 as u64) > (v2 as u64)
+                    });
                 trace!("Instruction: i64.gt_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_LE_S => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if v1 <= v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        v1 <= v2
+                    });
                 trace!("Instruction: i64.le_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_LE_U => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if (v1 as u64) <= (v2 as u64) { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        (v1 as u64) <= (v2 as u64)
+                    });
                 trace!("Instruction: i64.le_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_GE_S => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if v1 >= v2 { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        v1 >= v2
+                    });
                 trace!("Instruction: i64.ge_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             I64_GE_U => {
-                let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-
-                let res = if (v1 as u64) >= (v2 as u64) { 1 } else { 0 };
-
+                let (v1, v2, res) =
+                    hooked_relop(stack, hooks, &store.funcs, instr_pc, |v1: i64, v2: i64| {
+                        (v1 as u64) >= (v2 as u64)
+                    });
                 trace!("Instruction: i64.ge_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F32_EQ => {
-                let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-
-                let res = if v1 == v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F32, v2: value::F32| v1 == v2,
+                );
                 trace!("Instruction: f32.eq [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F32_NE => {
-                let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-
-                let res = if v1 != v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F32, v2: value::F32| v1 != v2,
+                );
                 trace!("Instruction: f32.ne [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F32_LT => {
-                let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-
-                let res = if v1 < v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F32, v2: value::F32| v1 < v2,
+                );
                 trace!("Instruction: f32.lt [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F32_GT => {
-                let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-
-                let res = if v1 > v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F32, v2: value::F32| v1 > v2,
+                );
                 trace!("Instruction: f32.gt [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F32_LE => {
-                let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-
-                let res = if v1 <= v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F32, v2: value::F32| v1 <= v2,
+                );
                 trace!("Instruction: f32.le [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F32_GE => {
-                let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-
-                let res = if v1 >= v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F32, v2: value::F32| v1 >= v2,
+                );
                 trace!("Instruction: f32.ge [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
 
             F64_EQ => {
-                let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-
-                let res = if v1 == v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F64, v2: value::F64| v1 == v2,
+                );
                 trace!("Instruction: f64.eq [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F64_NE => {
-                let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-
-                let res = if v1 != v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F64, v2: value::F64| v1 != v2,
+                );
                 trace!("Instruction: f64.ne [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F64_LT => {
-                let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-
-                let res = if v1 < v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F64, v2: value::F64| v1 < v2,
+                );
                 trace!("Instruction: f64.lt [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F64_GT => {
-                let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-
-                let res = if v1 > v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F64, v2: value::F64| v1 > v2,
+                );
                 trace!("Instruction: f64.gt [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F64_LE => {
-                let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-
-                let res = if v1 <= v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F64, v2: value::F64| v1 <= v2,
+                );
                 trace!("Instruction: f64.le [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
             F64_GE => {
-                let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-
-                let res = if v1 >= v2 { 1 } else { 0 };
-
+                let (v1, v2, res) = hooked_relop(
+                    stack,
+                    hooks,
+                    &store.funcs,
+                    instr_pc,
+                    |v1: value::F64, v2: value::F64| v1 >= v2,
+                );
                 trace!("Instruction: f64.ge [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
             }
 
             I32_CLZ => {
@@ -1033,6 +1496,141 @@ pub(super) fn run<H: HookSet>(
                 trace!("Instruction: f32.copysign [{v1} {v2}] -> [{res}]");
                 stack.push_value(res.into());
             }
+            I32_WRAP_I64 => {
+                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                let res: i32 = v1 as i32;
+
+                trace!("Instruction: i32.wrap_i64 [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I32_TRUNC_F32_S => {
+                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                if v1.is_nan() {
+                    return Err(RuntimeError::BadConversionToInteger);
+                }
+                // The bounds of all truncations are the nearest values outside of the integer's range,
+                // which are representable as floats
+                if !(-2147483904.0 < v1.0 && v1.0 < 2147483648.0) {
+                    return Err(RuntimeError::UnrepresentableResult);
+                }
+
+                let res: i32 = v1.0 as i32;
+
+                trace!("Instruction: i32.trunc_f32_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I32_TRUNC_F32_U => {
+                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                if v1.is_nan() {
+                    return Err(RuntimeError::BadConversionToInteger);
+                }
+                if !(-1.0 < v1.0 && v1.0 < 4294967296.0) {
+                    return Err(RuntimeError::UnrepresentableResult);
+                }
+
+                let res: u32 = v1.0 as u32;
+
+                trace!("Instruction: i32.trunc_f32_u [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I32_TRUNC_F64_S => {
+                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                if v1.is_nan() {
+                    return Err(RuntimeError::BadConversionToInteger);
+                }
+                if !(-2147483649.0 < v1.0 && v1.0 < 2147483648.0) {
+                    return Err(RuntimeError::UnrepresentableResult);
+                }
+
+                let res: i32 = v1.0 as i32;
+
+                trace!("Instruction: i32.trunc_f64_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I32_TRUNC_F64_U => {
+                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                if v1.is_nan() {
+                    return Err(RuntimeError::BadConversionToInteger);
+                }
+                if !(-1.0 < v1.0 && v1.0 < 4294967296.0) {
+                    return Err(RuntimeError::UnrepresentableResult);
+                }
+
+                let res: u32 = v1.0 as u32;
+
+                trace!("Instruction: i32.trunc_f64_u [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_EXTEND_I32_S => {
+                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                let res: i64 = v1 as i64;
+
+                trace!("Instruction: i64.extend_i32_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_EXTEND_I32_U => {
+                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                let res: i64 = v1 as u32 as i64;
+
+                trace!("Instruction: i64.extend_i32_u [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_TRUNC_F32_S => {
+                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                if v1.is_nan() {
+                    return Err(RuntimeError::BadConversionToInteger);
+                }
+                if !(-9223373136366403584.0 < v1.0 && v1.0 < 9223372036854775808.0) {
+                    return Err(RuntimeError::UnrepresentableResult);
+                }
+
+                let res: i64 = v1.0 as i64;
+
+                trace!("Instruction: i64.trunc_f32_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_TRUNC_F32_U => {
+                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                if v1.is_nan() {
+                    return Err(RuntimeError::BadConversionToInteger);
+                }
+                if !(-1.0 < v1.0 && v1.0 < 18446744073709551616.0) {
+                    return Err(RuntimeError::UnrepresentableResult);
+                }
+
+                let res: u64 = v1.0 as u64;
+
+                trace!("Instruction: i64.trunc_f32_u [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_TRUNC_F64_S => {
+                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                if v1.is_nan() {
+                    return Err(RuntimeError::BadConversionToInteger);
+                }
+                if !(-9223372036854777856.0 < v1.0 && v1.0 < 9223372036854775808.0) {
+                    return Err(RuntimeError::UnrepresentableResult);
+                }
+
+                let res: i64 = v1.0 as i64;
+
+                trace!("Instruction: i64.trunc_f64_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_TRUNC_F64_U => {
+                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                if v1.is_nan() {
+                    return Err(RuntimeError::BadConversionToInteger);
+                }
+                if !(-1.0 < v1.0 && v1.0 < 18446744073709551616.0) {
+                    return Err(RuntimeError::UnrepresentableResult);
+                }
+
+                let res: u64 = v1.0 as u64;
+
+                trace!("Instruction: i64.trunc_f64_u [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
             F32_CONVERT_I32_S => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                 let res: value::F32 = value::F32(v1 as f32);
@@ -1061,6 +1659,62 @@ pub(super) fn run<H: HookSet>(
                 trace!("Instruction: f32.convert_i64_u [{v1}] -> [{res}]");
                 stack.push_value(res.into());
             }
+            F32_DEMOTE_F64 => {
+                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                let res: value::F32 = value::F32(v1.0 as f32);
+
+                trace!("Instruction: f32.demote_f64 [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            F64_CONVERT_I32_S => {
+                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                let res: value::F64 = value::F64(v1 as f64);
+
+                trace!("Instruction: f64.convert_i32_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            F64_CONVERT_I32_U => {
+                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                let res: value::F64 = value::F64(v1 as u32 as f64);
+
+                trace!("Instruction: f64.convert_i32_u [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            F64_CONVERT_I64_S => {
+                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                let res: value::F64 = value::F64(v1 as f64);
+
+                trace!("Instruction: f64.convert_i64_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            F64_CONVERT_I64_U => {
+                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                let res: value::F64 = value::F64(v1 as u64 as f64);
+
+                trace!("Instruction: f64.convert_i64_u [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            F64_PROMOTE_F32 => {
+                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                let res: value::F64 = value::F64(v1.0 as f64);
+
+                trace!("Instruction: f64.promote_f32 [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I32_REINTERPRET_F32 => {
+                let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                let res: u32 = v1.0.to_bits();
+
+                trace!("Instruction: i32.reinterpret_f32 [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_REINTERPRET_F64 => {
+                let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                let res: u64 = v1.0.to_bits();
+
+                trace!("Instruction: i64.reinterpret_f64 [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
             F32_REINTERPRET_I32 => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                 let res: value::F32 = value::F32::from_bits(v1 as u32);
@@ -1068,6 +1722,15 @@ pub(super) fn run<H: HookSet>(
                 trace!("Instruction: f32.reinterpret_i32 [{v1}] -> [{res}]");
                 stack.push_value(res.into());
             }
+            F64_REINTERPRET_I64 => {
+                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                let res: value::F64 = value::F64::from_bits(v1 as u64);
+
+                trace!(
+                    "Instruction: f64.reinterpret_i64 [{v1}] -> [value::F64::from_bits(v1 as u64)]"
+                );
+                stack.push_value(res.into());
+            }
 
             F64_ABS => {
                 let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1174,11 +1837,921 @@ pub(super) fn run<H: HookSet>(
                 trace!("Instruction: f64.copysign [{v1} {v2}] -> [{res}]");
                 stack.push_value(res.into());
             }
+            I32_EXTEND8_S => {
+                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                let res: i32 = v1 as i8 as i32;
+
+                trace!("Instruction: i32.extend8_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I32_EXTEND16_S => {
+                let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                let res: i32 = v1 as i16 as i32;
+
+                trace!("Instruction: i32.extend16_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_EXTEND8_S => {
+                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                let res: i64 = v1 as i8 as i64;
+
+                trace!("Instruction: i64.extend8_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_EXTEND16_S => {
+                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                let res: i64 = v1 as i16 as i64;
+
+                trace!("Instruction: i64.extend16_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            I64_EXTEND32_S => {
+                let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                let res: i64 = v1 as i32 as i64;
+
+                trace!("Instruction: i64.extend32_s [{v1}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            REF_NULL => {
+                let ty = RefType::read_unvalidated(&mut wasm);
+
+                trace!("Instruction: ref.null {ty:?} [] -> [null]");
+                stack.push_value(Value::Ref(Ref::Null(ty)));
+            }
+            REF_IS_NULL => {
+                let Value::Ref(reference) = stack.pop_any_value() else {
+                    unreachable_validated!()
+                };
+                let res = u32::from(reference.is_null());
+
+                trace!("Instruction: ref.is_null [{reference:?}] -> [{res}]");
+                stack.push_value(res.into());
+            }
+            REF_FUNC => {
+                let func_idx = wasm.read_var_u32().unwrap_validated() as FuncIdx;
+
+                trace!("Instruction: ref.func {func_idx} [] -> [{func_idx}]");
+                stack.push_value(Value::Ref(Ref::Func(func_idx)));
+            }
+            FC_EXTENSIONS => {
+                let second_instr = wasm.read_var_u32().unwrap_validated();
+
+                use crate::core::reader::types::opcode::fc_extensions::*;
+                match second_instr {
+                    // The saturating truncations match the semantics of Rust's `as` casts: NaN is
+                    // converted to 0 and values outside of the integer's range are clamped
+                    I32_TRUNC_SAT_F32_S => {
+                        let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                        let res: i32 = v1.0 as i32;
+
+                        trace!("Instruction: i32.trunc_sat_f32_s [{v1}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I32_TRUNC_SAT_F32_U => {
+                        let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                        let res: u32 = v1.0 as u32;
+
+                        trace!("Instruction: i32.trunc_sat_f32_u [{v1}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I32_TRUNC_SAT_F64_S => {
+                        let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                        let res: i32 = v1.0 as i32;
+
+                        trace!("Instruction: i32.trunc_sat_f64_s [{v1}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I32_TRUNC_SAT_F64_U => {
+                        let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                        let res: u32 = v1.0 as u32;
+
+                        trace!("Instruction: i32.trunc_sat_f64_u [{v1}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I64_TRUNC_SAT_F32_S => {
+                        let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                        let res: i64 = v1.0 as i64;
+
+                        trace!("Instruction: i64.trunc_sat_f32_s [{v1}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I64_TRUNC_SAT_F32_U => {
+                        let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                        let res: u64 = v1.0 as u64;
+
+                        trace!("Instruction: i64.trunc_sat_f32_u [{v1}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I64_TRUNC_SAT_F64_S => {
+                        let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                        let res: i64 = v1.0 as i64;
+
+                        trace!("Instruction: i64.trunc_sat_f64_s [{v1}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I64_TRUNC_SAT_F64_U => {
+                        let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                        let res: u64 = v1.0 as u64;
+
+                        trace!("Instruction: i64.trunc_sat_f64_u [{v1}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    // All bulk memory and table instructions check their bounds before writing
+                    MEMORY_INIT => {
+                        let data_idx = wasm.read_var_u32().unwrap_validated() as DataIdx;
+                        let _mem_idx = wasm.read_var_u32().unwrap_validated();
+                        let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let s: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let d: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let mem = store.mems.first_mut().unwrap_validated(); // there is only one memory allowed as of now
+                        let data = &store.datas.get(data_idx).unwrap_validated().data;
+                        mem.init(d, data, s, n)?;
+
+                        #[cfg(feature = "hooks")]
+                        hooks.memory_store_hook(
+                            location!(),
+                            d as usize,
+                            &mem.data[d as usize..][..n as usize],
+                        );
+
+                        trace!("Instruction: memory.init {data_idx} [{d} {s} {n}] -> []");
+                    }
+                    DATA_DROP => {
+                        let data_idx = wasm.read_var_u32().unwrap_validated() as DataIdx;
+                        store.datas.get_mut(data_idx).unwrap_validated().data = Vec::new();
+
+                        trace!("Instruction: data.drop {data_idx} [] -> []");
+                    }
+                    MEMORY_COPY => {
+                        let _dest_mem_idx = wasm.read_var_u32().unwrap_validated();
+                        let _src_mem_idx = wasm.read_var_u32().unwrap_validated();
+                        let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let s: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let d: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let mem = store.mems.first_mut().unwrap_validated(); // there is only one memory allowed as of now
+                        mem.copy_within(d, s, n)?;
+
+                        #[cfg(feature = "hooks")]
+                        {
+                            // the destination now holds the bytes that were read from the source
+                            let bytes = &mem.data[d as usize..][..n as usize];
+                            let location = location!();
+                            hooks.memory_load_hook(location, s as usize, bytes);
+                            hooks.memory_store_hook(location, d as usize, bytes);
+                        }
+
+                        trace!("Instruction: memory.copy [{d} {s} {n}] -> []");
+                    }
+                    MEMORY_FILL => {
+                        let _mem_idx = wasm.read_var_u32().unwrap_validated();
+                        let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let val: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let d: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let mem = store.mems.first_mut().unwrap_validated(); // there is only one memory allowed as of now
+                        mem.fill(d, val as u8, n)?;
+
+                        #[cfg(feature = "hooks")]
+                        hooks.memory_store_hook(
+                            location!(),
+                            d as usize,
+                            &mem.data[d as usize..][..n as usize],
+                        );
+
+                        trace!("Instruction: memory.fill [{d} {val} {n}] -> []");
+                    }
+                    TABLE_INIT => {
+                        let elem_idx = wasm.read_var_u32().unwrap_validated() as ElemIdx;
+                        let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+                        let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let s: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let d: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let table = store.tables.get_mut(table_idx).unwrap_validated();
+                        let references = &store.elems.get(elem_idx).unwrap_validated().references;
+                        table.init(d, references, s, n)?;
+
+                        trace!(
+                            "Instruction: table.init {elem_idx} {table_idx} [{d} {s} {n}] -> []"
+                        );
+                    }
+                    ELEM_DROP => {
+                        let elem_idx = wasm.read_var_u32().unwrap_validated() as ElemIdx;
+                        store.elems.get_mut(elem_idx).unwrap_validated().references = Vec::new();
+
+                        trace!("Instruction: elem.drop {elem_idx} [] -> []");
+                    }
+                    TABLE_COPY => {
+                        let dest_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+                        let src_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+                        let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let s: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let d: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        if dest_idx == src_idx {
+                            let table = store.tables.get_mut(dest_idx).unwrap_validated();
+                            table.copy_within(d, s, n)?;
+                        } else {
+                            // Split the tables to borrow the source and destination at once
+                            let (dest, src) = if dest_idx < src_idx {
+                                let (lower, upper) = store.tables.split_at_mut(src_idx);
+                                (&mut lower[dest_idx], &upper[0])
+                            } else {
+                                let (lower, upper) = store.tables.split_at_mut(dest_idx);
+                                (&mut upper[0], &lower[src_idx])
+                            };
+                            dest.init(d, &src.elem, s, n)?;
+                        }
+
+                        trace!("Instruction: table.copy {dest_idx} {src_idx} [{d} {s} {n}] -> []");
+                    }
+                    TABLE_GROW => {
+                        let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+                        let table = store.tables.get_mut(table_idx).unwrap_validated();
+                        let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let Value::Ref(init) = stack.pop_value(ValType::RefType(table.ty.et))
+                        else {
+                            unreachable_validated!()
+                        };
+
+                        // Failure is not a trap, but signaled to the program by returning -1
+                        let old_size = table.grow(n, init).unwrap_or(u32::MAX);
+
+                        trace!(
+                            "Instruction: table.grow {table_idx} [{init:?} {n}] -> [{}]",
+                            old_size as i32
+                        );
+                        stack.push_value(Value::I32(old_size));
+                    }
+                    TABLE_SIZE => {
+                        let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+                        let size = store.tables.get(table_idx).unwrap_validated().size();
+
+                        trace!("Instruction: table.size {table_idx} [] -> [{size}]");
+                        stack.push_value(Value::I32(size));
+                    }
+                    TABLE_FILL => {
+                        let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+                        let table = store.tables.get_mut(table_idx).unwrap_validated();
+                        let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let Value::Ref(val) = stack.pop_value(ValType::RefType(table.ty.et)) else {
+                            unreachable_validated!()
+                        };
+                        let i: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        table.fill(i, val, n)?;
+
+                        trace!("Instruction: table.fill {table_idx} [{i} {val:?} {n}] -> []");
+                    }
+                    _ => unreachable_validated!(),
+                }
+            }
+            FD_EXTENSIONS => {
+                let second_instr = wasm.read_var_u32().unwrap_validated();
+
+                use crate::core::reader::types::opcode::fd_extensions::*;
+                match second_instr {
+                    V128_LOAD => {
+                        let memarg = MemArg::read_unvalidated(&mut wasm);
+                        let relative_address: u32 =
+                            stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
+
+                        let data: V128 = hooked_load(
+                            mem,
+                            hooks,
+                            || location!(),
+                            memarg.offset,
+                            relative_address,
+                        )?;
+
+                        trace!("Instruction: v128.load [{relative_address}] -> [{data:?}]");
+                        stack.push_value(data.into());
+                    }
+                    V128_LOAD8X8_S | V128_LOAD8X8_U | V128_LOAD16X4_S | V128_LOAD16X4_U
+                    | V128_LOAD32X2_S | V128_LOAD32X2_U => {
+                        let memarg = MemArg::read_unvalidated(&mut wasm);
+                        let relative_address: u32 =
+                            stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
+
+                        let bytes: [u8; 8] = hooked_load(
+                            mem,
+                            hooks,
+                            || location!(),
+                            memarg.offset,
+                            relative_address,
+                        )?;
+                        let data = match second_instr {
+                            V128_LOAD8X8_S => simd::load_extend::<i8, i16>(bytes),
+                            V128_LOAD8X8_U => simd::load_extend::<u8, u16>(bytes),
+                            V128_LOAD16X4_S => simd::load_extend::<i16, i32>(bytes),
+                            V128_LOAD16X4_U => simd::load_extend::<u16, u32>(bytes),
+                            V128_LOAD32X2_S => simd::load_extend::<i32, i64>(bytes),
+                            _ => simd::load_extend::<u32, u64>(bytes),
+                        };
+
+                        trace!("Instruction: v128.loadNxM [{relative_address}] -> [{data:?}]");
+                        stack.push_value(data.into());
+                    }
+                    V128_LOAD8_SPLAT | V128_LOAD16_SPLAT | V128_LOAD32_SPLAT
+                    | V128_LOAD64_SPLAT | V128_LOAD32_ZERO | V128_LOAD64_ZERO => {
+                        let memarg = MemArg::read_unvalidated(&mut wasm);
+                        let relative_address: u32 =
+                            stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
+
+                        let offset = memarg.offset;
+                        let data = match second_instr {
+                            V128_LOAD8_SPLAT => simd::splat(u8::from_le_bytes(hooked_load(
+                                mem,
+                                hooks,
+                                || location!(),
+                                offset,
+                                relative_address,
+                            )?)),
+                            V128_LOAD16_SPLAT => simd::splat(u16::from_le_bytes(hooked_load(
+                                mem,
+                                hooks,
+                                || location!(),
+                                offset,
+                                relative_address,
+                            )?)),
+                            V128_LOAD32_SPLAT => simd::splat(u32::from_le_bytes(hooked_load(
+                                mem,
+                                hooks,
+                                || location!(),
+                                offset,
+                                relative_address,
+                            )?)),
+                            V128_LOAD64_SPLAT => simd::splat(u64::from_le_bytes(hooked_load(
+                                mem,
+                                hooks,
+                                || location!(),
+                                offset,
+                                relative_address,
+                            )?)),
+                            V128_LOAD32_ZERO => simd::load_zero::<4>(hooked_load(
+                                mem,
+                                hooks,
+                                || location!(),
+                                offset,
+                                relative_address,
+                            )?),
+                            _ => simd::load_zero::<8>(hooked_load(
+                                mem,
+                                hooks,
+                                || location!(),
+                                offset,
+                                relative_address,
+                            )?),
+                        };
+
+                        trace!(
+                            "Instruction: v128.loadN_splat/zero [{relative_address}] -> [{data:?}]"
+                        );
+                        stack.push_value(data.into());
+                    }
+                    V128_STORE => {
+                        let memarg = MemArg::read_unvalidated(&mut wasm);
+                        let data: V128 = stack.pop_value(ValType::VecType).into();
+                        let relative_address: u32 =
+                            stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let mem = store.mems.get_mut(0).unwrap_validated(); // there is only one memory allowed as of now
+
+                        hooked_store(
+                            mem,
+                            hooks,
+                            || location!(),
+                            memarg.offset,
+                            relative_address,
+                            data,
+                        )?;
+
+                        trace!("Instruction: v128.store [{relative_address} {data:?}] -> []");
+                    }
+                    V128_LOAD8_LANE | V128_LOAD16_LANE | V128_LOAD32_LANE | V128_LOAD64_LANE => {
+                        let memarg = MemArg::read_unvalidated(&mut wasm);
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v: V128 = stack.pop_value(ValType::VecType).into();
+                        let relative_address: u32 =
+                            stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let mem = store.mems.first().unwrap_validated(); // there is only one memory allowed as of now
+
+                        let offset = memarg.offset;
+                        let res = match second_instr {
+                            V128_LOAD8_LANE => simd::replace_lane(
+                                v,
+                                lane,
+                                u8::from_le_bytes(hooked_load(
+                                    mem,
+                                    hooks,
+                                    || location!(),
+                                    offset,
+                                    relative_address,
+                                )?),
+                            ),
+                            V128_LOAD16_LANE => simd::replace_lane(
+                                v,
+                                lane,
+                                u16::from_le_bytes(hooked_load(
+                                    mem,
+                                    hooks,
+                                    || location!(),
+                                    offset,
+                                    relative_address,
+                                )?),
+                            ),
+                            V128_LOAD32_LANE => simd::replace_lane(
+                                v,
+                                lane,
+                                u32::from_le_bytes(hooked_load(
+                                    mem,
+                                    hooks,
+                                    || location!(),
+                                    offset,
+                                    relative_address,
+                                )?),
+                            ),
+                            _ => simd::replace_lane(
+                                v,
+                                lane,
+                                u64::from_le_bytes(hooked_load(
+                                    mem,
+                                    hooks,
+                                    || location!(),
+                                    offset,
+                                    relative_address,
+                                )?),
+                            ),
+                        };
+
+                        trace!("Instruction: v128.loadN_lane {lane} [{relative_address} {v:?}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    V128_STORE8_LANE | V128_STORE16_LANE | V128_STORE32_LANE
+                    | V128_STORE64_LANE => {
+                        let memarg = MemArg::read_unvalidated(&mut wasm);
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v: V128 = stack.pop_value(ValType::VecType).into();
+                        let relative_address: u32 =
+                            stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let mem = store.mems.get_mut(0).unwrap_validated(); // there is only one memory allowed as of now
+
+                        let offset = memarg.offset;
+                        match second_instr {
+                            V128_STORE8_LANE => {
+                                let data = simd::extract_lane::<u8>(&v, lane).to_le_bytes();
+                                hooked_store(
+                                    mem,
+                                    hooks,
+                                    || location!(),
+                                    offset,
+                                    relative_address,
+                                    data,
+                                )?;
+                            }
+                            V128_STORE16_LANE => {
+                                let data = simd::extract_lane::<u16>(&v, lane).to_le_bytes();
+                                hooked_store(
+                                    mem,
+                                    hooks,
+                                    || location!(),
+                                    offset,
+                                    relative_address,
+                                    data,
+                                )?;
+                            }
+                            V128_STORE32_LANE => {
+                                let data = simd::extract_lane::<u32>(&v, lane).to_le_bytes();
+                                hooked_store(
+                                    mem,
+                                    hooks,
+                                    || location!(),
+                                    offset,
+                                    relative_address,
+                                    data,
+                                )?;
+                            }
+                            _ => {
+                                let data = simd::extract_lane::<u64>(&v, lane).to_le_bytes();
+                                hooked_store(
+                                    mem,
+                                    hooks,
+                                    || location!(),
+                                    offset,
+                                    relative_address,
+                                    data,
+                                )?;
+                            }
+                        }
+
+                        trace!(
+                            "Instruction: v128.storeN_lane {lane} [{relative_address} {v:?}] -> []"
+                        );
+                    }
+                    V128_CONST => {
+                        let data: V128 = wasm.strip_bytes::<16>().unwrap_validated();
+
+                        trace!("Instruction: v128.const [] -> [{data:?}]");
+                        stack.push_value(data.into());
+                    }
+                    I8X16_SHUFFLE => {
+                        let lanes: [u8; 16] = wasm.strip_bytes::<16>().unwrap_validated();
+                        let v2: V128 = stack.pop_value(ValType::VecType).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::shuffle(&v1, &v2, &lanes);
+
+                        trace!("Instruction: i8x16.shuffle {lanes:?} [{v1:?} {v2:?}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    I8X16_SPLAT => {
+                        let v1: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let res = simd::splat(v1 as u8);
+
+                        trace!("Instruction: i8x16.splat [{v1}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    I16X8_SPLAT => {
+                        let v1: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let res = simd::splat(v1 as u16);
+
+                        trace!("Instruction: i16x8.splat [{v1}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    I32X4_SPLAT => {
+                        let v1: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let res = simd::splat(v1);
+
+                        trace!("Instruction: i32x4.splat [{v1}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    I64X2_SPLAT => {
+                        let v1: u64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                        let res = simd::splat(v1);
+
+                        trace!("Instruction: i64x2.splat [{v1}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    F32X4_SPLAT => {
+                        let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                        let res = simd::splat(v1.0);
+
+                        trace!("Instruction: f32x4.splat [{v1}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    F64X2_SPLAT => {
+                        let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                        let res = simd::splat(v1.0);
+
+                        trace!("Instruction: f64x2.splat [{v1}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    I8X16_EXTRACT_LANE_S => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res: i32 = simd::extract_lane::<i8>(&v1, lane).into();
+
+                        trace!("Instruction: i8x16.extract_lane_s {lane} [{v1:?}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I8X16_EXTRACT_LANE_U => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res: u32 = simd::extract_lane::<u8>(&v1, lane).into();
+
+                        trace!("Instruction: i8x16.extract_lane_u {lane} [{v1:?}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I16X8_EXTRACT_LANE_S => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res: i32 = simd::extract_lane::<i16>(&v1, lane).into();
+
+                        trace!("Instruction: i16x8.extract_lane_s {lane} [{v1:?}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I16X8_EXTRACT_LANE_U => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res: u32 = simd::extract_lane::<u16>(&v1, lane).into();
+
+                        trace!("Instruction: i16x8.extract_lane_u {lane} [{v1:?}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I32X4_EXTRACT_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res: u32 = simd::extract_lane(&v1, lane);
+
+                        trace!("Instruction: i32x4.extract_lane {lane} [{v1:?}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I64X2_EXTRACT_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res: u64 = simd::extract_lane(&v1, lane);
+
+                        trace!("Instruction: i64x2.extract_lane {lane} [{v1:?}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    F32X4_EXTRACT_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = value::F32(simd::extract_lane(&v1, lane));
+
+                        trace!("Instruction: f32x4.extract_lane {lane} [{v1:?}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    F64X2_EXTRACT_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = value::F64(simd::extract_lane(&v1, lane));
+
+                        trace!("Instruction: f64x2.extract_lane {lane} [{v1:?}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I8X16_REPLACE_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v2: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::replace_lane(v1, lane, v2 as u8);
+
+                        trace!("Instruction: i8x16.replace_lane {lane} [{v1:?} {v2}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    I16X8_REPLACE_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v2: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::replace_lane(v1, lane, v2 as u16);
+
+                        trace!("Instruction: i16x8.replace_lane {lane} [{v1:?} {v2}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    I32X4_REPLACE_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v2: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::replace_lane(v1, lane, v2);
+
+                        trace!("Instruction: i32x4.replace_lane {lane} [{v1:?} {v2}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    I64X2_REPLACE_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v2: u64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::replace_lane(v1, lane, v2);
+
+                        trace!("Instruction: i64x2.replace_lane {lane} [{v1:?} {v2}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    F32X4_REPLACE_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::replace_lane(v1, lane, v2.0);
+
+                        trace!("Instruction: f32x4.replace_lane {lane} [{v1:?} {v2}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    F64X2_REPLACE_LANE => {
+                        let lane = wasm.read_u8().unwrap_validated();
+                        let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::replace_lane(v1, lane, v2.0);
+
+                        trace!("Instruction: f64x2.replace_lane {lane} [{v1:?} {v2}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    V128_NOT
+                    | I8X16_ABS
+                    | I8X16_NEG
+                    | I8X16_POPCNT
+                    | I16X8_ABS
+                    | I16X8_NEG
+                    | I32X4_ABS
+                    | I32X4_NEG
+                    | I64X2_ABS
+                    | I64X2_NEG
+                    | F32X4_ABS
+                    | F32X4_NEG
+                    | F32X4_SQRT
+                    | F32X4_CEIL
+                    | F32X4_FLOOR
+                    | F32X4_TRUNC
+                    | F32X4_NEAREST
+                    | F64X2_ABS
+                    | F64X2_NEG
+                    | F64X2_SQRT
+                    | F64X2_CEIL
+                    | F64X2_FLOOR
+                    | F64X2_TRUNC
+                    | F64X2_NEAREST
+                    | I16X8_EXTADD_PAIRWISE_I8X16_S
+                    | I16X8_EXTADD_PAIRWISE_I8X16_U
+                    | I32X4_EXTADD_PAIRWISE_I16X8_S
+                    | I32X4_EXTADD_PAIRWISE_I16X8_U
+                    | I16X8_EXTEND_LOW_I8X16_S
+                    | I16X8_EXTEND_HIGH_I8X16_S
+                    | I16X8_EXTEND_LOW_I8X16_U
+                    | I16X8_EXTEND_HIGH_I8X16_U
+                    | I32X4_EXTEND_LOW_I16X8_S
+                    | I32X4_EXTEND_HIGH_I16X8_S
+                    | I32X4_EXTEND_LOW_I16X8_U
+                    | I32X4_EXTEND_HIGH_I16X8_U
+                    | I64X2_EXTEND_LOW_I32X4_S
+                    | I64X2_EXTEND_HIGH_I32X4_S
+                    | I64X2_EXTEND_LOW_I32X4_U
+                    | I64X2_EXTEND_HIGH_I32X4_U
+                    | I32X4_TRUNC_SAT_F32X4_S
+                    | I32X4_TRUNC_SAT_F32X4_U
+                    | I32X4_TRUNC_SAT_F64X2_S_ZERO
+                    | I32X4_TRUNC_SAT_F64X2_U_ZERO
+                    | F32X4_CONVERT_I32X4_S
+                    | F32X4_CONVERT_I32X4_U
+                    | F64X2_CONVERT_LOW_I32X4_S
+                    | F64X2_CONVERT_LOW_I32X4_U
+                    | F32X4_DEMOTE_F64X2_ZERO
+                    | F64X2_PROMOTE_LOW_F32X4 => {
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::unary(second_instr, &v1);
+
+                        trace!("Instruction: vector unop {second_instr:#X} [{v1:?}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    V128_AND
+                    | V128_ANDNOT
+                    | V128_OR
+                    | V128_XOR
+                    | I8X16_SWIZZLE
+                    | I8X16_EQ
+                    | I8X16_NE
+                    | I8X16_LT_S
+                    | I8X16_LT_U
+                    | I8X16_GT_S
+                    | I8X16_GT_U
+                    | I8X16_LE_S
+                    | I8X16_LE_U
+                    | I8X16_GE_S
+                    | I8X16_GE_U
+                    | I16X8_EQ
+                    | I16X8_NE
+                    | I16X8_LT_S
+                    | I16X8_LT_U
+                    | I16X8_GT_S
+                    | I16X8_GT_U
+                    | I16X8_LE_S
+                    | I16X8_LE_U
+                    | I16X8_GE_S
+                    | I16X8_GE_U
+                    | I32X4_EQ
+                    | I32X4_NE
+                    | I32X4_LT_S
+                    | I32X4_LT_U
+                    | I32X4_GT_S
+                    | I32X4_GT_U
+                    | I32X4_LE_S
+                    | I32X4_LE_U
+                    | I32X4_GE_S
+                    | I32X4_GE_U
+                    | I64X2_EQ
+                    | I64X2_NE
+                    | I64X2_LT_S
+                    | I64X2_GT_S
+                    | I64X2_LE_S
+                    | I64X2_GE_S
+                    | F32X4_EQ
+                    | F32X4_NE
+                    | F32X4_LT
+                    | F32X4_GT
+                    | F32X4_LE
+                    | F32X4_GE
+                    | F64X2_EQ
+                    | F64X2_NE
+                    | F64X2_LT
+                    | F64X2_GT
+                    | F64X2_LE
+                    | F64X2_GE
+                    | I8X16_ADD
+                    | I8X16_ADD_SAT_S
+                    | I8X16_ADD_SAT_U
+                    | I8X16_SUB
+                    | I8X16_SUB_SAT_S
+                    | I8X16_SUB_SAT_U
+                    | I8X16_MIN_S
+                    | I8X16_MIN_U
+                    | I8X16_MAX_S
+                    | I8X16_MAX_U
+                    | I8X16_AVGR_U
+                    | I16X8_ADD
+                    | I16X8_ADD_SAT_S
+                    | I16X8_ADD_SAT_U
+                    | I16X8_SUB
+                    | I16X8_SUB_SAT_S
+                    | I16X8_SUB_SAT_U
+                    | I16X8_MUL
+                    | I16X8_MIN_S
+                    | I16X8_MIN_U
+                    | I16X8_MAX_S
+                    | I16X8_MAX_U
+                    | I16X8_AVGR_U
+                    | I16X8_Q15MULR_SAT_S
+                    | I32X4_ADD
+                    | I32X4_SUB
+                    | I32X4_MUL
+                    | I32X4_MIN_S
+                    | I32X4_MIN_U
+                    | I32X4_MAX_S
+                    | I32X4_MAX_U
+                    | I32X4_DOT_I16X8_S
+                    | I64X2_ADD
+                    | I64X2_SUB
+                    | I64X2_MUL
+                    | I8X16_NARROW_I16X8_S
+                    | I8X16_NARROW_I16X8_U
+                    | I16X8_NARROW_I32X4_S
+                    | I16X8_NARROW_I32X4_U
+                    | I16X8_EXTMUL_LOW_I8X16_S
+                    | I16X8_EXTMUL_HIGH_I8X16_S
+                    | I16X8_EXTMUL_LOW_I8X16_U
+                    | I16X8_EXTMUL_HIGH_I8X16_U
+                    | I32X4_EXTMUL_LOW_I16X8_S
+                    | I32X4_EXTMUL_HIGH_I16X8_S
+                    | I32X4_EXTMUL_LOW_I16X8_U
+                    | I32X4_EXTMUL_HIGH_I16X8_U
+                    | I64X2_EXTMUL_LOW_I32X4_S
+                    | I64X2_EXTMUL_HIGH_I32X4_S
+                    | I64X2_EXTMUL_LOW_I32X4_U
+                    | I64X2_EXTMUL_HIGH_I32X4_U
+                    | F32X4_ADD
+                    | F32X4_SUB
+                    | F32X4_MUL
+                    | F32X4_DIV
+                    | F32X4_MIN
+                    | F32X4_MAX
+                    | F32X4_PMIN
+                    | F32X4_PMAX
+                    | F64X2_ADD
+                    | F64X2_SUB
+                    | F64X2_MUL
+                    | F64X2_DIV
+                    | F64X2_MIN
+                    | F64X2_MAX
+                    | F64X2_PMIN
+                    | F64X2_PMAX => {
+                        let v2: V128 = stack.pop_value(ValType::VecType).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::binary(second_instr, &v1, &v2);
+
+                        trace!("Instruction: vector binop {second_instr:#X} [{v1:?} {v2:?}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    V128_BITSELECT => {
+                        let v3: V128 = stack.pop_value(ValType::VecType).into();
+                        let v2: V128 = stack.pop_value(ValType::VecType).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::bitselect(&v1, &v2, &v3);
+
+                        trace!("Instruction: v128.bitselect [{v1:?} {v2:?} {v3:?}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    V128_ANY_TRUE | I8X16_ALL_TRUE | I8X16_BITMASK | I16X8_ALL_TRUE
+                    | I16X8_BITMASK | I32X4_ALL_TRUE | I32X4_BITMASK | I64X2_ALL_TRUE
+                    | I64X2_BITMASK => {
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::test(second_instr, &v1);
+
+                        trace!("Instruction: vector test {second_instr:#X} [{v1:?}] -> [{res}]");
+                        stack.push_value(res.into());
+                    }
+                    I8X16_SHL | I8X16_SHR_S | I8X16_SHR_U | I16X8_SHL | I16X8_SHR_S
+                    | I16X8_SHR_U | I32X4_SHL | I32X4_SHR_S | I32X4_SHR_U | I64X2_SHL
+                    | I64X2_SHR_S | I64X2_SHR_U => {
+                        let v2: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let v1: V128 = stack.pop_value(ValType::VecType).into();
+                        let res = simd::shift(second_instr, &v1, v2);
+
+                        trace!("Instruction: vector shift {second_instr:#X} [{v1:?} {v2}] -> [{res:?}]");
+                        stack.push_value(res.into());
+                    }
+                    _ => unreachable_validated!(),
+                }
+            }
 
             other => {
                 trace!("Unknown instruction {other:#x}, skipping..");
             }
         }
     }
-    Ok(())
+    Ok(ExecutionOutcome::Finished(()))
 }
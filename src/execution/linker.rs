@@ -0,0 +1,145 @@
+//! Resolution of a module's imports to functions and globals provided by the host.
+
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::core::reader::types::global::GlobalType;
+use crate::core::reader::types::{FuncType, ResultType, ValType};
+use crate::execution::store::MemInst;
+use crate::execution::value::Value;
+use crate::RuntimeError;
+
+/// A function provided by the host, which can be imported by a module
+///
+/// It receives the arguments of the call and returns the function's results. Returning an error
+/// traps, which aborts the invocation that called the host function.
+pub type HostFunc =
+    Box<dyn FnMut(&mut HostCallContext<'_>, &[Value]) -> Result<Vec<Value>, RuntimeError>>;
+
+/// Access to the calling instance during a call of a [`HostFunc`]
+pub struct HostCallContext<'a> {
+    pub(crate) mems: &'a mut [MemInst],
+}
+
+impl HostCallContext<'_> {
+    /// Returns the contents of the calling instance's linear memory, if it has one
+    pub fn memory(&mut self) -> Option<&mut [u8]> {
+        self.mems.first_mut().map(|mem| mem.data.as_mut_slice())
+    }
+}
+
+/// A function defined in a [`Linker`]
+pub(crate) struct HostFuncDefinition {
+    pub module_name: String,
+    pub name: String,
+    pub ty: FuncType,
+    pub func: HostFunc,
+}
+
+/// A global defined in a [`Linker`]
+pub(crate) struct HostGlobalDefinition {
+    pub module_name: String,
+    pub name: String,
+    pub ty: GlobalType,
+    pub value: Value,
+}
+
+/// A collection of host functions and globals, each identified by a module name and a name, which
+/// is used to resolve the imports of a module during instantiation
+#[derive(Default)]
+pub struct Linker {
+    pub(crate) host_funcs: Vec<HostFuncDefinition>,
+    pub(crate) host_globals: Vec<HostGlobalDefinition>,
+}
+
+impl Linker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define a host function of the type `[params] -> [returns]`, which satisfies imports of
+    /// `module_name`.`name`
+    ///
+    /// A previous definition with the same module name and name is replaced.
+    pub fn define_func(
+        &mut self,
+        module_name: &str,
+        name: &str,
+        params: &[ValType],
+        returns: &[ValType],
+        func: impl FnMut(&mut HostCallContext<'_>, &[Value]) -> Result<Vec<Value>, RuntimeError>
+            + 'static,
+    ) -> &mut Self {
+        self.host_funcs
+            .retain(|def| def.module_name != module_name || def.name != name);
+
+        self.host_funcs.push(HostFuncDefinition {
+            module_name: module_name.to_owned(),
+            name: name.to_owned(),
+            ty: FuncType {
+                params: ResultType {
+                    valtypes: params.to_vec(),
+                },
+                returns: ResultType {
+                    valtypes: returns.to_vec(),
+                },
+            },
+            func: Box::new(func),
+        });
+
+        self
+    }
+
+    /// Define a global with the initial value `value`, which satisfies imports of
+    /// `module_name`.`name`
+    ///
+    /// Every importing instance receives its own copy of the global, i.e. writes to a mutable
+    /// global are not shared. A previous definition with the same module name and name is
+    /// replaced.
+    pub fn define_global(
+        &mut self,
+        module_name: &str,
+        name: &str,
+        value: Value,
+        is_mut: bool,
+    ) -> &mut Self {
+        self.host_globals
+            .retain(|def| def.module_name != module_name || def.name != name);
+
+        self.host_globals.push(HostGlobalDefinition {
+            module_name: module_name.to_owned(),
+            name: name.to_owned(),
+            ty: GlobalType {
+                ty: value.to_ty(),
+                is_mut,
+            },
+            value,
+        });
+
+        self
+    }
+
+    /// Find the global satisfying an import of `module_name`.`name`
+    pub(crate) fn find_global(&self, module_name: &str, name: &str) -> Option<(GlobalType, Value)> {
+        self.host_globals
+            .iter()
+            .find(|def| def.module_name == module_name && def.name == name)
+            .map(|def| (def.ty, def.value))
+    }
+
+    /// Find the function satisfying an import of `module_name`.`name`, returning its index
+    pub(crate) fn find_func(&self, module_name: &str, name: &str) -> Option<(usize, &FuncType)> {
+        self.host_funcs
+            .iter()
+            .enumerate()
+            .find(|(_, def)| def.module_name == module_name && def.name == name)
+            .map(|(idx, def)| (idx, &def.ty))
+    }
+
+    /// Returns all host functions, in the order of the indices returned by [`Self::find_func`]
+    pub(crate) fn into_host_funcs(self) -> Vec<HostFunc> {
+        self.host_funcs.into_iter().map(|def| def.func).collect()
+    }
+}
@@ -26,6 +26,11 @@ impl Locals {
         Self { data }
     }
 
+    /// Returns the values of all locals, including parameters
+    pub fn values(&self) -> &[Value] {
+        &self.data
+    }
+
     pub fn get(&self, idx: usize) -> &Value {
         self.data.get(idx).unwrap_validated()
     }
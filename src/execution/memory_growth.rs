@@ -0,0 +1,44 @@
+//! Embedder control over the growth of linear memories.
+//!
+//! A `memory.grow` instruction only succeeds if the memory stays within its maximum size (or the
+//! 65536 pages addressable with 32 bits) and the instance's [`MemoryGrowthPolicy`] allows it.
+//! Otherwise, the instruction fails gracefully by returning `-1` to the program.
+
+/// Decides whether a linear memory may grow
+///
+/// Any `FnMut(u32, u32) -> bool` closure is a policy as well.
+pub trait MemoryGrowthPolicy {
+    /// Returns whether a memory of `current_pages` may grow to `requested_pages`
+    ///
+    /// This is only called for growth that is within the memory's limits.
+    fn allow_growth(&mut self, current_pages: u32, requested_pages: u32) -> bool;
+}
+
+impl<F> MemoryGrowthPolicy for F
+where
+    F: FnMut(u32, u32) -> bool,
+{
+    fn allow_growth(&mut self, current_pages: u32, requested_pages: u32) -> bool {
+        self(current_pages, requested_pages)
+    }
+}
+
+/// Caps the size of memories to a fixed number of pages, e.g. to stay within a RAM budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxPages(pub u32);
+
+impl MemoryGrowthPolicy for MaxPages {
+    fn allow_growth(&mut self, _current_pages: u32, requested_pages: u32) -> bool {
+        requested_pages <= self.0
+    }
+}
+
+/// Refuses any growth
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoGrowth;
+
+impl MemoryGrowthPolicy for NoGrowth {
+    fn allow_growth(&mut self, _current_pages: u32, _requested_pages: u32) -> bool {
+        false
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::boxed::Box;
 use alloc::vec::Vec;
 
 use interpreter_loop::run;
@@ -5,22 +6,45 @@ use locals::Locals;
 use value_stack::Stack;
 
 use crate::core::indices::FuncIdx;
+use crate::core::reader::span::Span;
+use crate::core::reader::types::const_expr::eval_const_expr;
+use crate::core::reader::types::data::DataMode;
+use crate::core::reader::types::element::{ElemItems, ElemMode};
 use crate::core::reader::types::export::{Export, ExportDesc};
+use crate::core::reader::types::import::ImportDesc;
 use crate::core::reader::types::{FuncType, ValType};
 use crate::core::reader::WasmReader;
+use crate::core::sidetable::Sidetable;
 use crate::execution::assert_validated::UnwrapValidatedExt;
-use crate::execution::hooks::{EmptyHookSet, HookSet};
-use crate::execution::store::{FuncInst, GlobalInst, MemInst, Store};
-use crate::execution::value::Value;
+use crate::execution::coverage::Decision;
+use crate::execution::fuel::{ExecutionOutcome, FuelCostTable};
+#[cfg(feature = "hooks")]
+use crate::execution::hooks::locate;
+use crate::execution::hooks::{EmptyHookSet, HookAction, HookSet};
+use crate::execution::linker::{HostCallContext, Linker};
+use crate::execution::memory_growth::MemoryGrowthPolicy;
+use crate::execution::resumable::Resumable;
+use crate::execution::store::{
+    DataInst, ElemInst, FuncInst, GlobalInst, ImportedFuncInst, LocalFuncInst, MemInst, Store,
+    TableInst,
+};
+use crate::execution::value::{Ref, Value};
 use crate::validation::code::read_declared_locals;
 use crate::value::InteropValueList;
-use crate::{RuntimeError, ValidationInfo};
+use crate::{unreachable_validated, RuntimeError, ValidationInfo};
 
 // TODO
 pub(crate) mod assert_validated;
+pub mod coverage;
+pub mod fuel;
 pub mod hooks;
 mod interpreter_loop;
+pub mod linker;
 pub(crate) mod locals;
+pub mod memory_growth;
+pub mod resumable;
+pub(crate) mod simd;
+pub mod snapshot;
 pub(crate) mod store;
 pub mod value;
 pub mod value_stack;
@@ -32,14 +56,27 @@ where
     pub wasm_bytecode: &'b [u8],
     types: Vec<FuncType>,
     exports: Vec<Export>,
+    sidetable: Sidetable,
+    /// The decisions in the code of the module's functions, found during validation
+    decisions: Vec<Decision>,
     store: Store,
     pub hook_set: H,
+    /// The fuel consumed by each instruction in fuel bounded invocations
+    pub fuel_cost_table: FuelCostTable,
 }
 
 impl<'b> RuntimeInstance<'b, EmptyHookSet> {
     pub fn new(validation_info: &'_ ValidationInfo<'b>) -> Result<Self, RuntimeError> {
         Self::new_with_hooks(validation_info, EmptyHookSet)
     }
+
+    /// Instantiate a module, resolving its imports with the host functions defined in `linker`
+    pub fn new_with_linker(
+        validation_info: &'_ ValidationInfo<'b>,
+        linker: Linker,
+    ) -> Result<Self, RuntimeError> {
+        Self::new_with_linker_and_hooks(validation_info, linker, EmptyHookSet)
+    }
 }
 
 impl<'b, H> RuntimeInstance<'b, H>
@@ -49,17 +86,32 @@ where
     pub fn new_with_hooks(
         validation_info: &'_ ValidationInfo<'b>,
         hook_set: H,
+    ) -> Result<Self, RuntimeError> {
+        Self::new_with_linker_and_hooks(validation_info, Linker::new(), hook_set)
+    }
+
+    /// Instantiate a module, resolving its imports with the host functions defined in `linker`
+    ///
+    /// Fails with [`RuntimeError::UnknownImport`] or [`RuntimeError::ImportTypeMismatch`] if an
+    /// imported function is not defined in `linker` or is defined with a different type.
+    pub fn new_with_linker_and_hooks(
+        validation_info: &'_ ValidationInfo<'b>,
+        linker: Linker,
+        hook_set: H,
     ) -> Result<Self, RuntimeError> {
         trace!("Starting instantiation of bytecode");
 
-        let store = Self::init_store(validation_info);
+        let store = Self::init_store(validation_info, linker)?;
 
         let mut instance = RuntimeInstance {
             wasm_bytecode: validation_info.wasm,
             types: validation_info.types.clone(),
             exports: validation_info.exports.clone(),
+            sidetable: validation_info.sidetable.clone(),
+            decisions: validation_info.decisions.clone(),
             store,
             hook_set,
+            fuel_cost_table: FuelCostTable::default(),
         };
 
         if let Some(start) = validation_info.start {
@@ -75,23 +127,19 @@ where
         func_name: &str,
         param: Param,
     ) -> Result<Returns, RuntimeError> {
-        // TODO: Optimize this search for better than linear-time. Pre-processing will likely be required
-        let func_idx = self.exports.iter().find_map(|export| {
-            if export.name == func_name {
-                match export.desc {
-                    ExportDesc::FuncIdx(idx) => Some(idx),
-                    _ => None,
-                }
-            } else {
-                None
-            }
-        });
+        let func_idx = self.get_exported_func_idx(func_name)?;
+        self.invoke_func(func_idx, param)
+    }
 
-        if let Some(func_idx) = func_idx {
-            self.invoke_func(func_idx, param)
-        } else {
-            Err(RuntimeError::FunctionNotFound)
-        }
+    /// Like [`Self::invoke_named`], but execution is bounded by the given amount of fuel
+    pub fn invoke_named_with_fuel<Param: InteropValueList, Returns: InteropValueList>(
+        &mut self,
+        func_name: &str,
+        param: Param,
+        fuel: u64,
+    ) -> Result<ExecutionOutcome<Returns>, RuntimeError> {
+        let func_idx = self.get_exported_func_idx(func_name)?;
+        self.invoke_func_with_fuel(func_idx, param, fuel)
     }
 
     /// Can only invoke functions with signature `[t1] -> [t2]` as of now.
@@ -100,52 +148,105 @@ where
         func_idx: FuncIdx,
         params: Param,
     ) -> Result<Returns, RuntimeError> {
-        // -=-= Verification =-=-
-        let func_inst = self.store.funcs.get(func_idx).expect("valid FuncIdx");
-        let func_ty = self.types.get(func_inst.ty).unwrap_validated();
-
-        // Check correct function parameters and return types
-        if func_ty.params.valtypes != Param::TYS {
-            panic!("Invalid `Param` generics");
-        }
-        if func_ty.returns.valtypes != Returns::TYS {
-            panic!("Invalid `Returns` generics");
+        match self.invoke_func_metered(func_idx, params, None)? {
+            ExecutionOutcome::Finished(returns) => Ok(returns),
+            ExecutionOutcome::OutOfFuel => {
+                unreachable!("execution without fuel never runs out of it")
+            }
+            ExecutionOutcome::Paused => Err(RuntimeError::UnresumablePause),
         }
+    }
 
-        // Prepare a new stack with the locals for the entry function
-        let mut stack = Stack::new();
-        let locals = Locals::new(
-            params.into_values().into_iter(),
-            func_inst.locals.iter().cloned(),
-        );
+    /// Like [`Self::invoke_func`], but execution is bounded by the given amount of fuel
+    ///
+    /// Each instruction consumes fuel according to [`Self::fuel_cost_table`]. If there is not
+    /// enough fuel left for the next instruction, [`ExecutionOutcome::OutOfFuel`] is returned
+    /// instead of the function's return values.
+    pub fn invoke_func_with_fuel<Param: InteropValueList, Returns: InteropValueList>(
+        &mut self,
+        func_idx: FuncIdx,
+        params: Param,
+        fuel: u64,
+    ) -> Result<ExecutionOutcome<Returns>, RuntimeError> {
+        self.invoke_func_metered(func_idx, params, Some(fuel))
+    }
 
-        // setting `usize::MAX` as return address for the outermost function ensures that we
-        // observably fail upon errornoeusly continuing execution after that function returns.
-        stack.push_stackframe(func_idx, func_ty, locals, usize::MAX);
+    fn get_exported_func_idx(&self, func_name: &str) -> Result<FuncIdx, RuntimeError> {
+        // TODO: Optimize this search for better than linear-time. Pre-processing will likely be required
+        self.exports
+            .iter()
+            .find_map(|export| {
+                if export.name == func_name {
+                    match export.desc {
+                        ExportDesc::FuncIdx(idx) => Some(idx),
+                        _ => None,
+                    }
+                } else {
+                    None
+                }
+            })
+            .ok_or(RuntimeError::FunctionNotFound)
+    }
 
-        // Run the interpreter
-        run(
-            self.wasm_bytecode,
-            &self.types,
-            &mut self.store,
-            &mut stack,
-            EmptyHookSet,
-        )?;
+    /// Prepare an invocation of an exported function, which is executed by resuming it
+    ///
+    /// See [`Self::invoke_func_resumable`].
+    pub fn invoke_named_resumable<Param: InteropValueList, Returns: InteropValueList>(
+        &mut self,
+        func_name: &str,
+        param: Param,
+    ) -> Result<Resumable<'_, 'b, Returns, H>, RuntimeError> {
+        let func_idx = self.get_exported_func_idx(func_name)?;
+        self.invoke_func_resumable(func_idx, param)
+    }
 
-        // Pop return values from stack
-        let return_values = Returns::TYS
-            .iter()
-            .map(|ty| stack.pop_value(*ty))
-            .collect::<Vec<Value>>();
+    /// Prepare an invocation of a function, which is executed by resuming it
+    ///
+    /// No instruction is executed until [`Resumable::resume`] is called. Whenever the invocation
+    /// runs out of fuel it is paused, and may be resumed with more fuel later on.
+    ///
+    /// Fails with [`RuntimeError::FunctionNotFound`] if there is no such function, with
+    /// [`RuntimeError::HostFunctionNotResumable`] if it is imported and with
+    /// [`RuntimeError::FunctionTypeMismatch`] if `Param` or `Returns` do not match its type.
+    pub fn invoke_func_resumable<Param: InteropValueList, Returns: InteropValueList>(
+        &mut self,
+        func_idx: FuncIdx,
+        params: Param,
+    ) -> Result<Resumable<'_, 'b, Returns, H>, RuntimeError> {
+        Resumable::new(self, func_idx, params)
+    }
 
-        // Values are reversed because they were popped from stack one-by-one. Now reverse them back
-        let reversed_values = return_values.into_iter().rev();
-        let ret: Returns = Returns::from_values(reversed_values);
-        debug!("Successfully invoked function");
-        Ok(ret)
+    /// Restrict the growth of this instance's memory beyond its own limits
+    ///
+    /// A `memory.grow` instruction that is refused by `policy` returns `-1` to the program.
+    pub fn set_memory_growth_policy(&mut self, policy: impl MemoryGrowthPolicy + 'static) {
+        self.store.memory_growth_policy = Some(Box::new(policy));
+    }
+
+    /// Invoke a function, optionally bounded by fuel
+    fn invoke_func_metered<Param: InteropValueList, Returns: InteropValueList>(
+        &mut self,
+        func_idx: FuncIdx,
+        params: Param,
+        fuel: Option<u64>,
+    ) -> Result<ExecutionOutcome<Returns>, RuntimeError> {
+        // Host functions run to completion, they can neither run out of fuel nor be paused
+        if let Some(FuncInst::Imported(_)) = self.store.funcs.get(func_idx) {
+            let returns = self.invoke_dynamic(func_idx, params.into_values(), Returns::TYS)?;
+            return Ok(ExecutionOutcome::Finished(Returns::from_values(
+                returns.into_iter(),
+            )));
+        }
+
+        self.invoke_func_resumable(func_idx, params)?
+            .resume_metered(fuel)
     }
 
     /// Invokes a function with the given parameters, and return types which are not known at compile time.
+    ///
+    /// Fails with [`RuntimeError::FunctionNotFound`] if there is no such function and with
+    /// [`RuntimeError::FunctionTypeMismatch`] if `params` or `ret_types` do not match its type.
+    /// Imported functions are called directly, without invoking any hooks.
     pub fn invoke_dynamic(
         &mut self,
         func_idx: FuncIdx,
@@ -153,43 +254,73 @@ where
         ret_types: &[ValType],
     ) -> Result<Vec<Value>, RuntimeError> {
         // -=-= Verification =-=-
-        let func_inst = self.store.funcs.get(func_idx).expect("valid FuncIdx");
-        let func_ty = self.types.get(func_inst.ty).unwrap_validated();
-
-        // Verify that the given parameters match the function parameters
-        let param_types = params.iter().map(|v| v.to_ty()).collect::<Vec<_>>();
-
-        if func_ty.params.valtypes != param_types {
-            panic!("Invalid parameters for function");
+        let func_inst = self
+            .store
+            .funcs
+            .get(func_idx)
+            .ok_or(RuntimeError::FunctionNotFound)?;
+        let func_ty = self.types.get(func_inst.ty()).unwrap_validated();
+
+        // Verify that the given parameters and return types match the function type
+        let param_types = params.iter().map(|v| v.to_ty());
+        if !param_types.eq(func_ty.params.valtypes.iter().copied())
+            || func_ty.returns.valtypes != ret_types
+        {
+            return Err(RuntimeError::FunctionTypeMismatch);
         }
 
-        // Verify that the given return types match the function return types
-        if func_ty.returns.valtypes != ret_types {
-            panic!("Invalid return types for function");
-        }
+        let func_inst = match func_inst {
+            FuncInst::Local(func) => func,
+            FuncInst::Imported(func) => {
+                let host_func_idx = func.host_func_idx;
+                let Store {
+                    mems, host_funcs, ..
+                } = &mut self.store;
+                let host_func = host_funcs.get_mut(host_func_idx).unwrap_validated();
+                let returns = host_func(&mut HostCallContext { mems }, &params)?;
+
+                if !returns
+                    .iter()
+                    .map(Value::to_ty)
+                    .eq(ret_types.iter().copied())
+                {
+                    return Err(RuntimeError::InvalidHostFunctionResults);
+                }
+                return Ok(returns);
+            }
+        };
 
         // Prepare a new stack with the locals for the entry function
         let mut stack = Stack::new();
         let locals = Locals::new(params.into_iter(), func_inst.locals.iter().cloned());
-        stack.push_stackframe(func_idx, func_ty, locals, 0);
+        stack.push_stackframe(func_idx, func_ty, locals, 0, 0);
+
+        let mut pc = func_inst.code_expr.from();
+        let mut stp = func_inst.stp;
 
         // Run the interpreter
-        run(
+        let outcome = interpret(
             self.wasm_bytecode,
             &self.types,
+            &self.sidetable,
             &mut self.store,
             &mut stack,
-            EmptyHookSet,
+            &mut pc,
+            &mut stp,
+            &mut None,
+            &self.fuel_cost_table,
+            &mut self.hook_set,
+            &mut false,
+            &mut false,
         )?;
-
-        let func_inst = self.store.funcs.get(func_idx).expect("valid FuncIdx");
-        let func_ty = self.types.get(func_inst.ty).unwrap_validated();
+        if outcome == ExecutionOutcome::Paused {
+            return Err(RuntimeError::UnresumablePause);
+        }
 
         // Pop return values from stack
-        let return_values = func_ty
-            .returns
-            .valtypes
+        let return_values = ret_types
             .iter()
+            .rev()
             .map(|ty| stack.pop_value(*ty))
             .collect::<Vec<Value>>();
 
@@ -200,16 +331,71 @@ where
         Ok(ret)
     }
 
-    fn init_store(validation_info: &ValidationInfo) -> Store {
-        let function_instances: Vec<FuncInst> = {
+    fn init_store(validation_info: &ValidationInfo, linker: Linker) -> Result<Store, RuntimeError> {
+        // Imports come first in the function and global index spaces
+        let mut function_instances: Vec<FuncInst> = Vec::new();
+        let mut global_instances: Vec<GlobalInst> = Vec::new();
+        for import in &validation_info.imports {
+            let unknown_import = || RuntimeError::UnknownImport {
+                module_name: import.module_name.clone(),
+                name: import.name.clone(),
+            };
+
+            match import.desc {
+                ImportDesc::Func(type_idx) => {
+                    let (host_func_idx, host_func_ty) = linker
+                        .find_func(&import.module_name, &import.name)
+                        .ok_or_else(unknown_import)?;
+
+                    let expected = validation_info.types.get(type_idx).unwrap_validated();
+                    if expected != host_func_ty {
+                        return Err(RuntimeError::ImportTypeMismatch {
+                            module_name: import.module_name.clone(),
+                            name: import.name.clone(),
+                            expected: Box::new(expected.clone()),
+                            actual: Box::new(host_func_ty.clone()),
+                        });
+                    }
+
+                    function_instances.push(FuncInst::Imported(ImportedFuncInst {
+                        ty: type_idx,
+                        host_func_idx,
+                    }));
+                }
+                ImportDesc::Global(expected) => {
+                    let (actual, value) = linker
+                        .find_global(&import.module_name, &import.name)
+                        .ok_or_else(unknown_import)?;
+
+                    if expected != actual {
+                        return Err(RuntimeError::GlobalImportTypeMismatch {
+                            module_name: import.module_name.clone(),
+                            name: import.name.clone(),
+                            expected,
+                            actual,
+                        });
+                    }
+
+                    global_instances.push(GlobalInst {
+                        ty: expected,
+                        value,
+                    });
+                }
+                ImportDesc::Table(()) | ImportDesc::Mem(()) => {
+                    unreachable!("table and memory imports can not be parsed yet")
+                }
+            }
+        }
+
+        let local_function_instances: Vec<FuncInst> = {
             let mut wasm_reader = WasmReader::new(validation_info.wasm);
 
             let functions = validation_info.functions.iter();
-            let func_blocks = validation_info.func_blocks.iter();
+            let func_blocks_stps = validation_info.func_blocks_stps.iter();
 
             functions
-                .zip(func_blocks)
-                .map(|(ty, func)| {
+                .zip(func_blocks_stps)
+                .map(|(ty, (func, stp))| {
                     wasm_reader
                         .move_start_to(*func)
                         .expect("function index to be in the bounds of the WASM binary");
@@ -220,41 +406,185 @@ where
 
                     let code_expr = wasm_reader
                         .make_span(func.len() - bytes_read)
-                        .expect("TODO remove this expect");
+                        .unwrap_validated();
 
-                    FuncInst {
+                    FuncInst::Local(LocalFuncInst {
                         ty: *ty,
                         locals,
                         code_expr,
-                    }
+                        stp: *stp,
+                    })
                 })
                 .collect()
         };
+        function_instances.extend(local_function_instances);
 
-        let memory_instances: Vec<MemInst> = validation_info
-            .memories
+        // Constant expressions may only read imported globals
+        let imported_global_values = global_instances
             .iter()
-            .map(|ty| MemInst::new(*ty))
-            .collect();
+            .map(|global| global.value)
+            .collect::<Vec<Value>>();
+        let eval =
+            |expr: Span| eval_const_expr(validation_info.wasm, expr, &imported_global_values);
+
+        for global in &validation_info.globals {
+            global_instances.push(GlobalInst {
+                ty: global.ty,
+                value: eval(global.init_expr),
+            });
+        }
 
-        let global_instances: Vec<GlobalInst> = validation_info
-            .globals
+        let mut table_instances: Vec<TableInst> = validation_info
+            .tables
             .iter()
-            .map(|global| {
-                // TODO execute `global.init_expr` to get initial value. For now just use a default value.
-                let value = Value::default_from_ty(global.ty.ty);
+            .map(|ty| TableInst::new(*ty))
+            .collect();
 
-                GlobalInst {
-                    global: *global,
-                    value,
+        let mut elem_instances: Vec<ElemInst> = Vec::new();
+        for segment in &validation_info.elements {
+            let references: Vec<Ref> = match &segment.items {
+                ElemItems::Funcs(funcs) => funcs.iter().map(|idx| Ref::Func(*idx)).collect(),
+                ElemItems::Exprs(exprs) => exprs
+                    .iter()
+                    .map(|expr| match eval(*expr) {
+                        Value::Ref(reference) => reference,
+                        _ => unreachable_validated!(),
+                    })
+                    .collect(),
+            };
+
+            // Copy active element segments into their tables, only passive segments are kept
+            let references = match segment.mode {
+                ElemMode::Active { table_idx, offset } => {
+                    let table = table_instances.get_mut(table_idx).unwrap_validated();
+                    let offset = u32::from(eval(offset));
+                    let len = references.len() as u32;
+                    table.init(offset, &references, 0, len)?;
+                    Vec::new()
                 }
-            })
+                ElemMode::Declarative => Vec::new(),
+                ElemMode::Passive => references,
+            };
+            elem_instances.push(ElemInst { references });
+        }
+
+        let mut memory_instances: Vec<MemInst> = validation_info
+            .memories
+            .iter()
+            .map(|ty| MemInst::new(*ty))
             .collect();
 
-        Store {
+        let wasm_reader = WasmReader::new(validation_info.wasm);
+        let mut data_instances: Vec<DataInst> = Vec::new();
+        for segment in &validation_info.data {
+            let init = &wasm_reader[segment.init];
+
+            // Copy active data segments into their memories, only passive segments are kept
+            let data = match segment.mode {
+                DataMode::Active { mem_idx, offset } => {
+                    let mem = memory_instances.get_mut(mem_idx).unwrap_validated();
+                    let offset = u32::from(eval(offset));
+                    mem.init(offset, init, 0, init.len() as u32)?;
+                    Vec::new()
+                }
+                DataMode::Passive => init.to_vec(),
+            };
+            data_instances.push(DataInst { data });
+        }
+
+        Ok(Store {
             funcs: function_instances,
+            tables: table_instances,
             mems: memory_instances,
             globals: global_instances,
-        }
+            elems: elem_instances,
+            datas: data_instances,
+            host_funcs: linker.into_host_funcs(),
+            memory_growth_policy: None,
+        })
     }
 }
+
+/// Runs the [interpreter loop](run), calling the trap hook if execution traps
+///
+/// Unless the function invoked by the host was already `entered`, its call hook is called first.
+/// If it pauses execution, the invocation counts as entered nonetheless, so that the hook is not
+/// called again upon resumption. See [`run`] for `paused_by_hook`.
+#[allow(clippy::too_many_arguments)]
+fn interpret<H: HookSet>(
+    wasm_bytecode: &[u8],
+    types: &[FuncType],
+    sidetable: &Sidetable,
+    store: &mut Store,
+    stack: &mut Stack,
+    pc: &mut usize,
+    stp: &mut usize,
+    fuel: &mut Option<u64>,
+    fuel_cost_table: &FuelCostTable,
+    hooks: &mut H,
+    entered: &mut bool,
+    paused_by_hook: &mut bool,
+) -> Result<ExecutionOutcome<()>, RuntimeError> {
+    // the PC of the instruction that is executed last, to locate it if it traps
+    let mut trap_pc = *pc;
+
+    #[cfg(feature = "hooks")]
+    let action = if *entered {
+        HookAction::Continue
+    } else {
+        // the arguments are the first locals of the invoked function
+        let frame = stack.current_stackframe();
+        let func_ty = types
+            .get(store.funcs[frame.func_idx].ty())
+            .unwrap_validated();
+        let args = &frame.locals.values()[..func_ty.params.valtypes.len()];
+        hooks.call_hook(None, frame.func_idx, args)
+    };
+    #[cfg(not(feature = "hooks"))]
+    let action = HookAction::Continue;
+
+    *entered = true;
+    let outcome = match action {
+        HookAction::Continue => run(
+            wasm_bytecode,
+            types,
+            sidetable,
+            store,
+            stack,
+            pc,
+            stp,
+            fuel,
+            fuel_cost_table,
+            hooks,
+            &mut trap_pc,
+            paused_by_hook,
+        ),
+        HookAction::Pause => Ok(ExecutionOutcome::Paused),
+        HookAction::Abort(code) => Err(RuntimeError::HookAbort(code)),
+    };
+
+    #[cfg(feature = "hooks")]
+    if let Err(error) = &outcome {
+        hooks.trap_hook(locate(&store.funcs, stack, trap_pc), error);
+    }
+    outcome
+}
+
+/// Take the branch described by the side-table entry that `stp` points to
+///
+/// `branch_pc` is the PC of the branch instruction, as the entry's PC adjustment is relative to it.
+fn do_sidetable_control_transfer(
+    wasm: &mut WasmReader,
+    stack: &mut Stack,
+    stp: &mut usize,
+    sidetable: &Sidetable,
+    branch_pc: usize,
+) {
+    let entry = sidetable.get(*stp).unwrap_validated();
+
+    stack.remove_inbetween(entry.popcnt, entry.valcnt);
+    wasm.pc = branch_pc
+        .checked_add_signed(entry.delta_pc)
+        .unwrap_validated();
+    *stp = stp.checked_add_signed(entry.delta_stp).unwrap_validated();
+}
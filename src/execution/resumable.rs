@@ -0,0 +1,203 @@
+//! Invocations that can be paused and resumed.
+//!
+//! A [`Resumable`] owns the complete state of an invocation, i.e. the value and call stacks as well
+//! as the PC and side-table pointer to continue at. Whenever it runs out of fuel or a hook pauses
+//! it, execution is paused between two instructions. Resuming it later on continues exactly where
+//! it stopped, as if it was never halted in the first place.
+//!
+//! See: REQ-1 and REQ-2
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::core::indices::FuncIdx;
+use crate::execution::assert_validated::UnwrapValidatedExt;
+use crate::execution::fuel::ExecutionOutcome;
+use crate::execution::hooks::{EmptyHookSet, HookSet};
+use crate::execution::locals::Locals;
+use crate::execution::value::Value;
+use crate::execution::value_stack::Stack;
+use crate::execution::{interpret, RuntimeInstance};
+use crate::value::InteropValueList;
+use crate::RuntimeError;
+
+/// A paused invocation of a function, returning `Returns` once it finishes
+pub struct Resumable<'i, 'b, Returns, H = EmptyHookSet>
+where
+    Returns: InteropValueList,
+    H: HookSet,
+{
+    pub(super) instance: &'i mut RuntimeInstance<'b, H>,
+    pub(super) stack: Stack,
+    /// PC of the next instruction to execute
+    pub(super) pc: usize,
+    /// Side-table pointer at the next instruction to execute
+    pub(super) stp: usize,
+    /// Whether the call hook of the invoked function was already called, so that it is not called
+    /// again upon resumption
+    pub(super) entered: bool,
+    /// Whether a hook paused execution before the instruction at `pc`, in which case the hooks
+    /// for that instruction are not called again upon resumption
+    pub(super) paused_by_hook: bool,
+    /// Whether the invocation is over, either because the function returned or because it trapped
+    pub(super) finished: bool,
+    pub(super) _returns: PhantomData<Returns>,
+}
+
+impl<'i, 'b, Returns, H> Resumable<'i, 'b, Returns, H>
+where
+    Returns: InteropValueList,
+    H: HookSet,
+{
+    /// Prepare the invocation of a function, without executing any of its instructions yet
+    ///
+    /// Fails if `func_idx` is out of bounds, refers to an imported function or if `Param` and
+    /// `Returns` do not match the function's type.
+    pub(super) fn new<Param: InteropValueList>(
+        instance: &'i mut RuntimeInstance<'b, H>,
+        func_idx: FuncIdx,
+        params: Param,
+    ) -> Result<Self, RuntimeError> {
+        // -=-= Verification =-=-
+        let func_inst = instance
+            .store
+            .funcs
+            .get(func_idx)
+            .ok_or(RuntimeError::FunctionNotFound)?
+            .try_into_local()
+            .ok_or(RuntimeError::HostFunctionNotResumable)?;
+        let func_ty = instance.types.get(func_inst.ty).unwrap_validated();
+
+        // Check correct function parameters and return types
+        if func_ty.params.valtypes != Param::TYS || func_ty.returns.valtypes != Returns::TYS {
+            return Err(RuntimeError::FunctionTypeMismatch);
+        }
+
+        // Prepare a new stack with the locals for the entry function
+        let mut stack = Stack::new();
+        let locals = Locals::new(
+            params.into_values().into_iter(),
+            func_inst.locals.iter().cloned(),
+        );
+
+        // setting `usize::MAX` as return address for the outermost function ensures that we
+        // observably fail upon errornoeusly continuing execution after that function returns.
+        stack.push_stackframe(func_idx, func_ty, locals, usize::MAX, usize::MAX);
+
+        let pc = func_inst.code_expr.from();
+        let stp = func_inst.stp;
+
+        Ok(Self {
+            instance,
+            stack,
+            pc,
+            stp,
+            entered: false,
+            paused_by_hook: false,
+            finished: false,
+            _returns: PhantomData,
+        })
+    }
+
+    /// Continue execution with the given amount of fuel
+    ///
+    /// Returns [`ExecutionOutcome::OutOfFuel`] or [`ExecutionOutcome::Paused`] if the invocation
+    /// was paused again, in which case it may be resumed once more. Fuel is consumed according to
+    /// the instance's [`fuel_cost_table`](RuntimeInstance::fuel_cost_table).
+    ///
+    /// # Panics
+    /// If the invocation already finished or trapped.
+    pub fn resume(&mut self, fuel: u64) -> Result<ExecutionOutcome<Returns>, RuntimeError> {
+        self.resume_metered(Some(fuel))
+    }
+
+    /// Continue execution, optionally bounded by fuel
+    pub(super) fn resume_metered(
+        &mut self,
+        mut fuel: Option<u64>,
+    ) -> Result<ExecutionOutcome<Returns>, RuntimeError> {
+        assert!(
+            !self.finished,
+            "can not resume an invocation that already finished"
+        );
+
+        // Run the interpreter
+        let outcome = interpret(
+            self.instance.wasm_bytecode,
+            &self.instance.types,
+            &self.instance.sidetable,
+            &mut self.instance.store,
+            &mut self.stack,
+            &mut self.pc,
+            &mut self.stp,
+            &mut fuel,
+            &self.instance.fuel_cost_table,
+            &mut self.instance.hook_set,
+            &mut self.entered,
+            &mut self.paused_by_hook,
+        )
+        .inspect_err(|_| self.finished = true)?;
+
+        match outcome {
+            ExecutionOutcome::Finished(()) => {}
+            ExecutionOutcome::OutOfFuel => {
+                debug!("Ran out of fuel, pausing at pc {}", self.pc);
+                return Ok(ExecutionOutcome::OutOfFuel);
+            }
+            ExecutionOutcome::Paused => {
+                debug!("Paused by a hook at pc {}", self.pc);
+                return Ok(ExecutionOutcome::Paused);
+            }
+        }
+        self.finished = true;
+
+        // Pop return values from stack
+        let return_values = Returns::TYS
+            .iter()
+            .rev()
+            .map(|ty| self.stack.pop_value(*ty))
+            .collect::<Vec<Value>>();
+
+        // Values are reversed because they were popped from stack one-by-one. Now reverse them back
+        let reversed_values = return_values.into_iter().rev();
+        let ret: Returns = Returns::from_values(reversed_values);
+        debug!("Successfully invoked function");
+        Ok(ExecutionOutcome::Finished(ret))
+    }
+
+    /// Abort the invocation, discarding its state
+    ///
+    /// Changes that were already made to the instance, e.g. to its memory or globals, persist.
+    pub fn cancel(self) {
+        debug!("Cancelled invocation paused at pc {}", self.pc);
+    }
+
+    /// Returns whether the invocation is over, either because the function returned or trapped
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the PC of the next instruction to execute
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the index of the function that is currently executed
+    ///
+    /// # Panics
+    /// If the invocation already finished.
+    pub fn current_func_idx(&self) -> FuncIdx {
+        self.stack.current_stackframe().func_idx
+    }
+
+    /// Returns the number of function calls that are currently active, including the invoked
+    /// function itself
+    pub fn call_depth(&self) -> usize {
+        self.stack.callframe_count()
+    }
+
+    /// Returns the values currently on the operand stack, with the topmost value being the last
+    pub fn values(&self) -> &[Value] {
+        self.stack.values()
+    }
+}
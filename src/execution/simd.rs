@@ -0,0 +1,439 @@
+//! Portable implementation of the 128-bit vector instructions.
+//!
+//! Vectors are represented by their 16 bytes in little-endian byte order and interpreted as lanes
+//! of the instruction's shape. All operations are implemented lane by lane in plain Rust, so no
+//! SIMD support of the host is required.
+//!
+//! See: <https://webassembly.github.io/spec/core/exec/instructions.html#vector-instructions>
+
+use core::iter;
+use core::mem::size_of;
+use core::ops::{Add, Mul};
+
+use crate::core::reader::types::opcode::fd_extensions::*;
+use crate::execution::assert_validated::UnwrapValidatedExt;
+use crate::execution::value::{F32, F64};
+use crate::unreachable_validated;
+
+/// The bytes of a `v128` value
+pub type V128 = [u8; 16];
+
+/// A scalar which a [`V128`] can be split into
+pub trait Lane: Copy + 'static {
+    fn from_bytes(bytes: &[u8]) -> Self;
+    fn to_bytes(self, bytes: &mut [u8]);
+}
+
+macro_rules! impl_lane {
+    ($($ty:ty),*) => {
+        $(impl Lane for $ty {
+            fn from_bytes(bytes: &[u8]) -> Self {
+                <$ty>::from_le_bytes(bytes.try_into().unwrap_validated())
+            }
+
+            fn to_bytes(self, bytes: &mut [u8]) {
+                bytes.copy_from_slice(&self.to_le_bytes());
+            }
+        })*
+    };
+}
+
+impl_lane!(i8, u8, i16, u16, i32, u32, i64, u64, f32, f64);
+
+/// The lanes of `v` in the shape `T`
+fn lanes<T: Lane>(v: &V128) -> impl Iterator<Item = T> + '_ {
+    v.chunks_exact(size_of::<T>()).map(T::from_bytes)
+}
+
+/// Builds a vector from exactly as many lanes of shape `T` as fit into it
+fn from_lanes<T: Lane>(lanes: impl IntoIterator<Item = T>) -> V128 {
+    let mut v = [0; 16];
+    for (bytes, lane) in v.chunks_exact_mut(size_of::<T>()).zip(lanes) {
+        lane.to_bytes(bytes);
+    }
+    v
+}
+
+fn map<T: Lane, U: Lane>(v: &V128, f: impl Fn(T) -> U) -> V128 {
+    from_lanes(lanes(v).map(f))
+}
+
+fn zip<T: Lane>(v1: &V128, v2: &V128, f: impl Fn(T, T) -> T) -> V128 {
+    from_lanes(lanes(v1).zip(lanes(v2)).map(|(x1, x2)| f(x1, x2)))
+}
+
+/// Sets all bits of the lanes for which `f` holds, and clears all others
+fn compare<T: Lane>(v1: &V128, v2: &V128, f: impl Fn(T, T) -> bool) -> V128 {
+    let mut res = [0; 16];
+    let results = lanes(v1).zip(lanes(v2)).map(|(x1, x2)| f(x1, x2));
+    for (bytes, result) in res.chunks_exact_mut(size_of::<T>()).zip(results) {
+        if result {
+            bytes.fill(0xFF);
+        }
+    }
+    res
+}
+
+/// The low or high half of the lanes of `v`, each extended to the twice as wide shape `U`
+fn extend<T: Lane, U: Lane + From<T>>(v: &V128, high: bool) -> impl Iterator<Item = U> + '_ {
+    let half = 16 / size_of::<T>() / 2;
+    lanes(v)
+        .skip(if high { half } else { 0 })
+        .take(half)
+        .map(U::from)
+}
+
+/// Multiplies the extended low or high halves of the lanes of `v1` and `v2`
+///
+/// The products always fit into the wider shape `U`.
+fn extmul<T: Lane, U: Lane + From<T> + Mul<Output = U>>(v1: &V128, v2: &V128, high: bool) -> V128 {
+    from_lanes(
+        extend::<T, U>(v1, high)
+            .zip(extend(v2, high))
+            .map(|(x1, x2)| x1 * x2),
+    )
+}
+
+/// Adds adjacent pairs of lanes of `v`, which always fit into the twice as wide shape `U`
+fn extadd_pairwise<T: Lane, U: Lane + From<T> + Add<Output = U>>(v: &V128) -> V128 {
+    let mut extended = lanes(v).map(U::from);
+    from_lanes(iter::from_fn(|| Some(extended.next()? + extended.next()?)))
+}
+
+/// Converts the lanes of `v1` followed by those of `v2` into the half as wide shape `U`
+fn narrow<T: Lane, U: Lane>(v1: &V128, v2: &V128, saturate: impl Fn(T) -> U) -> V128 {
+    from_lanes(lanes(v1).chain(lanes(v2)).map(saturate))
+}
+
+/// The minimum of the lanes in which `v2` is less than `v1`, keeping `v1` otherwise
+fn pmin<T: Lane + PartialOrd>(v1: &V128, v2: &V128) -> V128 {
+    zip::<T>(v1, v2, |x1, x2| if x2 < x1 { x2 } else { x1 })
+}
+
+/// The maximum of the lanes in which `v1` is less than `v2`, keeping `v1` otherwise
+fn pmax<T: Lane + PartialOrd>(v1: &V128, v2: &V128) -> V128 {
+    zip::<T>(v1, v2, |x1, x2| if x1 < x2 { x2 } else { x1 })
+}
+
+/// Every lane of a vector set to `x`
+pub fn splat<T: Lane>(x: T) -> V128 {
+    from_lanes(iter::repeat(x))
+}
+
+/// The lane with index `lane` of the shape `T`, which validation checked to exist
+pub fn extract_lane<T: Lane>(v: &V128, lane: u8) -> T {
+    lanes(v).nth(lane.into()).unwrap_validated()
+}
+
+/// `v` with the lane with index `lane` of the shape `T` replaced by `x`
+pub fn replace_lane<T: Lane>(mut v: V128, lane: u8, x: T) -> V128 {
+    let bytes = v.chunks_exact_mut(size_of::<T>()).nth(lane.into());
+    x.to_bytes(bytes.unwrap_validated());
+    v
+}
+
+/// Loads lanes of shape `T` from `bytes` and extends each of them to the twice as wide shape `U`
+pub fn load_extend<T: Lane, U: Lane + From<T>>(bytes: [u8; 8]) -> V128 {
+    from_lanes(
+        bytes
+            .chunks_exact(size_of::<T>())
+            .map(T::from_bytes)
+            .map(U::from),
+    )
+}
+
+/// A vector whose low bytes are `bytes` and all other bytes are zero
+pub fn load_zero<const N: usize>(bytes: [u8; N]) -> V128 {
+    let mut v = [0; 16];
+    v[..N].copy_from_slice(&bytes);
+    v
+}
+
+/// `i8x16.shuffle`: Selects the bytes of the concatenation of `v1` and `v2` at the indices `lanes`
+pub fn shuffle(v1: &V128, v2: &V128, lanes: &[u8; 16]) -> V128 {
+    lanes.map(|lane| {
+        let lane = usize::from(lane);
+        if lane < 16 {
+            v1[lane]
+        } else {
+            v2[lane - 16]
+        }
+    })
+}
+
+/// `v128.bitselect`: Selects the bits of `v1` where `mask` is set and those of `v2` elsewhere
+pub fn bitselect(v1: &V128, v2: &V128, mask: &V128) -> V128 {
+    let mut res = [0; 16];
+    for (i, byte) in res.iter_mut().enumerate() {
+        *byte = (v1[i] & mask[i]) | (v2[i] & !mask[i]);
+    }
+    res
+}
+
+/// Vector instructions of type `[v128 i32] -> [v128]`, which shift each lane by `amount` modulo
+/// the lane width
+pub fn shift(instr: u32, v: &V128, amount: u32) -> V128 {
+    match instr {
+        I8X16_SHL => map::<i8, _>(v, |x| x.wrapping_shl(amount)),
+        I8X16_SHR_S => map::<i8, _>(v, |x| x.wrapping_shr(amount)),
+        I8X16_SHR_U => map::<u8, _>(v, |x| x.wrapping_shr(amount)),
+        I16X8_SHL => map::<i16, _>(v, |x| x.wrapping_shl(amount)),
+        I16X8_SHR_S => map::<i16, _>(v, |x| x.wrapping_shr(amount)),
+        I16X8_SHR_U => map::<u16, _>(v, |x| x.wrapping_shr(amount)),
+        I32X4_SHL => map::<i32, _>(v, |x| x.wrapping_shl(amount)),
+        I32X4_SHR_S => map::<i32, _>(v, |x| x.wrapping_shr(amount)),
+        I32X4_SHR_U => map::<u32, _>(v, |x| x.wrapping_shr(amount)),
+        I64X2_SHL => map::<i64, _>(v, |x| x.wrapping_shl(amount)),
+        I64X2_SHR_S => map::<i64, _>(v, |x| x.wrapping_shr(amount)),
+        I64X2_SHR_U => map::<u64, _>(v, |x| x.wrapping_shr(amount)),
+        _ => unreachable_validated!(),
+    }
+}
+
+/// Vector instructions of type `[v128] -> [i32]`
+pub fn test(instr: u32, v: &V128) -> u32 {
+    let lane_size = match instr {
+        V128_ANY_TRUE => return u32::from(v.iter().any(|byte| *byte != 0)),
+        I8X16_ALL_TRUE | I8X16_BITMASK => 1,
+        I16X8_ALL_TRUE | I16X8_BITMASK => 2,
+        I32X4_ALL_TRUE | I32X4_BITMASK => 4,
+        I64X2_ALL_TRUE | I64X2_BITMASK => 8,
+        _ => unreachable_validated!(),
+    };
+    let mut lanes = v.chunks_exact(lane_size);
+
+    match instr {
+        I8X16_ALL_TRUE | I16X8_ALL_TRUE | I32X4_ALL_TRUE | I64X2_ALL_TRUE => {
+            u32::from(lanes.all(|lane| lane.iter().any(|byte| *byte != 0)))
+        }
+        // the bitmask consists of the sign bits of all lanes, starting with the first lane
+        _ => lanes
+            .enumerate()
+            .map(|(i, lane)| u32::from(lane[lane_size - 1] >> 7) << i)
+            .sum(),
+    }
+}
+
+/// Vector instructions of type `[v128] -> [v128]`
+pub fn unary(instr: u32, v: &V128) -> V128 {
+    match instr {
+        V128_NOT => v.map(|byte| !byte),
+
+        I8X16_ABS => map(v, i8::wrapping_abs),
+        I8X16_NEG => map(v, i8::wrapping_neg),
+        I8X16_POPCNT => map::<u8, _>(v, |x| x.count_ones() as u8),
+        I16X8_ABS => map(v, i16::wrapping_abs),
+        I16X8_NEG => map(v, i16::wrapping_neg),
+        I32X4_ABS => map(v, i32::wrapping_abs),
+        I32X4_NEG => map(v, i32::wrapping_neg),
+        I64X2_ABS => map(v, i64::wrapping_abs),
+        I64X2_NEG => map(v, i64::wrapping_neg),
+
+        F32X4_ABS => map::<f32, _>(v, |x| F32(x).abs().0),
+        F32X4_NEG => map::<f32, _>(v, |x| F32(x).neg().0),
+        F32X4_SQRT => map::<f32, _>(v, |x| F32(x).sqrt().0),
+        F32X4_CEIL => map::<f32, _>(v, |x| F32(x).ceil().0),
+        F32X4_FLOOR => map::<f32, _>(v, |x| F32(x).floor().0),
+        F32X4_TRUNC => map::<f32, _>(v, |x| F32(x).trunc().0),
+        // rounds to the nearest integer, with ties to even
+        F32X4_NEAREST => map(v, libm::rintf),
+        F64X2_ABS => map::<f64, _>(v, |x| F64(x).abs().0),
+        F64X2_NEG => map::<f64, _>(v, |x| F64(x).neg().0),
+        F64X2_SQRT => map::<f64, _>(v, |x| F64(x).sqrt().0),
+        F64X2_CEIL => map::<f64, _>(v, |x| F64(x).ceil().0),
+        F64X2_FLOOR => map::<f64, _>(v, |x| F64(x).floor().0),
+        F64X2_TRUNC => map::<f64, _>(v, |x| F64(x).trunc().0),
+        F64X2_NEAREST => map(v, libm::rint),
+
+        I16X8_EXTADD_PAIRWISE_I8X16_S => extadd_pairwise::<i8, i16>(v),
+        I16X8_EXTADD_PAIRWISE_I8X16_U => extadd_pairwise::<u8, u16>(v),
+        I32X4_EXTADD_PAIRWISE_I16X8_S => extadd_pairwise::<i16, i32>(v),
+        I32X4_EXTADD_PAIRWISE_I16X8_U => extadd_pairwise::<u16, u32>(v),
+
+        I16X8_EXTEND_LOW_I8X16_S => from_lanes(extend::<i8, i16>(v, false)),
+        I16X8_EXTEND_HIGH_I8X16_S => from_lanes(extend::<i8, i16>(v, true)),
+        I16X8_EXTEND_LOW_I8X16_U => from_lanes(extend::<u8, u16>(v, false)),
+        I16X8_EXTEND_HIGH_I8X16_U => from_lanes(extend::<u8, u16>(v, true)),
+        I32X4_EXTEND_LOW_I16X8_S => from_lanes(extend::<i16, i32>(v, false)),
+        I32X4_EXTEND_HIGH_I16X8_S => from_lanes(extend::<i16, i32>(v, true)),
+        I32X4_EXTEND_LOW_I16X8_U => from_lanes(extend::<u16, u32>(v, false)),
+        I32X4_EXTEND_HIGH_I16X8_U => from_lanes(extend::<u16, u32>(v, true)),
+        I64X2_EXTEND_LOW_I32X4_S => from_lanes(extend::<i32, i64>(v, false)),
+        I64X2_EXTEND_HIGH_I32X4_S => from_lanes(extend::<i32, i64>(v, true)),
+        I64X2_EXTEND_LOW_I32X4_U => from_lanes(extend::<u32, u64>(v, false)),
+        I64X2_EXTEND_HIGH_I32X4_U => from_lanes(extend::<u32, u64>(v, true)),
+
+        // Like their scalar counterparts, the saturating truncations match the semantics of
+        // Rust's `as` casts. The lanes that have no counterpart in the input are set to zero.
+        I32X4_TRUNC_SAT_F32X4_S => map::<f32, _>(v, |x| x as i32),
+        I32X4_TRUNC_SAT_F32X4_U => map::<f32, _>(v, |x| x as u32),
+        I32X4_TRUNC_SAT_F64X2_S_ZERO => from_lanes(lanes::<f64>(v).map(|x| x as i32).chain([0, 0])),
+        I32X4_TRUNC_SAT_F64X2_U_ZERO => from_lanes(lanes::<f64>(v).map(|x| x as u32).chain([0, 0])),
+        F32X4_CONVERT_I32X4_S => map::<i32, _>(v, |x| x as f32),
+        F32X4_CONVERT_I32X4_U => map::<u32, _>(v, |x| x as f32),
+        F64X2_CONVERT_LOW_I32X4_S => from_lanes(lanes::<i32>(v).take(2).map(f64::from)),
+        F64X2_CONVERT_LOW_I32X4_U => from_lanes(lanes::<u32>(v).take(2).map(f64::from)),
+        F32X4_DEMOTE_F64X2_ZERO => from_lanes(lanes::<f64>(v).map(|x| x as f32).chain([0.0, 0.0])),
+        F64X2_PROMOTE_LOW_F32X4 => from_lanes(lanes::<f32>(v).take(2).map(f64::from)),
+
+        _ => unreachable_validated!(),
+    }
+}
+
+/// Vector instructions of type `[v128 v128] -> [v128]`
+pub fn binary(instr: u32, v1: &V128, v2: &V128) -> V128 {
+    match instr {
+        V128_AND => zip::<u8>(v1, v2, |x1, x2| x1 & x2),
+        V128_ANDNOT => zip::<u8>(v1, v2, |x1, x2| x1 & !x2),
+        V128_OR => zip::<u8>(v1, v2, |x1, x2| x1 | x2),
+        V128_XOR => zip::<u8>(v1, v2, |x1, x2| x1 ^ x2),
+
+        // selects the bytes of `v1` at the indices in `v2`, with out of range indices selecting 0
+        I8X16_SWIZZLE => v2.map(|idx| v1.get(usize::from(idx)).copied().unwrap_or(0)),
+
+        I8X16_EQ => compare::<u8>(v1, v2, |x1, x2| x1 == x2),
+        I8X16_NE => compare::<u8>(v1, v2, |x1, x2| x1 != x2),
+        I8X16_LT_S => compare::<i8>(v1, v2, |x1, x2| x1 < x2),
+        I8X16_LT_U => compare::<u8>(v1, v2, |x1, x2| x1 < x2),
+        I8X16_GT_S => compare::<i8>(v1, v2, |x1, x2| x1 > x2),
+        I8X16_GT_U => compare::<u8>(v1, v2, |x1, x2| x1 > x2),
+        I8X16_LE_S => compare::<i8>(v1, v2, |x1, x2| x1 <= x2),
+        I8X16_LE_U => compare::<u8>(v1, v2, |x1, x2| x1 <= x2),
+        I8X16_GE_S => compare::<i8>(v1, v2, |x1, x2| x1 >= x2),
+        I8X16_GE_U => compare::<u8>(v1, v2, |x1, x2| x1 >= x2),
+        I16X8_EQ => compare::<u16>(v1, v2, |x1, x2| x1 == x2),
+        I16X8_NE => compare::<u16>(v1, v2, |x1, x2| x1 != x2),
+        I16X8_LT_S => compare::<i16>(v1, v2, |x1, x2| x1 < x2),
+        I16X8_LT_U => compare::<u16>(v1, v2, |x1, x2| x1 < x2),
+        I16X8_GT_S => compare::<i16>(v1, v2, |x1, x2| x1 > x2),
+        I16X8_GT_U => compare::<u16>(v1, v2, |x1, x2| x1 > x2),
+        I16X8_LE_S => compare::<i16>(v1, v2, |x1, x2| x1 <= x2),
+        I16X8_LE_U => compare::<u16>(v1, v2, |x1, x2| x1 <= x2),
+        I16X8_GE_S => compare::<i16>(v1, v2, |x1, x2| x1 >= x2),
+        I16X8_GE_U => compare::<u16>(v1, v2, |x1, x2| x1 >= x2),
+        I32X4_EQ => compare::<u32>(v1, v2, |x1, x2| x1 == x2),
+        I32X4_NE => compare::<u32>(v1, v2, |x1, x2| x1 != x2),
+        I32X4_LT_S => compare::<i32>(v1, v2, |x1, x2| x1 < x2),
+        I32X4_LT_U => compare::<u32>(v1, v2, |x1, x2| x1 < x2),
+        I32X4_GT_S => compare::<i32>(v1, v2, |x1, x2| x1 > x2),
+        I32X4_GT_U => compare::<u32>(v1, v2, |x1, x2| x1 > x2),
+        I32X4_LE_S => compare::<i32>(v1, v2, |x1, x2| x1 <= x2),
+        I32X4_LE_U => compare::<u32>(v1, v2, |x1, x2| x1 <= x2),
+        I32X4_GE_S => compare::<i32>(v1, v2, |x1, x2| x1 >= x2),
+        I32X4_GE_U => compare::<u32>(v1, v2, |x1, x2| x1 >= x2),
+        I64X2_EQ => compare::<u64>(v1, v2, |x1, x2| x1 == x2),
+        I64X2_NE => compare::<u64>(v1, v2, |x1, x2| x1 != x2),
+        I64X2_LT_S => compare::<i64>(v1, v2, |x1, x2| x1 < x2),
+        I64X2_GT_S => compare::<i64>(v1, v2, |x1, x2| x1 > x2),
+        I64X2_LE_S => compare::<i64>(v1, v2, |x1, x2| x1 <= x2),
+        I64X2_GE_S => compare::<i64>(v1, v2, |x1, x2| x1 >= x2),
+        F32X4_EQ => compare::<f32>(v1, v2, |x1, x2| x1 == x2),
+        F32X4_NE => compare::<f32>(v1, v2, |x1, x2| x1 != x2),
+        F32X4_LT => compare::<f32>(v1, v2, |x1, x2| x1 < x2),
+        F32X4_GT => compare::<f32>(v1, v2, |x1, x2| x1 > x2),
+        F32X4_LE => compare::<f32>(v1, v2, |x1, x2| x1 <= x2),
+        F32X4_GE => compare::<f32>(v1, v2, |x1, x2| x1 >= x2),
+        F64X2_EQ => compare::<f64>(v1, v2, |x1, x2| x1 == x2),
+        F64X2_NE => compare::<f64>(v1, v2, |x1, x2| x1 != x2),
+        F64X2_LT => compare::<f64>(v1, v2, |x1, x2| x1 < x2),
+        F64X2_GT => compare::<f64>(v1, v2, |x1, x2| x1 > x2),
+        F64X2_LE => compare::<f64>(v1, v2, |x1, x2| x1 <= x2),
+        F64X2_GE => compare::<f64>(v1, v2, |x1, x2| x1 >= x2),
+
+        I8X16_ADD => zip(v1, v2, u8::wrapping_add),
+        I8X16_ADD_SAT_S => zip(v1, v2, i8::saturating_add),
+        I8X16_ADD_SAT_U => zip(v1, v2, u8::saturating_add),
+        I8X16_SUB => zip(v1, v2, u8::wrapping_sub),
+        I8X16_SUB_SAT_S => zip(v1, v2, i8::saturating_sub),
+        I8X16_SUB_SAT_U => zip(v1, v2, u8::saturating_sub),
+        I8X16_MIN_S => zip(v1, v2, i8::min),
+        I8X16_MIN_U => zip(v1, v2, u8::min),
+        I8X16_MAX_S => zip(v1, v2, i8::max),
+        I8X16_MAX_U => zip(v1, v2, u8::max),
+        I8X16_AVGR_U => zip::<u8>(v1, v2, |x1, x2| {
+            (u16::from(x1) + u16::from(x2)).div_ceil(2) as u8
+        }),
+        I16X8_ADD => zip(v1, v2, u16::wrapping_add),
+        I16X8_ADD_SAT_S => zip(v1, v2, i16::saturating_add),
+        I16X8_ADD_SAT_U => zip(v1, v2, u16::saturating_add),
+        I16X8_SUB => zip(v1, v2, u16::wrapping_sub),
+        I16X8_SUB_SAT_S => zip(v1, v2, i16::saturating_sub),
+        I16X8_SUB_SAT_U => zip(v1, v2, u16::saturating_sub),
+        I16X8_MUL => zip(v1, v2, u16::wrapping_mul),
+        I16X8_MIN_S => zip(v1, v2, i16::min),
+        I16X8_MIN_U => zip(v1, v2, u16::min),
+        I16X8_MAX_S => zip(v1, v2, i16::max),
+        I16X8_MAX_U => zip(v1, v2, u16::max),
+        I16X8_AVGR_U => zip::<u16>(v1, v2, |x1, x2| {
+            (u32::from(x1) + u32::from(x2)).div_ceil(2) as u16
+        }),
+        // multiplication of fixed-point numbers with 15 fractional bits, rounding to nearest
+        I16X8_Q15MULR_SAT_S => zip::<i16>(v1, v2, |x1, x2| {
+            let product = (i32::from(x1) * i32::from(x2) + 0x4000) >> 15;
+            product.clamp(i16::MIN.into(), i16::MAX.into()) as i16
+        }),
+        I32X4_ADD => zip(v1, v2, u32::wrapping_add),
+        I32X4_SUB => zip(v1, v2, u32::wrapping_sub),
+        I32X4_MUL => zip(v1, v2, u32::wrapping_mul),
+        I32X4_MIN_S => zip(v1, v2, i32::min),
+        I32X4_MIN_U => zip(v1, v2, u32::min),
+        I32X4_MAX_S => zip(v1, v2, i32::max),
+        I32X4_MAX_U => zip(v1, v2, u32::max),
+        I32X4_DOT_I16X8_S => {
+            let mut products = lanes::<i16>(v1)
+                .zip(lanes::<i16>(v2))
+                .map(|(x1, x2)| i32::from(x1) * i32::from(x2));
+            from_lanes(iter::from_fn(|| {
+                Some(products.next()?.wrapping_add(products.next()?))
+            }))
+        }
+        I64X2_ADD => zip(v1, v2, u64::wrapping_add),
+        I64X2_SUB => zip(v1, v2, u64::wrapping_sub),
+        I64X2_MUL => zip(v1, v2, u64::wrapping_mul),
+
+        I8X16_NARROW_I16X8_S => {
+            narrow::<i16, i8>(v1, v2, |x| x.clamp(i8::MIN.into(), i8::MAX.into()) as i8)
+        }
+        I8X16_NARROW_I16X8_U => {
+            narrow::<i16, u8>(v1, v2, |x| x.clamp(u8::MIN.into(), u8::MAX.into()) as u8)
+        }
+        I16X8_NARROW_I32X4_S => {
+            narrow::<i32, i16>(v1, v2, |x| x.clamp(i16::MIN.into(), i16::MAX.into()) as i16)
+        }
+        I16X8_NARROW_I32X4_U => {
+            narrow::<i32, u16>(v1, v2, |x| x.clamp(u16::MIN.into(), u16::MAX.into()) as u16)
+        }
+
+        I16X8_EXTMUL_LOW_I8X16_S => extmul::<i8, i16>(v1, v2, false),
+        I16X8_EXTMUL_HIGH_I8X16_S => extmul::<i8, i16>(v1, v2, true),
+        I16X8_EXTMUL_LOW_I8X16_U => extmul::<u8, u16>(v1, v2, false),
+        I16X8_EXTMUL_HIGH_I8X16_U => extmul::<u8, u16>(v1, v2, true),
+        I32X4_EXTMUL_LOW_I16X8_S => extmul::<i16, i32>(v1, v2, false),
+        I32X4_EXTMUL_HIGH_I16X8_S => extmul::<i16, i32>(v1, v2, true),
+        I32X4_EXTMUL_LOW_I16X8_U => extmul::<u16, u32>(v1, v2, false),
+        I32X4_EXTMUL_HIGH_I16X8_U => extmul::<u16, u32>(v1, v2, true),
+        I64X2_EXTMUL_LOW_I32X4_S => extmul::<i32, i64>(v1, v2, false),
+        I64X2_EXTMUL_HIGH_I32X4_S => extmul::<i32, i64>(v1, v2, true),
+        I64X2_EXTMUL_LOW_I32X4_U => extmul::<u32, u64>(v1, v2, false),
+        I64X2_EXTMUL_HIGH_I32X4_U => extmul::<u32, u64>(v1, v2, true),
+
+        F32X4_ADD => zip::<f32>(v1, v2, |x1, x2| x1 + x2),
+        F32X4_SUB => zip::<f32>(v1, v2, |x1, x2| x1 - x2),
+        F32X4_MUL => zip::<f32>(v1, v2, |x1, x2| x1 * x2),
+        F32X4_DIV => zip::<f32>(v1, v2, |x1, x2| x1 / x2),
+        F32X4_MIN => zip::<f32>(v1, v2, |x1, x2| F32(x1).min(F32(x2)).0),
+        F32X4_MAX => zip::<f32>(v1, v2, |x1, x2| F32(x1).max(F32(x2)).0),
+        F32X4_PMIN => pmin::<f32>(v1, v2),
+        F32X4_PMAX => pmax::<f32>(v1, v2),
+        F64X2_ADD => zip::<f64>(v1, v2, |x1, x2| x1 + x2),
+        F64X2_SUB => zip::<f64>(v1, v2, |x1, x2| x1 - x2),
+        F64X2_MUL => zip::<f64>(v1, v2, |x1, x2| x1 * x2),
+        F64X2_DIV => zip::<f64>(v1, v2, |x1, x2| x1 / x2),
+        F64X2_MIN => zip::<f64>(v1, v2, |x1, x2| F64(x1).min(F64(x2)).0),
+        F64X2_MAX => zip::<f64>(v1, v2, |x1, x2| F64(x1).max(F64(x2)).0),
+        F64X2_PMIN => pmin::<f64>(v1, v2),
+        F64X2_PMAX => pmax::<f64>(v1, v2),
+
+        _ => unreachable_validated!(),
+    }
+}
@@ -0,0 +1,534 @@
+//! Serialization of paused invocations into snapshots, which can be restored later on, possibly on
+//! another machine.
+//!
+//! A snapshot uses a canonical binary encoding that does not depend on the host's endianness or
+//! pointer width. All integers are encoded in little-endian byte order, indices and addresses are
+//! always encoded as `u64`. Sequences are prefixed by their length.
+//!
+//! | Field        | Encoding                                                                  |
+//! |--------------|---------------------------------------------------------------------------|
+//! | magic        | `b"WASMSNAP"`                                                             |
+//! | version      | `u32`, see [`SNAPSHOT_VERSION`]                                           |
+//! | module hash  | SHA-256 of the module's bytecode, 32 bytes                                |
+//! | pc, stp      | `u64` each                                                                |
+//! | entered      | `u8`, `1` if the call hook of the invoked function was already called     |
+//! | paused       | `u8`, `1` if a hook paused execution before the instruction at `pc`       |
+//! | memories     | sequence of memories, each a sequence of bytes                            |
+//! | globals      | sequence of values                                                        |
+//! | tables       | sequence of tables, each a sequence of reference values                   |
+//! | data         | sequence of data segments, each a `u8`, `1` if it was dropped             |
+//! | elements     | sequence of element segments, each a `u8`, `1` if it was dropped          |
+//! | values       | sequence of values on the operand stack                                   |
+//! | call frames  | sequence of `func_idx`, `return_addr`, `return_stp`,                      |
+//! |              | `value_stack_base_idx`, `return_value_count` and the locals as values     |
+//!
+//! A value is encoded as the byte of its value type in the WASM binary format, followed by its
+//! bits as `u32` or `u64`, or by the 16 bytes of a vector. References are followed by the `u64`
+//! index of the referenced function or the host's handle of an external reference, with
+//! `u64::MAX` denoting a null reference.
+//!
+//! The contents of data and element segments are not part of a snapshot, as they are defined by
+//! the module. Only whether they were dropped, e.g. by `data.drop`, is recorded. Therefore a
+//! snapshot can not be restored on an instance that already dropped a segment which was not dropped
+//! when the snapshot was taken.
+//!
+//! See: REQ-12 and REQ-13
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::core::reader::types::{MemType, RefType};
+use crate::core::sha256::sha256;
+use crate::execution::assert_validated::UnwrapValidatedExt;
+use crate::execution::hooks::HookSet;
+use crate::execution::locals::Locals;
+use crate::execution::resumable::Resumable;
+use crate::execution::store::{FuncInst, MemInst};
+use crate::execution::value::{ExternAddr, Ref, Value, F32, F64};
+use crate::execution::value_stack::{CallFrame, Stack};
+use crate::execution::RuntimeInstance;
+use crate::validation::code::InstructionProbe;
+use crate::validation::validate_with_probe;
+use crate::value::InteropValueList;
+use crate::SnapshotError;
+
+const SNAPSHOT_MAGIC: &[u8; 8] = b"WASMSNAP";
+
+/// The version of the snapshot format, which is incremented on every incompatible change
+pub const SNAPSHOT_VERSION: u32 = 5;
+
+impl<'i, 'b, Returns, H> Resumable<'i, 'b, Returns, H>
+where
+    Returns: InteropValueList,
+    H: HookSet,
+{
+    /// Serialize the paused invocation, including the instance's store, into a snapshot
+    ///
+    /// The snapshot can be restored with [`RuntimeInstance::restore_resumable`] on an instance of
+    /// the same module.
+    ///
+    /// # Panics
+    /// If the invocation already finished or trapped.
+    pub fn serialize(&self) -> Vec<u8> {
+        assert!(
+            !self.finished,
+            "can not serialize an invocation that already finished"
+        );
+
+        let mut snapshot = Vec::new();
+        snapshot.extend_from_slice(SNAPSHOT_MAGIC);
+        write_u32(&mut snapshot, SNAPSHOT_VERSION);
+        snapshot.extend_from_slice(&sha256(self.instance.wasm_bytecode));
+        write_usize(&mut snapshot, self.pc);
+        write_usize(&mut snapshot, self.stp);
+        snapshot.push(u8::from(self.entered));
+        snapshot.push(u8::from(self.paused_by_hook));
+
+        let store = &self.instance.store;
+        write_usize(&mut snapshot, store.mems.len());
+        for mem in &store.mems {
+            write_usize(&mut snapshot, mem.data.len());
+            snapshot.extend_from_slice(&mem.data);
+        }
+
+        write_usize(&mut snapshot, store.globals.len());
+        for global in &store.globals {
+            write_value(&mut snapshot, global.value);
+        }
+
+        write_usize(&mut snapshot, store.tables.len());
+        for table in &store.tables {
+            write_usize(&mut snapshot, table.elem.len());
+            for reference in &table.elem {
+                write_value(&mut snapshot, Value::Ref(*reference));
+            }
+        }
+
+        // dropped segments are empty, non-empty segments can not have been dropped
+        write_usize(&mut snapshot, store.datas.len());
+        for data in &store.datas {
+            snapshot.push(u8::from(data.data.is_empty()));
+        }
+
+        write_usize(&mut snapshot, store.elems.len());
+        for elem in &store.elems {
+            snapshot.push(u8::from(elem.references.is_empty()));
+        }
+
+        write_values(&mut snapshot, self.stack.values());
+
+        let frames = self.stack.frames();
+        write_usize(&mut snapshot, frames.len());
+        for frame in frames {
+            write_usize(&mut snapshot, frame.func_idx);
+            write_usize(&mut snapshot, frame.return_addr);
+            write_usize(&mut snapshot, frame.return_stp);
+            write_usize(&mut snapshot, frame.value_stack_base_idx);
+            write_usize(&mut snapshot, frame.return_value_count);
+            write_values(&mut snapshot, frame.locals.values());
+        }
+
+        snapshot
+    }
+}
+
+impl<'b, H> RuntimeInstance<'b, H>
+where
+    H: HookSet,
+{
+    /// Restore a paused invocation from a snapshot created by [`Resumable::serialize`]
+    ///
+    /// The instance's store is replaced by the one from the snapshot. Snapshots taken from
+    /// instances of other modules are refused, as well as snapshots whose call frames, operands or
+    /// PC could not occur while executing this module. Nothing is changed if an error is returned.
+    ///
+    /// Fails with [`SnapshotError::FunctionTypeMismatch`] if `Returns` does not match the return
+    /// types of the invoked function.
+    pub fn restore_resumable<Returns: InteropValueList>(
+        &mut self,
+        snapshot: &[u8],
+    ) -> Result<Resumable<'_, 'b, Returns, H>, SnapshotError> {
+        let mut reader = SnapshotReader {
+            bytes: snapshot,
+            num_funcs: self.store.funcs.len(),
+        };
+
+        if reader.read_bytes(SNAPSHOT_MAGIC.len())? != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidMagic);
+        }
+        let version = reader.read_u32()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        if reader.read_bytes(32)? != sha256(self.wasm_bytecode) {
+            return Err(SnapshotError::ModuleMismatch);
+        }
+        let pc = reader.read_usize()?;
+        let stp = reader.read_usize()?;
+        let entered = reader.read_bool()?;
+        let paused_by_hook = reader.read_bool()?;
+
+        let num_mems = reader.read_usize()?;
+        if num_mems != self.store.mems.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+        let mut mems_data = Vec::with_capacity(num_mems);
+        for mem in &self.store.mems {
+            let len = reader.read_usize()?;
+            let data = reader.read_bytes(len)?;
+
+            let pages = len / MemInst::PAGE_SIZE;
+            let pages_within_limits = pages >= mem.ty.limits.min as usize
+                && pages <= mem.ty.limits.max.unwrap_or(MemType::MAX_PAGES) as usize;
+            if len % MemInst::PAGE_SIZE != 0 || !pages_within_limits {
+                return Err(SnapshotError::InvalidState);
+            }
+            mems_data.push(data.to_vec());
+        }
+
+        let num_globals = reader.read_usize()?;
+        if num_globals != self.store.globals.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+        let mut global_values = Vec::with_capacity(num_globals);
+        for global in &self.store.globals {
+            let value = reader.read_value()?;
+            if value.to_ty() != global.ty.ty {
+                return Err(SnapshotError::InvalidState);
+            }
+            global_values.push(value);
+        }
+
+        let num_tables = reader.read_usize()?;
+        if num_tables != self.store.tables.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+        let mut tables_elems = Vec::with_capacity(num_tables);
+        for table in &self.store.tables {
+            let elem = reader
+                .read_values()?
+                .into_iter()
+                .map(|value| match value {
+                    Value::Ref(reference) if reference.ty() == table.ty.et => Ok(reference),
+                    _ => Err(SnapshotError::InvalidState),
+                })
+                .collect::<Result<Vec<Ref>, SnapshotError>>()?;
+
+            let len_within_limits = elem.len() >= table.ty.lim.min as usize
+                && elem.len() <= table.ty.lim.max.unwrap_or(u32::MAX) as usize;
+            if !len_within_limits {
+                return Err(SnapshotError::InvalidState);
+            }
+            tables_elems.push(elem);
+        }
+
+        // A segment dropped by this instance can not be brought back
+        let num_datas = reader.read_usize()?;
+        if num_datas != self.store.datas.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+        let mut datas_dropped = Vec::with_capacity(num_datas);
+        for data in &self.store.datas {
+            let dropped = reader.read_bool()?;
+            if !dropped && data.data.is_empty() {
+                return Err(SnapshotError::InvalidState);
+            }
+            datas_dropped.push(dropped);
+        }
+
+        let num_elems = reader.read_usize()?;
+        if num_elems != self.store.elems.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+        let mut elems_dropped = Vec::with_capacity(num_elems);
+        for elem in &self.store.elems {
+            let dropped = reader.read_bool()?;
+            if !dropped && elem.references.is_empty() {
+                return Err(SnapshotError::InvalidState);
+            }
+            elems_dropped.push(dropped);
+        }
+
+        let values = reader.read_values()?;
+
+        let num_frames = reader.read_usize()?;
+        let mut frames = Vec::new();
+        for _ in 0..num_frames {
+            let func_idx = reader.read_usize()?;
+            let return_addr = reader.read_usize()?;
+            let return_stp = reader.read_usize()?;
+            let value_stack_base_idx = reader.read_usize()?;
+            let return_value_count = reader.read_usize()?;
+            let locals = reader.read_values()?;
+
+            let func_inst = self
+                .store
+                .funcs
+                .get(func_idx)
+                .and_then(FuncInst::try_into_local)
+                .ok_or(SnapshotError::InvalidState)?;
+            let func_ty = &self.types[func_inst.ty];
+
+            let local_tys = func_ty.params.valtypes.iter().chain(&func_inst.locals);
+            let locals_match = locals.iter().map(Value::to_ty).eq(local_tys.copied());
+            let previous_base_idx = frames
+                .last()
+                .map_or(0, |frame: &CallFrame| frame.value_stack_base_idx);
+            // The outermost frame owns all values below the first call
+            let base_idx_valid = match frames.is_empty() {
+                true => value_stack_base_idx == 0,
+                false => value_stack_base_idx >= previous_base_idx,
+            };
+            if !locals_match
+                || return_value_count != func_ty.returns.valtypes.len()
+                || !base_idx_valid
+                || value_stack_base_idx > values.len()
+            {
+                return Err(SnapshotError::InvalidState);
+            }
+
+            frames.push(CallFrame {
+                func_idx,
+                locals: Locals::new(locals.into_iter(), core::iter::empty()),
+                return_addr,
+                return_stp,
+                value_stack_base_idx,
+                return_value_count,
+            });
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(SnapshotError::TrailingBytes);
+        }
+
+        // A paused invocation always has a function that it continues in
+        if frames.is_empty() {
+            return Err(SnapshotError::InvalidState);
+        }
+
+        // Every frame continues at an instruction, which is the paused one for the innermost frame
+        // and the one after the call of the next frame for all others. Validation of the module
+        // is repeated to find out which side-table pointer and operands these instructions expect.
+        let continuations = frames
+            .iter()
+            .skip(1)
+            .map(|callee| (callee.return_addr, callee.return_stp))
+            .chain([(pc, stp)])
+            .collect::<Vec<(usize, usize)>>();
+        let mut probe = InstructionProbe::new(continuations.iter().map(|(pc, _)| *pc));
+        validate_with_probe(self.wasm_bytecode, Some(&mut probe)).unwrap_validated();
+
+        for (idx, (frame, (continue_pc, continue_stp))) in
+            frames.iter().zip(continuations).enumerate()
+        {
+            let code = self.store.funcs[frame.func_idx]
+                .try_into_local()
+                .unwrap_validated()
+                .code_expr;
+            let state = probe
+                .state(continue_pc)
+                .filter(|_| (code.from()..code.from() + code.len()).contains(&continue_pc))
+                .filter(|state| state.stp == continue_stp)
+                .ok_or(SnapshotError::InvalidState)?;
+
+            // The results of a callee are already on the stack once its caller continues
+            let (values_end, callee_results) = match frames.get(idx + 1) {
+                Some(callee) => {
+                    let callee_ty = &self.types[self.store.funcs[callee.func_idx].ty()];
+                    (callee.value_stack_base_idx, &callee_ty.returns.valtypes[..])
+                }
+                None => (values.len(), &[][..]),
+            };
+            let frame_tys = values[frame.value_stack_base_idx..values_end]
+                .iter()
+                .map(Value::to_ty)
+                .chain(callee_results.iter().copied());
+            if !frame_tys.eq(state.val_types.iter().copied()) {
+                return Err(SnapshotError::InvalidState);
+            }
+        }
+
+        let invoked_func = &self.store.funcs[frames[0].func_idx];
+        if self.types[invoked_func.ty()].returns.valtypes != Returns::TYS {
+            return Err(SnapshotError::FunctionTypeMismatch);
+        }
+
+        // The snapshot is valid, now the instance's state can be replaced
+        for (mem, data) in self.store.mems.iter_mut().zip(mems_data) {
+            mem.data = data;
+        }
+        for (global, value) in self.store.globals.iter_mut().zip(global_values) {
+            global.value = value;
+        }
+        for (table, elem) in self.store.tables.iter_mut().zip(tables_elems) {
+            table.elem = elem;
+        }
+        for (data, dropped) in self.store.datas.iter_mut().zip(datas_dropped) {
+            if dropped {
+                data.data = Vec::new();
+            }
+        }
+        for (elem, dropped) in self.store.elems.iter_mut().zip(elems_dropped) {
+            if dropped {
+                elem.references = Vec::new();
+            }
+        }
+
+        Ok(Resumable {
+            instance: self,
+            stack: Stack::from_parts(values, frames),
+            pc,
+            stp,
+            entered,
+            paused_by_hook,
+            finished: false,
+            _returns: PhantomData,
+        })
+    }
+}
+
+fn write_u32(snapshot: &mut Vec<u8>, value: u32) {
+    snapshot.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_u64(snapshot: &mut Vec<u8>, value: u64) {
+    snapshot.extend_from_slice(&value.to_le_bytes());
+}
+
+/// `usize::MAX` is used as a marker, e.g. for the return address of the outermost function. It is
+/// encoded as `u64::MAX`, so that it keeps its meaning across different pointer widths.
+fn write_usize(snapshot: &mut Vec<u8>, value: usize) {
+    let value = if value == usize::MAX {
+        u64::MAX
+    } else {
+        value as u64
+    };
+    write_u64(snapshot, value);
+}
+
+fn write_value(snapshot: &mut Vec<u8>, value: Value) {
+    match value {
+        Value::I32(x) => {
+            snapshot.push(0x7F);
+            write_u32(snapshot, x);
+        }
+        Value::I64(x) => {
+            snapshot.push(0x7E);
+            write_u64(snapshot, x);
+        }
+        Value::F32(x) => {
+            snapshot.push(0x7D);
+            write_u32(snapshot, x.0.to_bits());
+        }
+        Value::F64(x) => {
+            snapshot.push(0x7C);
+            write_u64(snapshot, x.0.to_bits());
+        }
+        Value::V128(bytes) => {
+            snapshot.push(0x7B);
+            snapshot.extend_from_slice(&bytes);
+        }
+        Value::Ref(reference) => {
+            match reference.ty() {
+                RefType::FuncRef => snapshot.push(0x70),
+                RefType::ExternRef => snapshot.push(0x6F),
+            }
+            match reference {
+                Ref::Null(_) => write_usize(snapshot, usize::MAX),
+                Ref::Func(func_idx) => write_usize(snapshot, func_idx),
+                Ref::Extern(ExternAddr(handle)) => write_u64(snapshot, handle.into()),
+            }
+        }
+    }
+}
+
+fn write_values(snapshot: &mut Vec<u8>, values: &[Value]) {
+    write_usize(snapshot, values.len());
+    for value in values {
+        write_value(snapshot, *value);
+    }
+}
+
+struct SnapshotReader<'a> {
+    /// The bytes that were not read yet
+    bytes: &'a [u8],
+    /// The number of functions of the instance, which function references must not exceed
+    num_funcs: usize,
+}
+
+impl<'a> SnapshotReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.bytes.len() < len {
+            return Err(SnapshotError::Eof);
+        }
+        let (read, remaining) = self.bytes.split_at(len);
+        self.bytes = remaining;
+        Ok(read)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    fn read_u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    /// Reads a flag, which is encoded as either `0` or `1`
+    fn read_bool(&mut self) -> Result<bool, SnapshotError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SnapshotError::InvalidState),
+        }
+    }
+
+    fn read_u32(&mut self) -> Result<u32, SnapshotError> {
+        self.read_array().map(u32::from_le_bytes)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, SnapshotError> {
+        self.read_array().map(u64::from_le_bytes)
+    }
+
+    /// See [`write_usize`]
+    fn read_usize(&mut self) -> Result<usize, SnapshotError> {
+        match self.read_u64()? {
+            u64::MAX => Ok(usize::MAX),
+            value => usize::try_from(value).map_err(|_| SnapshotError::InvalidState),
+        }
+    }
+
+    fn read_value(&mut self) -> Result<Value, SnapshotError> {
+        let value = match self.read_u8()? {
+            0x7F => Value::I32(self.read_u32()?),
+            0x7E => Value::I64(self.read_u64()?),
+            0x7D => Value::F32(F32(f32::from_bits(self.read_u32()?))),
+            0x7C => Value::F64(F64(f64::from_bits(self.read_u64()?))),
+            0x7B => Value::V128(self.read_array()?),
+            0x70 => match self.read_usize()? {
+                usize::MAX => Value::Ref(Ref::Null(RefType::FuncRef)),
+                func_idx if func_idx < self.num_funcs => Value::Ref(Ref::Func(func_idx)),
+                _ => return Err(SnapshotError::InvalidState),
+            },
+            0x6F => match self.read_u64()? {
+                u64::MAX => Value::Ref(Ref::Null(RefType::ExternRef)),
+                handle => u32::try_from(handle)
+                    .map(|handle| Value::Ref(Ref::Extern(ExternAddr(handle))))
+                    .map_err(|_| SnapshotError::InvalidState)?,
+            },
+            _ => return Err(SnapshotError::InvalidState),
+        };
+        Ok(value)
+    }
+
+    fn read_values(&mut self) -> Result<Vec<Value>, SnapshotError> {
+        let len = self.read_usize()?;
+        // Every value takes at least 5 bytes, which limits the allocation for malicious lengths
+        let mut values = Vec::with_capacity(len.min(self.bytes.len() / 5));
+        for _ in 0..len {
+            values.push(self.read_value()?);
+        }
+        Ok(values)
+    }
+}
@@ -1,12 +1,16 @@
+use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
-use core::iter;
+use core::ops::Range;
 
 use crate::core::indices::TypeIdx;
 use crate::core::reader::span::Span;
-use crate::core::reader::types::global::Global;
+use crate::core::reader::types::global::GlobalType;
 use crate::core::reader::types::{MemType, TableType, ValType};
+use crate::execution::linker::HostFunc;
+use crate::execution::memory_growth::MemoryGrowthPolicy;
 use crate::execution::value::{Ref, Value};
+use crate::RuntimeError;
 
 /// The store represents all global state that can be manipulated by WebAssembly programs. It
 /// consists of the runtime representation of all instances of functions, tables, memories, and
@@ -15,31 +19,133 @@ use crate::execution::value::{Ref, Value};
 /// <https://webassembly.github.io/spec/core/exec/runtime.html#store>
 pub struct Store {
     pub funcs: Vec<FuncInst>,
-    // tables: Vec<TableInst>,
+    pub tables: Vec<TableInst>,
     pub mems: Vec<MemInst>,
     pub globals: Vec<GlobalInst>,
+    pub elems: Vec<ElemInst>,
+    pub datas: Vec<DataInst>,
+    /// The host functions that imported functions refer to
+    pub host_funcs: Vec<HostFunc>,
+    /// Decides whether memories may grow, in addition to their limits
+    pub memory_growth_policy: Option<Box<dyn MemoryGrowthPolicy>>,
 }
 
-pub struct FuncInst {
+pub enum FuncInst {
+    Local(LocalFuncInst),
+    Imported(ImportedFuncInst),
+}
+
+impl FuncInst {
+    pub fn ty(&self) -> TypeIdx {
+        match self {
+            FuncInst::Local(func) => func.ty,
+            FuncInst::Imported(func) => func.ty,
+        }
+    }
+
+    pub fn try_into_local(&self) -> Option<&LocalFuncInst> {
+        match self {
+            FuncInst::Local(func) => Some(func),
+            FuncInst::Imported(_) => None,
+        }
+    }
+}
+
+/// A function defined in the module itself
+pub struct LocalFuncInst {
     pub ty: TypeIdx,
     pub locals: Vec<ValType>,
     pub code_expr: Span,
+    /// Index of the function's first entry in the side-table
+    pub stp: usize,
+}
+
+/// A function imported from the host
+pub struct ImportedFuncInst {
+    pub ty: TypeIdx,
+    /// Index into [`Store::host_funcs`]
+    pub host_func_idx: usize,
 }
 
-#[allow(dead_code)]
 pub struct TableInst {
     pub ty: TableType,
     pub elem: Vec<Ref>,
 }
 
+impl TableInst {
+    pub fn new(ty: TableType) -> Self {
+        Self {
+            ty,
+            elem: vec![Ref::Null(ty.et); ty.lim.min as usize],
+        }
+    }
+
+    /// Returns the size of the table in elements
+    pub fn size(&self) -> u32 {
+        self.elem.len() as u32
+    }
+
+    /// Grows the table by `delta` elements initialized to `init`, returning its previous size
+    ///
+    /// Fails if the new size would exceed the table's maximum or if the table can not be
+    /// allocated. The table is left unchanged in that case.
+    pub fn grow(&mut self, delta: u32, init: Ref) -> Option<u32> {
+        let size = self.size();
+        let max = self.ty.lim.max.unwrap_or(u32::MAX);
+        let new_size = size
+            .checked_add(delta)
+            .filter(|new_size| *new_size <= max)?;
+
+        self.elem.try_reserve_exact(delta as usize).ok()?;
+        self.elem.resize(new_size as usize, init);
+
+        Some(size)
+    }
+
+    /// Sets `len` elements starting at `dest` to `value`
+    pub fn fill(&mut self, dest: u32, value: Ref, len: u32) -> Result<(), RuntimeError> {
+        let range = self.range(dest, len)?;
+        self.elem[range].fill(value);
+        Ok(())
+    }
+
+    /// Returns the range of `len` elements starting at `start`, if it lies entirely within this
+    /// table
+    pub fn range(&self, start: u32, len: u32) -> Result<Range<usize>, RuntimeError> {
+        checked_range(start, len, self.elem.len()).ok_or(RuntimeError::TableAccessOutOfBounds)
+    }
+
+    /// Copies `len` elements from `src` to `dest` within this table, the ranges may overlap
+    pub fn copy_within(&mut self, dest: u32, src: u32, len: u32) -> Result<(), RuntimeError> {
+        let src_range = self.range(src, len)?;
+        let dest_range = self.range(dest, len)?;
+        self.elem.copy_within(src_range, dest_range.start);
+        Ok(())
+    }
+
+    /// Copies `len` references starting at `src` from `refs` to `dest` in this table
+    pub fn init(
+        &mut self,
+        dest: u32,
+        refs: &[Ref],
+        src: u32,
+        len: u32,
+    ) -> Result<(), RuntimeError> {
+        let src_range =
+            checked_range(src, len, refs.len()).ok_or(RuntimeError::TableAccessOutOfBounds)?;
+        let dest_range = self.range(dest, len)?;
+        self.elem[dest_range].copy_from_slice(&refs[src_range]);
+        Ok(())
+    }
+}
+
 pub struct MemInst {
-    #[allow(warnings)]
     pub ty: MemType,
     pub data: Vec<u8>,
 }
 
 impl MemInst {
-    const PAGE_SIZE: usize = 1 << 16;
+    pub const PAGE_SIZE: usize = 1 << 16;
     pub fn new(ty: MemType) -> Self {
         let initial_size = Self::PAGE_SIZE * ty.limits.min as usize;
 
@@ -49,20 +155,135 @@ impl MemInst {
         }
     }
 
-    #[allow(dead_code)]
-    pub fn grow(&mut self, delta_pages: usize) {
-        self.data
-            .extend(iter::repeat(0).take(delta_pages * Self::PAGE_SIZE))
+    /// Grows the memory by `delta_pages`, returning its previous size in pages
+    ///
+    /// Fails if the new size would exceed the memory's maximum, if `policy` refuses the growth or
+    /// if the memory can not be allocated. The memory is left unchanged in that case.
+    pub fn grow(
+        &mut self,
+        delta_pages: u32,
+        policy: Option<&mut (dyn MemoryGrowthPolicy + 'static)>,
+    ) -> Option<u32> {
+        let size = self.size();
+        let max = self.ty.limits.max.unwrap_or(MemType::MAX_PAGES);
+        let new_size = size
+            .checked_add(delta_pages)
+            .filter(|new_size| *new_size <= max)?;
+
+        if let Some(policy) = policy {
+            if !policy.allow_growth(size, new_size) {
+                return None;
+            }
+        }
+
+        let additional = (delta_pages as usize).checked_mul(Self::PAGE_SIZE)?;
+        self.data.try_reserve_exact(additional).ok()?;
+        self.data.resize(self.data.len() + additional, 0);
+
+        Some(size)
+    }
+
+    /// Returns the size of the memory in pages
+    pub fn size(&self) -> u32 {
+        (self.data.len() / Self::PAGE_SIZE) as u32
+    }
+
+    /// Reads `N` bytes starting at the effective address `offset + address`
+    pub fn load<const N: usize>(&self, offset: u32, address: u32) -> Result<[u8; N], RuntimeError> {
+        let range = self.access_range(offset, address, N)?;
+        Ok(self.data[range]
+            .try_into()
+            .expect("the range to be exactly N bytes"))
+    }
+
+    /// Writes `bytes` starting at the effective address `offset + address`
+    pub fn store<const N: usize>(
+        &mut self,
+        offset: u32,
+        address: u32,
+        bytes: [u8; N],
+    ) -> Result<(), RuntimeError> {
+        let range = self.access_range(offset, address, N)?;
+        self.data[range].copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    /// Sets `len` bytes starting at `dest` to `value`
+    pub fn fill(&mut self, dest: u32, value: u8, len: u32) -> Result<(), RuntimeError> {
+        let range = self.access_range(0, dest, len as usize)?;
+        self.data[range].fill(value);
+        Ok(())
+    }
+
+    /// Copies `len` bytes from `src` to `dest` within this memory, the ranges may overlap
+    pub fn copy_within(&mut self, dest: u32, src: u32, len: u32) -> Result<(), RuntimeError> {
+        let src_range = self.access_range(0, src, len as usize)?;
+        let dest_range = self.access_range(0, dest, len as usize)?;
+        self.data.copy_within(src_range, dest_range.start);
+        Ok(())
     }
 
-    #[allow(dead_code)]
-    pub fn size(&self) -> usize {
-        self.data.len() / Self::PAGE_SIZE
+    /// Copies `len` bytes starting at `src` from `bytes` to `dest` in this memory
+    pub fn init(
+        &mut self,
+        dest: u32,
+        bytes: &[u8],
+        src: u32,
+        len: u32,
+    ) -> Result<(), RuntimeError> {
+        let src_range =
+            checked_range(src, len, bytes.len()).ok_or(RuntimeError::MemoryOutOfBounds)?;
+        let dest_range = self.access_range(0, dest, len as usize)?;
+        self.data[dest_range].copy_from_slice(&bytes[src_range]);
+        Ok(())
+    }
+
+    /// Returns the range of an access of `len` bytes, if it lies entirely within this memory
+    ///
+    /// The effective address is a 33-bit integer, so it is computed without wrapping around.
+    /// See: <https://webassembly.github.io/spec/core/exec/instructions.html#exec-load>
+    fn access_range(
+        &self,
+        offset: u32,
+        address: u32,
+        len: usize,
+    ) -> Result<Range<usize>, RuntimeError> {
+        let start = u64::from(offset) + u64::from(address);
+        let end = start + len as u64;
+        if end > self.data.len() as u64 {
+            return Err(RuntimeError::MemoryOutOfBounds);
+        }
+
+        // Both fit into a usize, as they are bounded by the memory's length
+        Ok(start as usize..end as usize)
     }
 }
 
+/// Returns the range of `len` items starting at `start`, if it lies within a sequence of `total`
+/// items
+fn checked_range(start: u32, len: u32, total: usize) -> Option<Range<usize>> {
+    let end = u64::from(start) + u64::from(len);
+    (end <= total as u64).then_some(start as usize..end as usize)
+}
+
+/// The references of an element segment, which can be copied into tables by `table.init`
+///
+/// Active and declarative segments are dropped during instantiation.
+/// <https://webassembly.github.io/spec/core/exec/runtime.html#element-instances>
+pub struct ElemInst {
+    pub references: Vec<Ref>,
+}
+
+/// The bytes of a data segment, which can be copied into memory by `memory.init`
+///
+/// Active segments are dropped during instantiation.
+/// <https://webassembly.github.io/spec/core/exec/runtime.html#data-instances>
+pub struct DataInst {
+    pub data: Vec<u8>,
+}
+
 pub struct GlobalInst {
-    pub global: Global,
+    pub ty: GlobalType,
     /// Must be of the same type as specified in `ty`
     pub value: Value,
 }
@@ -4,7 +4,8 @@ use core::f32;
 use core::fmt::{Debug, Display};
 use core::ops::{Add, Div, Mul, Sub};
 
-use crate::core::reader::types::{NumType, ValType};
+use crate::core::indices::FuncIdx;
+use crate::core::reader::types::{NumType, RefType, ValType};
 use crate::execution::assert_validated::UnwrapValidatedExt;
 use crate::unreachable_validated;
 
@@ -209,16 +210,40 @@ pub enum Value {
     I64(u64),
     F32(F32),
     F64(F64),
-    // F64,
-    // V128,
+    /// The 16 bytes of a vector in little-endian byte order, so that lane `i` of a shape with
+    /// `n`-byte lanes consists of the bytes `i * n..(i + 1) * n`
+    V128([u8; 16]),
+    Ref(Ref),
 }
 
-#[derive(Clone, Debug, PartialEq)]
-#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Ref {
-    Null,
-    // Func,
-    // Extern,
+    Null(RefType),
+    /// A reference to the function at this index in the function index space
+    Func(FuncIdx),
+    /// A reference to an object of the host, which WASM code can only pass around
+    Extern(ExternAddr),
+}
+
+/// An opaque handle to an object of the host, which is chosen by the host itself
+///
+/// Hosts can pass these as `externref` values into and out of
+/// [`RuntimeInstance::invoke_dynamic`](crate::RuntimeInstance::invoke_dynamic).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExternAddr(pub u32);
+
+impl Ref {
+    pub fn ty(&self) -> RefType {
+        match self {
+            Ref::Null(ty) => *ty,
+            Ref::Func(_) => RefType::FuncRef,
+            Ref::Extern(_) => RefType::ExternRef,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Ref::Null(_))
+    }
 }
 
 impl Value {
@@ -228,9 +253,8 @@ impl Value {
             ValType::NumType(NumType::I64) => Self::I64(0),
             ValType::NumType(NumType::F32) => Self::F32(F32(0.0)),
             ValType::NumType(NumType::F64) => Self::F64(F64(0.0_f64)),
-            other => {
-                todo!("cannot determine type for {other:?} because this value is not supported yet")
-            }
+            ValType::VecType => Self::V128([0; 16]),
+            ValType::RefType(ty) => Self::Ref(Ref::Null(ty)),
         }
     }
 
@@ -240,6 +264,8 @@ impl Value {
             Value::I64(_) => ValType::NumType(NumType::I64),
             Value::F32(_) => ValType::NumType(NumType::F32),
             Value::F64(_) => ValType::NumType(NumType::F64),
+            Value::V128(_) => ValType::VecType,
+            Value::Ref(reference) => ValType::RefType(reference.ty()),
         }
     }
 }
@@ -402,82 +428,99 @@ impl InteropValue for f64 {
     }
 }
 
-impl InteropValueList for () {
-    const TYS: &'static [ValType] = &[];
+impl InteropValue for [u8; 16] {
+    const TY: ValType = ValType::VecType;
 
     #[allow(warnings)]
-    fn into_values(self) -> Vec<Value> {
-        Vec::new()
+    fn into_value(self) -> Value {
+        Value::V128(self)
     }
 
     #[allow(warnings)]
-    fn from_values(_values: impl Iterator<Item = Value>) -> Self {}
+    fn from_value(value: Value) -> Self {
+        match value {
+            Value::V128(bytes) => bytes,
+            _ => unreachable_validated!(),
+        }
+    }
 }
 
-impl<A: InteropValue> InteropValueList for A {
-    const TYS: &'static [ValType] = &[A::TY];
+/// Vectors as a single integer, whose least significant byte is the vector's first byte
+impl InteropValue for u128 {
+    const TY: ValType = ValType::VecType;
 
     #[allow(warnings)]
-    fn into_values(self) -> Vec<Value> {
-        vec![self.into_value()]
+    fn into_value(self) -> Value {
+        Value::V128(self.to_le_bytes())
     }
 
     #[allow(warnings)]
-    fn from_values(mut values: impl Iterator<Item = Value>) -> Self {
-        A::from_value(values.next().unwrap_validated())
+    fn from_value(value: Value) -> Self {
+        match value {
+            Value::V128(bytes) => u128::from_le_bytes(bytes),
+            _ => unreachable_validated!(),
+        }
     }
 }
 
-impl<A: InteropValue> InteropValueList for (A,) {
-    const TYS: &'static [ValType] = &[A::TY];
+impl InteropValueList for () {
+    const TYS: &'static [ValType] = &[];
+
     #[allow(warnings)]
     fn into_values(self) -> Vec<Value> {
-        vec![self.0.into_value()]
+        Vec::new()
     }
 
     #[allow(warnings)]
-    fn from_values(mut values: impl Iterator<Item = Value>) -> Self {
-        (A::from_value(values.next().unwrap_validated()),)
-    }
+    fn from_values(_values: impl Iterator<Item = Value>) -> Self {}
 }
 
-impl<A: InteropValue, B: InteropValue> InteropValueList for (A, B) {
-    const TYS: &'static [ValType] = &[A::TY, B::TY];
+impl<A: InteropValue> InteropValueList for A {
+    const TYS: &'static [ValType] = &[A::TY];
+
     #[allow(warnings)]
     fn into_values(self) -> Vec<Value> {
-        vec![self.0.into_value(), self.1.into_value()]
+        vec![self.into_value()]
     }
 
     #[allow(warnings)]
     fn from_values(mut values: impl Iterator<Item = Value>) -> Self {
-        (
-            A::from_value(values.next().unwrap_validated()),
-            B::from_value(values.next().unwrap_validated()),
-        )
+        A::from_value(values.next().unwrap_validated())
     }
 }
 
-impl<A: InteropValue, B: InteropValue, C: InteropValue> InteropValueList for (A, B, C) {
-    const TYS: &'static [ValType] = &[A::TY, B::TY, C::TY];
-    #[allow(warnings)]
-    fn into_values(self) -> Vec<Value> {
-        vec![
-            self.0.into_value(),
-            self.1.into_value(),
-            self.2.into_value(),
-        ]
-    }
+/// Implements [InteropValueList] for a tuple, given each element's type parameter and field index
+macro_rules! impl_interop_value_list_for_tuple {
+    ($($ty:ident $idx:tt),+) => {
+        impl<$($ty: InteropValue),+> InteropValueList for ($($ty,)+) {
+            const TYS: &'static [ValType] = &[$($ty::TY),+];
 
-    #[allow(warnings)]
-    fn from_values(mut values: impl Iterator<Item = Value>) -> Self {
-        (
-            A::from_value(values.next().unwrap_validated()),
-            B::from_value(values.next().unwrap_validated()),
-            C::from_value(values.next().unwrap_validated()),
-        )
-    }
+            #[allow(warnings)]
+            fn into_values(self) -> Vec<Value> {
+                vec![$(self.$idx.into_value()),+]
+            }
+
+            #[allow(warnings)]
+            fn from_values(mut values: impl Iterator<Item = Value>) -> Self {
+                ($($ty::from_value(values.next().unwrap_validated()),)+)
+            }
+        }
+    };
 }
 
+impl_interop_value_list_for_tuple!(A 0);
+impl_interop_value_list_for_tuple!(A 0, B 1);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2, D 3);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_interop_value_list_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
 // TODO: don't let this like this, use a macro
 impl From<f32> for Value {
     fn from(x: f32) -> Self {
@@ -526,3 +569,5 @@ impl_value_conversion!(u64);
 impl_value_conversion!(i64);
 impl_value_conversion!(F32);
 impl_value_conversion!(F64);
+impl_value_conversion!([u8; 16]);
+impl_value_conversion!(u128);
@@ -9,8 +9,9 @@ use crate::unreachable_validated;
 
 /// The stack at runtime containing
 /// 1. Values
-/// 2. Labels
-/// 3. Activations
+/// 2. Activations
+///
+/// Labels are not stored on the stack, branches are resolved using the side-table instead.
 ///
 /// See <https://webassembly.github.io/spec/core/exec/runtime.html#stack>
 #[derive(Default)]
@@ -31,6 +32,16 @@ impl Stack {
 
     /// Pop a value of the given [ValType] from the value stack
     pub fn pop_value(&mut self, ty: ValType) -> Value {
+        let popped = self.pop_any_value();
+        if popped.to_ty() == ty {
+            popped
+        } else {
+            unreachable_validated!()
+        }
+    }
+
+    /// Pop a value of any type, e.g. for parametric instructions like `drop`
+    pub fn pop_any_value(&mut self) -> Value {
         // If there is at least one stack frame, we shall not pop values past the current
         // stackframe. However, there is one legitimate reason to pop when there is **no** current
         // stackframe: after the outermost function returns, to extract the final return values of
@@ -44,12 +55,7 @@ impl Stack {
             "can not pop values past the current stackframe"
         );
 
-        let popped = self.values.pop().unwrap_validated();
-        if popped.to_ty() == ty {
-            popped
-        } else {
-            unreachable_validated!()
-        }
+        self.values.pop().unwrap_validated()
     }
 
     /// Copy a value of the given [ValType] from the value stack without removing it
@@ -78,10 +84,6 @@ impl Stack {
         let local_ty = self.current_stackframe().locals.get_ty(idx);
 
         let stack_value = self.pop_value(local_ty);
-        debug_assert!(
-            self.values.len() > self.current_stackframe().value_stack_base_idx,
-            "can not pop values past the current stackframe"
-        );
 
         trace!("Instruction: local.set [{stack_value:?}] -> []");
         *self.current_stackframe_mut().locals.get_mut(idx) = stack_value;
@@ -105,10 +107,12 @@ impl Stack {
         self.frames.last_mut().unwrap_validated()
     }
 
-    /// Pop a [`CallFrame`] from the call stack, returning the return address
-    pub fn pop_stackframe(&mut self) -> usize {
+    /// Pop a [`CallFrame`] from the call stack, returning the return address and the side-table
+    /// pointer to continue with
+    pub fn pop_stackframe(&mut self) -> (usize, usize) {
         let CallFrame {
             return_addr,
+            return_stp,
             value_stack_base_idx,
             return_value_count,
             ..
@@ -123,7 +127,7 @@ impl Stack {
             "after a function call finished, the stack must have exactly as many values as it had before calling the function plus the number of function return values"
         );
 
-        return_addr
+        (return_addr, return_stp)
     }
 
     /// Push a stackframe to the call stack
@@ -135,35 +139,59 @@ impl Stack {
         func_ty: &FuncType,
         locals: Locals,
         return_addr: usize,
+        return_stp: usize,
     ) {
         self.frames.push(CallFrame {
             func_idx,
             locals,
             return_addr,
+            return_stp,
             value_stack_base_idx: self.values.len(),
             return_value_count: func_ty.returns.valtypes.len(),
         })
     }
 
+    /// Create a stack from previously saved values and call frames
+    pub fn from_parts(values: Vec<Value>, frames: Vec<CallFrame>) -> Self {
+        Self { values, frames }
+    }
+
+    /// Returns all call frames, with the current one being the last
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+
+    /// Returns all values on the value stack, with the topmost value being the last
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
     /// Returns how many stackframes are on the stack, in total.
     pub fn callframe_count(&self) -> usize {
         self.frames.len()
     }
 
+    /// Returns the topmost `n` values of the value stack, with the first element being closest to
+    /// the **bottom** of the value stack
+    #[cfg(feature = "hooks")]
+    pub fn peek_tail(&self, n: usize) -> &[Value] {
+        &self.values[self.values.len().saturating_sub(n)..]
+    }
+
     /// Pop `n` elements from the value stack's tail as an iterator, with the first element being
     /// closest to the **bottom** of the value stack
     ///
     /// Note that this is providing the values in reverse order compared to popping `n` values
     /// (which would yield the element closest to the **top** of the value stack first).
-    pub fn pop_tail_iter(&mut self, n: usize) -> Drain<Value> {
+    pub fn pop_tail_iter(&mut self, n: usize) -> Drain<'_, Value> {
         let start = self.values.len() - n;
         self.values.drain(start..)
     }
 
-    /// Clear all of the values pushed to the value stack by the current stack frame
-    pub fn clear_callframe_values(&mut self) {
-        self.values
-            .truncate(self.current_stackframe().value_stack_base_idx);
+    /// Discard the `popcnt` values below the topmost `valcnt` values, as done when taking a branch
+    pub fn remove_inbetween(&mut self, popcnt: usize, valcnt: usize) {
+        let truncation_top = self.values.len() - valcnt;
+        let _ = self.values.drain((truncation_top - popcnt)..truncation_top);
     }
 }
 
@@ -178,6 +206,9 @@ pub(crate) struct CallFrame {
     /// Value that the PC has to be set to when this function returns
     pub return_addr: usize,
 
+    /// Value that the side-table pointer has to be set to when this function returns
+    pub return_stp: usize,
+
     /// The index to the first value on [`Stack::values`] that belongs to this [`CallFrame`]
     pub value_stack_base_idx: usize,
 
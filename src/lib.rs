@@ -3,8 +3,8 @@ extern crate alloc;
 #[macro_use]
 extern crate log;
 
-pub use core::error::{Error, Result, RuntimeError};
-pub use core::reader::types::{NumType, RefType, ValType};
+pub use core::error::{Error, Result, RuntimeError, SnapshotError};
+pub use core::reader::types::{Limits, NumType, RefType, ValType};
 pub use execution::value::Value;
 pub use execution::*;
 pub use validation::*;
@@ -1,26 +1,84 @@
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::vec::Vec;
 use core::iter;
 
-use crate::core::indices::{FuncIdx, GlobalIdx, LocalIdx};
+use crate::core::indices::{
+    DataIdx, ElemIdx, FuncIdx, GlobalIdx, LabelIdx, LocalIdx, MemIdx, TableIdx, TypeIdx,
+};
 use crate::core::reader::section_header::{SectionHeader, SectionTy};
 use crate::core::reader::span::Span;
-use crate::core::reader::types::global::Global;
+use crate::core::reader::types::element::ElemSegment;
+use crate::core::reader::types::global::GlobalType;
 use crate::core::reader::types::memarg::MemArg;
-use crate::core::reader::types::{FuncType, NumType, ResultType, ValType};
+use crate::core::reader::types::{
+    BlockType, FuncType, MemType, NumType, RefType, ResultType, TableType, ValType,
+};
 use crate::core::reader::{WasmReadable, WasmReader};
+use crate::core::sidetable::{IncompleteSidetableEntry, Sidetable, SidetableEntry};
+use crate::execution::coverage::{Decision, DecisionKind};
+use crate::execution::hooks::Location;
+use crate::validation::validation_stack::{LabelKind, ValidationStack, ValidationStackEntry};
 use crate::{Error, Result};
 
+/// The state of validation right before an instruction
+pub(crate) struct InstructionState {
+    /// The side-table pointer at the instruction
+    pub stp: usize,
+    /// The types of the function's operands, with the last type being at the top of the stack
+    pub val_types: Vec<ValType>,
+}
+
+/// Records the state of validation before the instructions at a few given PCs
+///
+/// This allows to check whether an operand stack can occur at an instruction without keeping the
+/// state of validation for every single instruction around.
+pub(crate) struct InstructionProbe {
+    states: BTreeMap<usize, Option<InstructionState>>,
+}
+
+impl InstructionProbe {
+    pub fn new(pcs: impl IntoIterator<Item = usize>) -> Self {
+        Self {
+            states: pcs.into_iter().map(|pc| (pc, None)).collect(),
+        }
+    }
+
+    /// The state before the instruction at `pc`, or `None` if no reachable instruction starts at
+    /// `pc`
+    pub fn state(&self, pc: usize) -> Option<&InstructionState> {
+        self.states.get(&pc)?.as_ref()
+    }
+
+    fn record(&mut self, pc: usize, stack: &ValidationStack, stp: usize) {
+        if let Some(state) = self.states.get_mut(&pc) {
+            *state = stack
+                .reachable_val_types()
+                .map(|val_types| InstructionState { stp, val_types });
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn validate_code_section(
     wasm: &mut WasmReader,
     section_header: SectionHeader,
     fn_types: &[FuncType],
     type_idx_of_fn: &[usize],
-    globals: &[Global],
-) -> Result<Vec<Span>> {
+    num_imported_funcs: usize,
+    tables: &[TableType],
+    memories: &[MemType],
+    globals: &[GlobalType],
+    elements: &[ElemSegment],
+    data_count: Option<u32>,
+    declared_func_refs: &BTreeSet<FuncIdx>,
+    sidetable: &mut Sidetable,
+    decisions: &mut Vec<Decision>,
+    mut probe: Option<&mut InstructionProbe>,
+) -> Result<Vec<(Span, usize)>> {
     assert_eq!(section_header.ty, SectionTy::Code);
 
     let code_block_spans = wasm.read_vec_enumerated(|wasm, idx| {
-        let ty_idx = type_idx_of_fn[idx];
+        let ty_idx = type_idx_of_fn[num_imported_funcs + idx];
         let func_ty = fn_types[ty_idx].clone();
 
         let func_size = wasm.read_var_u32()?;
@@ -32,19 +90,39 @@ pub fn validate_code_section(
             params.chain(declared_locals).collect::<Vec<ValType>>()
         };
 
-        validate_value_stack(func_ty.returns, |value_stack| {
-            read_instructions(
-                idx,
-                wasm,
-                value_stack,
-                &locals,
-                globals,
-                fn_types,
-                type_idx_of_fn,
-            )
-        })?;
-
-        Ok(func_block)
+        // the side-table entries of this function start here
+        let stp = sidetable.len();
+
+        let code_start = wasm.pc;
+        let mut func_decisions = Vec::new();
+        let mut stack = ValidationStack::new_for_func(&func_ty, code_start, stp);
+        read_instructions(
+            wasm,
+            &mut stack,
+            sidetable,
+            &locals,
+            globals,
+            fn_types,
+            type_idx_of_fn,
+            tables,
+            memories,
+            elements,
+            data_count,
+            declared_func_refs,
+            &mut func_decisions,
+            probe.as_deref_mut(),
+        )?;
+
+        // decisions are located relative to the start of the function's code
+        decisions.extend(func_decisions.into_iter().map(|(pc, kind)| Decision {
+            location: Location {
+                func_idx: num_imported_funcs + idx,
+                offset: pc - code_start,
+            },
+            kind,
+        }));
+
+        Ok((func_block, stp))
     })?;
 
     trace!(
@@ -72,27 +150,136 @@ pub fn read_declared_locals(wasm: &mut WasmReader) -> Result<Vec<ValType>> {
     Ok(locals)
 }
 
+/// Read the [`MemArg`] of a load or store instruction, which accesses memory 0
+///
+/// `max_align` is the exponent of the natural alignment of the accessed type, which the alignment
+/// of the access must not exceed.
+fn read_memarg(wasm: &mut WasmReader, memories: &[MemType], max_align: u32) -> Result<MemArg> {
+    let memarg = MemArg::read(wasm)?;
+
+    if memories.is_empty() {
+        return Err(Error::InvalidMemIdx(0));
+    }
+    if memarg.align > max_align {
+        return Err(Error::InvalidAlignment(memarg.align));
+    }
+
+    Ok(memarg)
+}
+
+/// Read the index of a lane of a vector instruction, which must be less than `num_lanes`
+fn read_lane_idx(wasm: &mut WasmReader, num_lanes: u8) -> Result<u8> {
+    let lane_idx = wasm.read_u8()?;
+    if lane_idx >= num_lanes {
+        return Err(Error::InvalidLaneIdx(lane_idx));
+    }
+
+    Ok(lane_idx)
+}
+
+/// Read the index of a memory that an instruction refers to
+fn read_mem_idx(wasm: &mut WasmReader, memories: &[MemType]) -> Result<MemIdx> {
+    let mem_idx = wasm.read_var_u32()? as MemIdx;
+    if mem_idx >= memories.len() {
+        return Err(Error::InvalidMemIdx(mem_idx));
+    }
+
+    Ok(mem_idx)
+}
+
+/// Read the index of a table that an instruction refers to, returning the table's type
+fn read_table_idx(wasm: &mut WasmReader, tables: &[TableType]) -> Result<TableType> {
+    let table_idx = wasm.read_var_u32()? as TableIdx;
+    tables
+        .get(table_idx)
+        .copied()
+        .ok_or(Error::InvalidTableIdx(table_idx))
+}
+
+/// Read the index of an element segment that an instruction refers to, returning the segment
+fn read_elem_idx<'a>(
+    wasm: &mut WasmReader,
+    elements: &'a [ElemSegment],
+) -> Result<&'a ElemSegment> {
+    let elem_idx = wasm.read_var_u32()? as ElemIdx;
+    elements
+        .get(elem_idx)
+        .ok_or(Error::InvalidElemIdx(elem_idx))
+}
+
+/// Read the index of a data segment that an instruction refers to
+///
+/// Because the data section follows the code section, data segments can only be referenced if
+/// their number is announced in the data count section.
+fn read_data_idx(wasm: &mut WasmReader, data_count: Option<u32>) -> Result<DataIdx> {
+    let data_idx = wasm.read_var_u32()? as DataIdx;
+    let data_count = data_count.ok_or(Error::DataCountMissing)?;
+    if data_idx >= data_count as usize {
+        return Err(Error::InvalidDataIdx(data_idx));
+    }
+
+    Ok(data_idx)
+}
+
+/// Emit a side-table entry for a branch at `branch_pc` to the label `label_idx` levels outwards
+///
+/// This must be called while the values carried by the branch are still on the operand stack.
+fn add_branch_entry(
+    stack: &mut ValidationStack,
+    sidetable: &mut Sidetable,
+    branch_pc: usize,
+    label_idx: LabelIdx,
+) -> Result<()> {
+    let values_len = stack.values_len();
+    let label = stack.get_label_mut(label_idx)?;
+
+    let valcnt = label.label_types().len();
+    // Operands may be missing in unreachable code, but such a branch will never be taken anyway
+    let popcnt = values_len.saturating_sub(label.height + valcnt);
+
+    let branch = IncompleteSidetableEntry {
+        branch_pc,
+        stp: sidetable.len(),
+    };
+    sidetable.push(SidetableEntry {
+        delta_pc: 0,
+        delta_stp: 0,
+        valcnt,
+        popcnt,
+    });
+
+    // The target of a branch to a loop is already known, all other targets are behind the `end`
+    if label.kind == LabelKind::Loop {
+        branch.complete(sidetable, label.start_pc, label.start_stp);
+    } else {
+        label.pending_branches.push(branch);
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 fn read_instructions(
-    this_function_idx: usize,
     wasm: &mut WasmReader,
-    value_stack: &mut Vec<ValType>,
+    stack: &mut ValidationStack,
+    sidetable: &mut Sidetable,
     locals: &[ValType],
-    globals: &[Global],
+    globals: &[GlobalType],
     fn_types: &[FuncType],
     type_idx_of_fn: &[usize],
+    tables: &[TableType],
+    memories: &[MemType],
+    elements: &[ElemSegment],
+    data_count: Option<u32>,
+    declared_func_refs: &BTreeSet<FuncIdx>,
+    decisions: &mut Vec<(usize, DecisionKind)>,
+    mut probe: Option<&mut InstructionProbe>,
 ) -> Result<()> {
-    let assert_pop_value_stack = |value_stack: &mut Vec<ValType>, expected_ty: ValType| {
-        value_stack
-            .pop()
-            .ok_or(Error::InvalidValueStackType(None))
-            .and_then(|ty| {
-                (ty == expected_ty)
-                    .then_some(())
-                    .ok_or(Error::InvalidValueStackType(Some(ty)))
-            })
-    };
-
     loop {
+        let instr_pc = wasm.pc;
+        if let Some(probe) = probe.as_deref_mut() {
+            probe.record(instr_pc, stack, sidetable.len());
+        }
         let Ok(first_instr_byte) = wasm.read_u8() else {
             return Err(Error::ExprMissingEnd);
         };
@@ -100,63 +287,255 @@ fn read_instructions(
 
         use crate::core::reader::types::opcode::*;
         match first_instr_byte {
+            // unreachable: [t1*] -> [t2*]
+            UNREACHABLE => {
+                stack.make_unreachable();
+            }
             // nop
             NOP => {}
+            // block: [t1*] -> [t2*]
+            BLOCK => {
+                let block_ty = BlockType::read(wasm)?.as_func_type(fn_types)?;
+                stack.assert_pop_val_types(&block_ty.params.valtypes)?;
+                stack.push_ctrl(LabelKind::Block, block_ty, wasm.pc, sidetable.len());
+            }
+            // loop: [t1*] -> [t2*]
+            LOOP => {
+                let block_ty = BlockType::read(wasm)?.as_func_type(fn_types)?;
+                stack.assert_pop_val_types(&block_ty.params.valtypes)?;
+                stack.push_ctrl(LabelKind::Loop, block_ty, wasm.pc, sidetable.len());
+            }
+            // if: [t1* i32] -> [t2*]
+            IF => {
+                let block_ty = BlockType::read(wasm)?.as_func_type(fn_types)?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_types(&block_ty.params.valtypes)?;
+
+                // if the condition is false, execution continues in the else branch or at the end
+                let if_branch = IncompleteSidetableEntry {
+                    branch_pc: instr_pc,
+                    stp: sidetable.len(),
+                };
+                sidetable.push(SidetableEntry {
+                    delta_pc: 0,
+                    delta_stp: 0,
+                    valcnt: block_ty.params.valtypes.len(),
+                    popcnt: 0,
+                });
+
+                stack.push_ctrl(LabelKind::If, block_ty, wasm.pc, sidetable.len());
+                stack.innermost_ctrl_mut().if_branch = Some(if_branch);
+                decisions.push((instr_pc, DecisionKind::If));
+            }
+            ELSE => {
+                let ctrl = stack.pop_ctrl()?;
+                if ctrl.kind != LabelKind::If {
+                    return Err(Error::ElseWithoutMatchingIf);
+                }
+
+                // at the end of the then branch, execution continues behind the `end`
+                let mut pending_branches = ctrl.pending_branches;
+                pending_branches.push(IncompleteSidetableEntry {
+                    branch_pc: instr_pc,
+                    stp: sidetable.len(),
+                });
+                sidetable.push(SidetableEntry {
+                    delta_pc: 0,
+                    delta_stp: 0,
+                    valcnt: ctrl.end_types.len(),
+                    popcnt: 0,
+                });
+
+                ctrl.if_branch
+                    .unwrap()
+                    .complete(sidetable, wasm.pc, sidetable.len());
+
+                stack.push_ctrl(
+                    LabelKind::Else,
+                    FuncType {
+                        params: ResultType {
+                            valtypes: ctrl.start_types,
+                        },
+                        returns: ResultType {
+                            valtypes: ctrl.end_types,
+                        },
+                    },
+                    wasm.pc,
+                    sidetable.len(),
+                );
+                stack.innermost_ctrl_mut().pending_branches = pending_branches;
+            }
             // end
             END => {
-                return Ok(());
+                let ctrl = stack.pop_ctrl()?;
+
+                // An `if` without an `else` behaves as if it had an empty else branch, which only
+                // type-checks if the block's parameters and results are the same
+                if ctrl.kind == LabelKind::If && ctrl.start_types != ctrl.end_types {
+                    return Err(Error::IfWithoutMatchingElse);
+                }
+
+                // Branches to the function body target its final `end`, which returns from the
+                // function. All other branches continue right behind the `end` of their block.
+                let target_pc = if stack.ctrl_stack_is_empty() {
+                    instr_pc
+                } else {
+                    wasm.pc
+                };
+                let target_stp = sidetable.len();
+                for branch in ctrl.pending_branches.into_iter().chain(ctrl.if_branch) {
+                    branch.complete(sidetable, target_pc, target_stp);
+                }
+
+                if stack.ctrl_stack_is_empty() {
+                    return Ok(());
+                }
+
+                stack.push_valtypes(&ctrl.end_types);
             }
-            RETURN => {
-                let this_func_ty = &fn_types[type_idx_of_fn[this_function_idx]];
+            // br: [t1* t*] -> [t2*]
+            BR => {
+                let label_idx = wasm.read_var_u32()? as LabelIdx;
+                let label_types = stack.get_label(label_idx)?.label_types().to_vec();
+
+                add_branch_entry(stack, sidetable, instr_pc, label_idx)?;
+                stack.assert_pop_val_types(&label_types)?;
+                stack.make_unreachable();
+            }
+            // br_if: [t* i32] -> [t*]
+            BR_IF => {
+                let label_idx = wasm.read_var_u32()? as LabelIdx;
+                let label_types = stack.get_label(label_idx)?.label_types().to_vec();
+
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                add_branch_entry(stack, sidetable, instr_pc, label_idx)?;
+                stack.assert_pop_val_types(&label_types)?;
+                stack.push_valtypes(&label_types);
+                decisions.push((instr_pc, DecisionKind::BrIf));
+            }
+            // br_table: [t1* t* i32] -> [t2*]
+            BR_TABLE => {
+                let label_idxs =
+                    wasm.read_vec(|wasm| wasm.read_var_u32().map(|idx| idx as LabelIdx))?;
+                let default_label_idx = wasm.read_var_u32()? as LabelIdx;
+                let num_labels = label_idxs.len() as u32;
 
-                if value_stack.len() < this_func_ty.returns.valtypes.len() {
-                    return Err(Error::EndInvalidValueStack);
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+
+                let default_label_types =
+                    stack.get_label(default_label_idx)?.label_types().to_vec();
+                for label_idx in label_idxs {
+                    let label_types = stack.get_label(label_idx)?.label_types().to_vec();
+                    if label_types.len() != default_label_types.len() {
+                        return Err(Error::InvalidBrTableArity);
+                    }
+
+                    stack.assert_val_types_on_top(&label_types)?;
+                    add_branch_entry(stack, sidetable, instr_pc, label_idx)?;
                 }
+                add_branch_entry(stack, sidetable, instr_pc, default_label_idx)?;
 
-                let ret_vals_start = value_stack.len() - this_func_ty.returns.valtypes.len();
-                let _remaining_locals = value_stack.drain(..ret_vals_start);
+                stack.assert_pop_val_types(&default_label_types)?;
+                stack.make_unreachable();
+                decisions.push((instr_pc, DecisionKind::BrTable { num_labels }));
+            }
+            // return: [t1* t*] -> [t2*]
+            RETURN => {
+                let this_func_label_idx = stack.ctrl_stack_len() - 1;
+                let return_types = stack.get_label(this_func_label_idx)?.label_types().to_vec();
 
-                // TODO(george-cosma): a `return Ok(());` should probably be introduced here, but since we don't have
-                // controls flows implemented, the only way to test `return` is to place it at the end of function.
-                // However, an `end` is introduced after it, which is invalid. Compilation for this test case should
-                // probably fail.
+                stack.assert_pop_val_types(&return_types)?;
+                stack.make_unreachable();
             }
             // call [t1*] -> [t2*]
             CALL => {
                 let func_to_call_idx = wasm.read_var_u32()? as FuncIdx;
-                let func_ty = &fn_types[type_idx_of_fn[func_to_call_idx]];
+                let type_idx = type_idx_of_fn
+                    .get(func_to_call_idx)
+                    .ok_or(Error::InvalidFuncIdx(func_to_call_idx))?;
+                let func_ty = &fn_types[*type_idx];
+
+                stack.assert_pop_val_types(&func_ty.params.valtypes)?;
+                stack.push_valtypes(&func_ty.returns.valtypes);
+            }
+            // call_indirect: [t1* i32] -> [t2*]
+            CALL_INDIRECT => {
+                let type_idx = wasm.read_var_u32()? as TypeIdx;
+                let table_idx = wasm.read_var_u32()? as TableIdx;
 
-                for typ in func_ty.params.valtypes.iter().rev() {
-                    assert_pop_value_stack(value_stack, *typ)?;
+                let table = tables
+                    .get(table_idx)
+                    .ok_or(Error::InvalidTableIdx(table_idx))?;
+                if table.et != RefType::FuncRef {
+                    return Err(Error::RefTypeMismatch);
                 }
+                let func_ty = fn_types
+                    .get(type_idx)
+                    .ok_or(Error::InvalidTypeIdx(type_idx))?;
 
-                for typ in func_ty.returns.valtypes.iter() {
-                    value_stack.push(*typ);
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_types(&func_ty.params.valtypes)?;
+                stack.push_valtypes(&func_ty.returns.valtypes);
+            }
+            // drop: [t] -> []
+            DROP => {
+                stack.pop_any()?;
+            }
+            // select: [t t i32] -> [t], where t must be a numeric type
+            SELECT => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                let entry2 = stack.pop_any()?;
+                let entry1 = stack.pop_any()?;
+
+                for entry in [entry1, entry2] {
+                    if let ValidationStackEntry::Val(ty @ ValType::RefType(_)) = entry {
+                        return Err(Error::InvalidSelectOperand(ty));
+                    }
                 }
+
+                // In unreachable code the operands may be of an unknown type
+                let result = match (entry1, entry2) {
+                    (ValidationStackEntry::Val(ty1), ValidationStackEntry::Val(ty2)) => {
+                        if ty1 != ty2 {
+                            return Err(Error::InvalidValueStackType(Some(ty2)));
+                        }
+                        entry1
+                    }
+                    (ValidationStackEntry::Unknown, entry) | (entry, _) => entry,
+                };
+                stack.push_entry(result);
+                decisions.push((instr_pc, DecisionKind::Select));
+            }
+            // select t: [t t i32] -> [t]
+            SELECT_T => {
+                let tys = wasm.read_vec(ValType::read)?;
+                let [ty] = tys[..] else {
+                    return Err(Error::InvalidSelectArity(tys.len()));
+                };
+
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_types(&[ty, ty])?;
+                stack.push_valtype(ty);
+                decisions.push((instr_pc, DecisionKind::Select));
             }
             // local.get: [] -> [t]
             LOCAL_GET => {
                 let local_idx = wasm.read_var_u32()? as LocalIdx;
                 let local_ty = locals.get(local_idx).ok_or(Error::InvalidLocalIdx)?;
-                value_stack.push(*local_ty);
+                stack.push_valtype(*local_ty);
             }
             // local.set [t] -> [0]
             LOCAL_SET => {
                 let local_idx = wasm.read_var_u32()? as LocalIdx;
                 let local_ty = locals.get(local_idx).ok_or(Error::InvalidLocalIdx)?;
-                let popped = value_stack.pop();
-                if popped.as_ref() != Some(local_ty) {
-                    return Err(Error::InvalidValueStackType(popped));
-                }
+                stack.assert_pop_val_type(*local_ty)?;
             }
             // local.set [t] -> [t]
             LOCAL_TEE => {
                 let local_idx = wasm.read_var_u32()? as LocalIdx;
                 let local_ty = locals.get(local_idx).ok_or(Error::InvalidLocalIdx)?;
-                let back = value_stack.last();
-                if back != Some(local_ty) {
-                    return Err(Error::InvalidValueStackType(back.cloned()));
-                }
+                stack.assert_pop_val_type(*local_ty)?;
+                stack.push_valtype(*local_ty);
             }
             // global.get [] -> [t]
             GLOBAL_GET => {
@@ -165,7 +544,7 @@ fn read_instructions(
                     .get(global_idx)
                     .ok_or(Error::InvalidGlobalIdx(global_idx))?;
 
-                value_stack.push(global.ty.ty);
+                stack.push_valtype(global.ty);
             }
             // global.set [t] -> []
             GLOBAL_SET => {
@@ -174,216 +553,733 @@ fn read_instructions(
                     .get(global_idx)
                     .ok_or(Error::InvalidGlobalIdx(global_idx))?;
 
-                if !global.ty.is_mut {
+                if !global.is_mut {
                     return Err(Error::GlobalIsConst);
                 }
 
-                let ty_on_stack = value_stack
-                    .pop()
-                    .ok_or(Error::InvalidValueStackType(None))?;
-
-                if ty_on_stack != global.ty.ty {
-                    return Err(Error::InvalidValueStackType(Some(ty_on_stack)));
-                }
+                stack.assert_pop_val_type(global.ty)?;
             }
-            // i32.load [i32] -> [i32]
-            I32_LOAD => {
-                let _memarg = MemArg::read_unvalidated(wasm);
+            // table.get x: [i32] -> [t]
+            TABLE_GET => {
+                let table = read_table_idx(wasm, tables)?;
 
-                // TODO check correct `memarg.align`
-                // TODO check if memory[0] exists
-
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
-
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.push_valtype(ValType::RefType(table.et));
             }
-            // f32.load [f32] -> [f32]
-            F32_LOAD => {
-                let _memarg = MemArg::read_unvalidated(wasm);
-
-                // Check for I32 because that's the address where we find our value
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+            // table.set x: [i32 t] -> []
+            TABLE_SET => {
+                let table = read_table_idx(wasm, tables)?;
 
-                value_stack.push(ValType::NumType(NumType::F32));
+                stack.assert_pop_val_types(&[
+                    ValType::NumType(NumType::I32),
+                    ValType::RefType(table.et),
+                ])?;
             }
-            // f32.load [f32] -> [f32]
-            F64_LOAD => {
-                let _memarg = MemArg::read_unvalidated(wasm);
+            // t.load [i32] -> [t]
+            I32_LOAD | I64_LOAD | F32_LOAD | F64_LOAD | I32_LOAD8_S | I32_LOAD8_U
+            | I32_LOAD16_S | I32_LOAD16_U | I64_LOAD8_S | I64_LOAD8_U | I64_LOAD16_S
+            | I64_LOAD16_U | I64_LOAD32_S | I64_LOAD32_U => {
+                let (ty, max_align) = match first_instr_byte {
+                    I32_LOAD => (NumType::I32, 2),
+                    I64_LOAD => (NumType::I64, 3),
+                    F32_LOAD => (NumType::F32, 2),
+                    F64_LOAD => (NumType::F64, 3),
+                    I32_LOAD8_S | I32_LOAD8_U => (NumType::I32, 0),
+                    I32_LOAD16_S | I32_LOAD16_U => (NumType::I32, 1),
+                    I64_LOAD8_S | I64_LOAD8_U => (NumType::I64, 0),
+                    I64_LOAD16_S | I64_LOAD16_U => (NumType::I64, 1),
+                    I64_LOAD32_S | I64_LOAD32_U => (NumType::I64, 2),
+                    _ => unreachable!(),
+                };
+                read_memarg(wasm, memories, max_align)?;
 
-                // Check for I32 because that's the address where we find our value
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                // Address
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
 
-                value_stack.push(ValType::NumType(NumType::F64));
+                stack.push_valtype(ValType::NumType(ty));
             }
-            // i32.store [i32] -> [i32]
-            I32_STORE => {
-                let _memarg = MemArg::read_unvalidated(wasm);
-
-                // TODO check correct `memarg.align`
-                // TODO check if memory[0] exists
+            // t.store [i32 t] -> []
+            I32_STORE | I64_STORE | F32_STORE | F64_STORE | I32_STORE8 | I32_STORE16
+            | I64_STORE8 | I64_STORE16 | I64_STORE32 => {
+                let (ty, max_align) = match first_instr_byte {
+                    I32_STORE => (NumType::I32, 2),
+                    I64_STORE => (NumType::I64, 3),
+                    F32_STORE => (NumType::F32, 2),
+                    F64_STORE => (NumType::F64, 3),
+                    I32_STORE8 => (NumType::I32, 0),
+                    I32_STORE16 => (NumType::I32, 1),
+                    I64_STORE8 => (NumType::I64, 0),
+                    I64_STORE16 => (NumType::I64, 1),
+                    I64_STORE32 => (NumType::I64, 2),
+                    _ => unreachable!(),
+                };
+                read_memarg(wasm, memories, max_align)?;
 
                 // Value to store
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(ty))?;
                 // Address
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
             }
-            // f32.store [f32] -> [f32]
-            F32_STORE => {
-                let _memarg = MemArg::read_unvalidated(wasm);
+            // memory.size [] -> [i32]
+            MEMORY_SIZE => {
+                read_mem_idx(wasm, memories)?;
 
-                // Value to store
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F32))?;
-                // Address
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
-            F64_STORE => {
-                let _memarg = MemArg::read_unvalidated(wasm);
+            // memory.grow [i32] -> [i32]
+            MEMORY_GROW => {
+                read_mem_idx(wasm, memories)?;
 
-                // Value to store
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F64))?;
-                // Address
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             // i32.const: [] -> [i32]
             I32_CONST => {
                 let _num = wasm.read_var_i32()?;
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I64_CONST => {
                 let _num = wasm.read_var_i64()?;
-                value_stack.push(ValType::NumType(NumType::I64));
+                stack.push_valtype(ValType::NumType(NumType::I64));
             }
             F32_CONST => {
                 let _num = wasm.read_var_f32()?;
-                value_stack.push(ValType::NumType(NumType::F32));
+                stack.push_valtype(ValType::NumType(NumType::F32));
             }
             F64_CONST => {
                 let _num = wasm.read_var_f64()?;
-                value_stack.push(ValType::NumType(NumType::F64));
+                stack.push_valtype(ValType::NumType(NumType::F64));
             }
             I32_EQZ => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
 
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I32_EQ | I32_NE | I32_LT_S | I32_LT_U | I32_GT_S | I32_GT_U | I32_LE_S | I32_LE_U
             | I32_GE_S | I32_GE_U => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
 
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I64_EQZ => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
 
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I64_EQ | I64_NE | I64_LT_S | I64_LT_U | I64_GT_S | I64_GT_U | I64_LE_S | I64_LE_U
             | I64_GE_S | I64_GE_U => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I64))?;
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
 
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             F32_EQ | F32_NE | F32_LT | F32_GT | F32_LE | F32_GE => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F32))?;
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
 
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             F64_EQ | F64_NE | F64_LT | F64_GT | F64_LE | F64_GE => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F64))?;
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
 
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             F32_ABS | F32_NEG | F32_CEIL | F32_FLOOR | F32_TRUNC | F32_NEAREST | F32_SQRT => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
 
-                value_stack.push(ValType::NumType(NumType::F32));
+                stack.push_valtype(ValType::NumType(NumType::F32));
             }
             F32_ADD | F32_SUB | F32_MUL | F32_DIV | F32_MIN | F32_MAX | F32_COPYSIGN => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F32))?;
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
 
-                value_stack.push(ValType::NumType(NumType::F32));
+                stack.push_valtype(ValType::NumType(NumType::F32));
             }
             F64_ABS | F64_NEG | F64_CEIL | F64_FLOOR | F64_TRUNC | F64_NEAREST | F64_SQRT => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
 
-                value_stack.push(ValType::NumType(NumType::F64));
+                stack.push_valtype(ValType::NumType(NumType::F64));
             }
             F64_ADD | F64_SUB | F64_MUL | F64_DIV | F64_MIN | F64_MAX | F64_COPYSIGN => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F64))?;
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::F64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
 
-                value_stack.push(ValType::NumType(NumType::F64));
+                stack.push_valtype(ValType::NumType(NumType::F64));
             }
             I32_ADD | I32_SUB | I32_MUL | I32_DIV_S | I32_DIV_U | I32_REM_S => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
 
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             // i32.clz: [i32] -> [i32]
             I32_CLZ | I32_CTZ | I32_POPCNT => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
 
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I32_REM_U | I32_AND | I32_OR | I32_XOR | I32_SHL | I32_SHR_S | I32_SHR_U | I32_ROTL
             | I32_ROTR => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
 
-                value_stack.push(ValType::NumType(NumType::I32));
+                stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I64_CLZ | I64_CTZ | I64_POPCNT => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
 
-                value_stack.push(ValType::NumType(NumType::I64));
+                stack.push_valtype(ValType::NumType(NumType::I64));
             }
 
             I64_ADD | I64_SUB | I64_MUL | I64_DIV_S | I64_DIV_U | I64_REM_S | I64_REM_U
             | I64_AND | I64_OR | I64_XOR | I64_SHL | I64_SHR_S | I64_SHR_U | I64_ROTL
             | I64_ROTR => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I64))?;
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
 
-                value_stack.push(ValType::NumType(NumType::I64));
+                stack.push_valtype(ValType::NumType(NumType::I64));
             }
 
-            F32_CONVERT_I32_S | F32_CONVERT_I32_U | F32_REINTERPRET_I32 => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I32))?;
+            I32_WRAP_I64 => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
+
+                stack.push_valtype(ValType::NumType(NumType::I32));
+            }
+            I32_TRUNC_F32_S | I32_TRUNC_F32_U | I32_REINTERPRET_F32 => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
+
+                stack.push_valtype(ValType::NumType(NumType::I32));
+            }
+            I32_TRUNC_F64_S | I32_TRUNC_F64_U => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
+
+                stack.push_valtype(ValType::NumType(NumType::I32));
+            }
+            I64_EXTEND_I32_S | I64_EXTEND_I32_U => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+
+                stack.push_valtype(ValType::NumType(NumType::I64));
+            }
+            I64_TRUNC_F32_S | I64_TRUNC_F32_U => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
 
-                value_stack.push(ValType::NumType(NumType::F32));
+                stack.push_valtype(ValType::NumType(NumType::I64));
             }
+            I64_TRUNC_F64_S | I64_TRUNC_F64_U | I64_REINTERPRET_F64 => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
+
+                stack.push_valtype(ValType::NumType(NumType::I64));
+            }
+            F32_CONVERT_I32_S | F32_CONVERT_I32_U | F32_REINTERPRET_I32 => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
 
+                stack.push_valtype(ValType::NumType(NumType::F32));
+            }
             F32_CONVERT_I64_S | F32_CONVERT_I64_U => {
-                assert_pop_value_stack(value_stack, ValType::NumType(NumType::I64))?;
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
+
+                stack.push_valtype(ValType::NumType(NumType::F32));
+            }
+            F32_DEMOTE_F64 => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
+
+                stack.push_valtype(ValType::NumType(NumType::F32));
+            }
+            F64_CONVERT_I32_S | F64_CONVERT_I32_U => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+
+                stack.push_valtype(ValType::NumType(NumType::F64));
+            }
+            F64_CONVERT_I64_S | F64_CONVERT_I64_U | F64_REINTERPRET_I64 => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
+
+                stack.push_valtype(ValType::NumType(NumType::F64));
+            }
+            F64_PROMOTE_F32 => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
+
+                stack.push_valtype(ValType::NumType(NumType::F64));
+            }
+            I32_EXTEND8_S | I32_EXTEND16_S => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+
+                stack.push_valtype(ValType::NumType(NumType::I32));
+            }
+            I64_EXTEND8_S | I64_EXTEND16_S | I64_EXTEND32_S => {
+                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
+
+                stack.push_valtype(ValType::NumType(NumType::I64));
+            }
+            // ref.null t: [] -> [t]
+            REF_NULL => {
+                let ty = RefType::read(wasm)?;
+                stack.push_valtype(ValType::RefType(ty));
+            }
+            // ref.is_null: [t] -> [i32], where t must be a reference type
+            REF_IS_NULL => {
+                if let ValidationStackEntry::Val(ty @ ValType::NumType(_) | ty @ ValType::VecType) =
+                    stack.pop_any()?
+                {
+                    return Err(Error::InvalidValueStackType(Some(ty)));
+                }
+                stack.push_valtype(ValType::NumType(NumType::I32));
+            }
+            // ref.func x: [] -> [funcref]
+            REF_FUNC => {
+                let func_idx = wasm.read_var_u32()? as FuncIdx;
+                if func_idx >= type_idx_of_fn.len() {
+                    return Err(Error::InvalidFuncIdx(func_idx));
+                }
+                if !declared_func_refs.contains(&func_idx) {
+                    return Err(Error::UndeclaredFuncRef(func_idx));
+                }
+
+                stack.push_valtype(ValType::RefType(RefType::FuncRef));
+            }
+            FC_EXTENSIONS => {
+                let second_instr = wasm.read_var_u32()?;
+                trace!("Read multi-byte instruction {first_instr_byte:#X?} {second_instr}");
+
+                use crate::core::reader::types::opcode::fc_extensions::*;
+                match second_instr {
+                    I32_TRUNC_SAT_F32_S | I32_TRUNC_SAT_F32_U => {
+                        stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
+
+                        stack.push_valtype(ValType::NumType(NumType::I32));
+                    }
+                    I32_TRUNC_SAT_F64_S | I32_TRUNC_SAT_F64_U => {
+                        stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
+
+                        stack.push_valtype(ValType::NumType(NumType::I32));
+                    }
+                    I64_TRUNC_SAT_F32_S | I64_TRUNC_SAT_F32_U => {
+                        stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
+
+                        stack.push_valtype(ValType::NumType(NumType::I64));
+                    }
+                    I64_TRUNC_SAT_F64_S | I64_TRUNC_SAT_F64_U => {
+                        stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
+
+                        stack.push_valtype(ValType::NumType(NumType::I64));
+                    }
+                    // memory.init x: [i32 i32 i32] -> []
+                    MEMORY_INIT => {
+                        read_data_idx(wasm, data_count)?;
+                        read_mem_idx(wasm, memories)?;
+
+                        stack.assert_pop_val_types(&[ValType::NumType(NumType::I32); 3])?;
+                    }
+                    // data.drop x: [] -> []
+                    DATA_DROP => {
+                        read_data_idx(wasm, data_count)?;
+                    }
+                    // memory.copy: [i32 i32 i32] -> []
+                    MEMORY_COPY => {
+                        read_mem_idx(wasm, memories)?;
+                        read_mem_idx(wasm, memories)?;
+
+                        stack.assert_pop_val_types(&[ValType::NumType(NumType::I32); 3])?;
+                    }
+                    // memory.fill: [i32 i32 i32] -> []
+                    MEMORY_FILL => {
+                        read_mem_idx(wasm, memories)?;
+
+                        stack.assert_pop_val_types(&[ValType::NumType(NumType::I32); 3])?;
+                    }
+                    // table.init x y: [i32 i32 i32] -> []
+                    TABLE_INIT => {
+                        let segment = read_elem_idx(wasm, elements)?;
+                        let table = read_table_idx(wasm, tables)?;
+                        if table.et != segment.ty {
+                            return Err(Error::RefTypeMismatch);
+                        }
+
+                        stack.assert_pop_val_types(&[ValType::NumType(NumType::I32); 3])?;
+                    }
+                    // elem.drop x: [] -> []
+                    ELEM_DROP => {
+                        read_elem_idx(wasm, elements)?;
+                    }
+                    // table.copy x y: [i32 i32 i32] -> []
+                    TABLE_COPY => {
+                        let dest_table = read_table_idx(wasm, tables)?;
+                        let src_table = read_table_idx(wasm, tables)?;
+                        if dest_table.et != src_table.et {
+                            return Err(Error::RefTypeMismatch);
+                        }
+
+                        stack.assert_pop_val_types(&[ValType::NumType(NumType::I32); 3])?;
+                    }
+                    // table.grow x: [t i32] -> [i32]
+                    TABLE_GROW => {
+                        let table = read_table_idx(wasm, tables)?;
+
+                        stack.assert_pop_val_types(&[
+                            ValType::RefType(table.et),
+                            ValType::NumType(NumType::I32),
+                        ])?;
+                        stack.push_valtype(ValType::NumType(NumType::I32));
+                    }
+                    // table.size x: [] -> [i32]
+                    TABLE_SIZE => {
+                        read_table_idx(wasm, tables)?;
+
+                        stack.push_valtype(ValType::NumType(NumType::I32));
+                    }
+                    // table.fill x: [i32 t i32] -> []
+                    TABLE_FILL => {
+                        let table = read_table_idx(wasm, tables)?;
+
+                        stack.assert_pop_val_types(&[
+                            ValType::NumType(NumType::I32),
+                            ValType::RefType(table.et),
+                            ValType::NumType(NumType::I32),
+                        ])?;
+                    }
+                    _ => return Err(Error::InvalidMultiByteInstr(first_instr_byte, second_instr)),
+                }
+            }
+            FD_EXTENSIONS => {
+                let second_instr = wasm.read_var_u32()?;
+                trace!("Read multi-byte instruction {first_instr_byte:#X?} {second_instr}");
+
+                use crate::core::reader::types::opcode::fd_extensions::*;
+                match second_instr {
+                    // v128.load: [i32] -> [v128]
+                    V128_LOAD | V128_LOAD8X8_S | V128_LOAD8X8_U | V128_LOAD16X4_S
+                    | V128_LOAD16X4_U | V128_LOAD32X2_S | V128_LOAD32X2_U | V128_LOAD8_SPLAT
+                    | V128_LOAD16_SPLAT | V128_LOAD32_SPLAT | V128_LOAD64_SPLAT
+                    | V128_LOAD32_ZERO | V128_LOAD64_ZERO => {
+                        let max_align = match second_instr {
+                            V128_LOAD => 4,
+                            V128_LOAD8_SPLAT => 0,
+                            V128_LOAD16_SPLAT => 1,
+                            V128_LOAD32_SPLAT | V128_LOAD32_ZERO => 2,
+                            _ => 3,
+                        };
+                        read_memarg(wasm, memories, max_align)?;
+
+                        stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+
+                        stack.push_valtype(ValType::VecType);
+                    }
+                    // v128.store: [i32 v128] -> []
+                    V128_STORE => {
+                        read_memarg(wasm, memories, 4)?;
+
+                        stack.assert_pop_val_types(&[
+                            ValType::NumType(NumType::I32),
+                            ValType::VecType,
+                        ])?;
+                    }
+                    // v128.loadN_lane: [i32 v128] -> [v128]
+                    // v128.storeN_lane: [i32 v128] -> []
+                    V128_LOAD8_LANE | V128_LOAD16_LANE | V128_LOAD32_LANE | V128_LOAD64_LANE
+                    | V128_STORE8_LANE | V128_STORE16_LANE | V128_STORE32_LANE
+                    | V128_STORE64_LANE => {
+                        let max_align = match second_instr {
+                            V128_LOAD8_LANE | V128_STORE8_LANE => 0,
+                            V128_LOAD16_LANE | V128_STORE16_LANE => 1,
+                            V128_LOAD32_LANE | V128_STORE32_LANE => 2,
+                            _ => 3,
+                        };
+                        read_memarg(wasm, memories, max_align)?;
+                        read_lane_idx(wasm, 16 >> max_align)?;
+
+                        stack.assert_pop_val_types(&[
+                            ValType::NumType(NumType::I32),
+                            ValType::VecType,
+                        ])?;
+
+                        let is_load = matches!(
+                            second_instr,
+                            V128_LOAD8_LANE
+                                | V128_LOAD16_LANE
+                                | V128_LOAD32_LANE
+                                | V128_LOAD64_LANE
+                        );
+                        if is_load {
+                            stack.push_valtype(ValType::VecType);
+                        }
+                    }
+                    // v128.const: [] -> [v128]
+                    V128_CONST => {
+                        wasm.strip_bytes::<16>()?;
+
+                        stack.push_valtype(ValType::VecType);
+                    }
+                    // i8x16.shuffle: [v128 v128] -> [v128]
+                    I8X16_SHUFFLE => {
+                        for _ in 0..16 {
+                            read_lane_idx(wasm, 32)?;
+                        }
+
+                        stack.assert_pop_val_types(&[ValType::VecType; 2])?;
+
+                        stack.push_valtype(ValType::VecType);
+                    }
+                    // shape.splat: [t] -> [v128]
+                    I8X16_SPLAT | I16X8_SPLAT | I32X4_SPLAT | I64X2_SPLAT | F32X4_SPLAT
+                    | F64X2_SPLAT => {
+                        let ty = match second_instr {
+                            I8X16_SPLAT | I16X8_SPLAT | I32X4_SPLAT => NumType::I32,
+                            I64X2_SPLAT => NumType::I64,
+                            F32X4_SPLAT => NumType::F32,
+                            _ => NumType::F64,
+                        };
+                        stack.assert_pop_val_type(ValType::NumType(ty))?;
+
+                        stack.push_valtype(ValType::VecType);
+                    }
+                    // shape.extract_lane: [v128] -> [t]
+                    // shape.replace_lane: [v128 t] -> [v128]
+                    I8X16_EXTRACT_LANE_S | I8X16_EXTRACT_LANE_U | I8X16_REPLACE_LANE
+                    | I16X8_EXTRACT_LANE_S | I16X8_EXTRACT_LANE_U | I16X8_REPLACE_LANE
+                    | I32X4_EXTRACT_LANE | I32X4_REPLACE_LANE | I64X2_EXTRACT_LANE
+                    | I64X2_REPLACE_LANE | F32X4_EXTRACT_LANE | F32X4_REPLACE_LANE
+                    | F64X2_EXTRACT_LANE | F64X2_REPLACE_LANE => {
+                        let (ty, num_lanes) =
+                            match second_instr {
+                                I8X16_EXTRACT_LANE_S | I8X16_EXTRACT_LANE_U
+                                | I8X16_REPLACE_LANE => (NumType::I32, 16),
+                                I16X8_EXTRACT_LANE_S | I16X8_EXTRACT_LANE_U
+                                | I16X8_REPLACE_LANE => (NumType::I32, 8),
+                                I32X4_EXTRACT_LANE | I32X4_REPLACE_LANE => (NumType::I32, 4),
+                                I64X2_EXTRACT_LANE | I64X2_REPLACE_LANE => (NumType::I64, 2),
+                                F32X4_EXTRACT_LANE | F32X4_REPLACE_LANE => (NumType::F32, 4),
+                                _ => (NumType::F64, 2),
+                            };
+                        read_lane_idx(wasm, num_lanes)?;
 
-                value_stack.push(ValType::NumType(NumType::F32));
+                        let is_replace = matches!(
+                            second_instr,
+                            I8X16_REPLACE_LANE
+                                | I16X8_REPLACE_LANE
+                                | I32X4_REPLACE_LANE
+                                | I64X2_REPLACE_LANE
+                                | F32X4_REPLACE_LANE
+                                | F64X2_REPLACE_LANE
+                        );
+                        if is_replace {
+                            stack
+                                .assert_pop_val_types(&[ValType::VecType, ValType::NumType(ty)])?;
+                            stack.push_valtype(ValType::VecType);
+                        } else {
+                            stack.assert_pop_val_type(ValType::VecType)?;
+                            stack.push_valtype(ValType::NumType(ty));
+                        }
+                    }
+                    // vunop: [v128] -> [v128]
+                    V128_NOT
+                    | I8X16_ABS
+                    | I8X16_NEG
+                    | I8X16_POPCNT
+                    | I16X8_ABS
+                    | I16X8_NEG
+                    | I32X4_ABS
+                    | I32X4_NEG
+                    | I64X2_ABS
+                    | I64X2_NEG
+                    | F32X4_ABS
+                    | F32X4_NEG
+                    | F32X4_SQRT
+                    | F32X4_CEIL
+                    | F32X4_FLOOR
+                    | F32X4_TRUNC
+                    | F32X4_NEAREST
+                    | F64X2_ABS
+                    | F64X2_NEG
+                    | F64X2_SQRT
+                    | F64X2_CEIL
+                    | F64X2_FLOOR
+                    | F64X2_TRUNC
+                    | F64X2_NEAREST
+                    | I16X8_EXTADD_PAIRWISE_I8X16_S
+                    | I16X8_EXTADD_PAIRWISE_I8X16_U
+                    | I32X4_EXTADD_PAIRWISE_I16X8_S
+                    | I32X4_EXTADD_PAIRWISE_I16X8_U
+                    | I16X8_EXTEND_LOW_I8X16_S
+                    | I16X8_EXTEND_HIGH_I8X16_S
+                    | I16X8_EXTEND_LOW_I8X16_U
+                    | I16X8_EXTEND_HIGH_I8X16_U
+                    | I32X4_EXTEND_LOW_I16X8_S
+                    | I32X4_EXTEND_HIGH_I16X8_S
+                    | I32X4_EXTEND_LOW_I16X8_U
+                    | I32X4_EXTEND_HIGH_I16X8_U
+                    | I64X2_EXTEND_LOW_I32X4_S
+                    | I64X2_EXTEND_HIGH_I32X4_S
+                    | I64X2_EXTEND_LOW_I32X4_U
+                    | I64X2_EXTEND_HIGH_I32X4_U
+                    | I32X4_TRUNC_SAT_F32X4_S
+                    | I32X4_TRUNC_SAT_F32X4_U
+                    | I32X4_TRUNC_SAT_F64X2_S_ZERO
+                    | I32X4_TRUNC_SAT_F64X2_U_ZERO
+                    | F32X4_CONVERT_I32X4_S
+                    | F32X4_CONVERT_I32X4_U
+                    | F64X2_CONVERT_LOW_I32X4_S
+                    | F64X2_CONVERT_LOW_I32X4_U
+                    | F32X4_DEMOTE_F64X2_ZERO
+                    | F64X2_PROMOTE_LOW_F32X4 => {
+                        stack.assert_pop_val_type(ValType::VecType)?;
+
+                        stack.push_valtype(ValType::VecType);
+                    }
+                    // vbinop: [v128 v128] -> [v128]
+                    V128_AND
+                    | V128_ANDNOT
+                    | V128_OR
+                    | V128_XOR
+                    | I8X16_SWIZZLE
+                    | I8X16_EQ
+                    | I8X16_NE
+                    | I8X16_LT_S
+                    | I8X16_LT_U
+                    | I8X16_GT_S
+                    | I8X16_GT_U
+                    | I8X16_LE_S
+                    | I8X16_LE_U
+                    | I8X16_GE_S
+                    | I8X16_GE_U
+                    | I16X8_EQ
+                    | I16X8_NE
+                    | I16X8_LT_S
+                    | I16X8_LT_U
+                    | I16X8_GT_S
+                    | I16X8_GT_U
+                    | I16X8_LE_S
+                    | I16X8_LE_U
+                    | I16X8_GE_S
+                    | I16X8_GE_U
+                    | I32X4_EQ
+                    | I32X4_NE
+                    | I32X4_LT_S
+                    | I32X4_LT_U
+                    | I32X4_GT_S
+                    | I32X4_GT_U
+                    | I32X4_LE_S
+                    | I32X4_LE_U
+                    | I32X4_GE_S
+                    | I32X4_GE_U
+                    | I64X2_EQ
+                    | I64X2_NE
+                    | I64X2_LT_S
+                    | I64X2_GT_S
+                    | I64X2_LE_S
+                    | I64X2_GE_S
+                    | F32X4_EQ
+                    | F32X4_NE
+                    | F32X4_LT
+                    | F32X4_GT
+                    | F32X4_LE
+                    | F32X4_GE
+                    | F64X2_EQ
+                    | F64X2_NE
+                    | F64X2_LT
+                    | F64X2_GT
+                    | F64X2_LE
+                    | F64X2_GE
+                    | I8X16_ADD
+                    | I8X16_ADD_SAT_S
+                    | I8X16_ADD_SAT_U
+                    | I8X16_SUB
+                    | I8X16_SUB_SAT_S
+                    | I8X16_SUB_SAT_U
+                    | I8X16_MIN_S
+                    | I8X16_MIN_U
+                    | I8X16_MAX_S
+                    | I8X16_MAX_U
+                    | I8X16_AVGR_U
+                    | I16X8_ADD
+                    | I16X8_ADD_SAT_S
+                    | I16X8_ADD_SAT_U
+                    | I16X8_SUB
+                    | I16X8_SUB_SAT_S
+                    | I16X8_SUB_SAT_U
+                    | I16X8_MUL
+                    | I16X8_MIN_S
+                    | I16X8_MIN_U
+                    | I16X8_MAX_S
+                    | I16X8_MAX_U
+                    | I16X8_AVGR_U
+                    | I16X8_Q15MULR_SAT_S
+                    | I32X4_ADD
+                    | I32X4_SUB
+                    | I32X4_MUL
+                    | I32X4_MIN_S
+                    | I32X4_MIN_U
+                    | I32X4_MAX_S
+                    | I32X4_MAX_U
+                    | I32X4_DOT_I16X8_S
+                    | I64X2_ADD
+                    | I64X2_SUB
+                    | I64X2_MUL
+                    | I8X16_NARROW_I16X8_S
+                    | I8X16_NARROW_I16X8_U
+                    | I16X8_NARROW_I32X4_S
+                    | I16X8_NARROW_I32X4_U
+                    | I16X8_EXTMUL_LOW_I8X16_S
+                    | I16X8_EXTMUL_HIGH_I8X16_S
+                    | I16X8_EXTMUL_LOW_I8X16_U
+                    | I16X8_EXTMUL_HIGH_I8X16_U
+                    | I32X4_EXTMUL_LOW_I16X8_S
+                    | I32X4_EXTMUL_HIGH_I16X8_S
+                    | I32X4_EXTMUL_LOW_I16X8_U
+                    | I32X4_EXTMUL_HIGH_I16X8_U
+                    | I64X2_EXTMUL_LOW_I32X4_S
+                    | I64X2_EXTMUL_HIGH_I32X4_S
+                    | I64X2_EXTMUL_LOW_I32X4_U
+                    | I64X2_EXTMUL_HIGH_I32X4_U
+                    | F32X4_ADD
+                    | F32X4_SUB
+                    | F32X4_MUL
+                    | F32X4_DIV
+                    | F32X4_MIN
+                    | F32X4_MAX
+                    | F32X4_PMIN
+                    | F32X4_PMAX
+                    | F64X2_ADD
+                    | F64X2_SUB
+                    | F64X2_MUL
+                    | F64X2_DIV
+                    | F64X2_MIN
+                    | F64X2_MAX
+                    | F64X2_PMIN
+                    | F64X2_PMAX => {
+                        stack.assert_pop_val_types(&[ValType::VecType; 2])?;
+
+                        stack.push_valtype(ValType::VecType);
+                    }
+                    // v128.bitselect: [v128 v128 v128] -> [v128]
+                    V128_BITSELECT => {
+                        stack.assert_pop_val_types(&[ValType::VecType; 3])?;
+
+                        stack.push_valtype(ValType::VecType);
+                    }
+                    // vtestop, bitmask: [v128] -> [i32]
+                    V128_ANY_TRUE | I8X16_ALL_TRUE | I8X16_BITMASK | I16X8_ALL_TRUE
+                    | I16X8_BITMASK | I32X4_ALL_TRUE | I32X4_BITMASK | I64X2_ALL_TRUE
+                    | I64X2_BITMASK => {
+                        stack.assert_pop_val_type(ValType::VecType)?;
+
+                        stack.push_valtype(ValType::NumType(NumType::I32));
+                    }
+                    // vshiftop: [v128 i32] -> [v128]
+                    I8X16_SHL | I8X16_SHR_S | I8X16_SHR_U | I16X8_SHL | I16X8_SHR_S
+                    | I16X8_SHR_U | I32X4_SHL | I32X4_SHR_S | I32X4_SHR_U | I64X2_SHL
+                    | I64X2_SHR_S | I64X2_SHR_U => {
+                        stack.assert_pop_val_types(&[
+                            ValType::VecType,
+                            ValType::NumType(NumType::I32),
+                        ])?;
+
+                        stack.push_valtype(ValType::VecType);
+                    }
+                    _ => return Err(Error::InvalidMultiByteInstr(first_instr_byte, second_instr)),
+                }
             }
             _ => return Err(Error::InvalidInstr(first_instr_byte)),
         }
     }
 }
-
-fn validate_value_stack<F>(return_ty: ResultType, f: F) -> Result<()>
-where
-    F: FnOnce(&mut Vec<ValType>) -> Result<()>,
-{
-    let mut value_stack: Vec<ValType> = Vec::new();
-
-    f(&mut value_stack)?;
-
-    // TODO also check here if correct order
-    if value_stack != return_ty.valtypes {
-        error!(
-            "Expected types {:?} on stack, got {:?}",
-            return_ty.valtypes, value_stack
-        );
-        return Err(Error::EndInvalidValueStack);
-    }
-    Ok(())
-}
@@ -1,37 +1,64 @@
+use alloc::collections::BTreeSet;
 use alloc::vec::Vec;
 
 use crate::core::indices::{FuncIdx, TypeIdx};
 use crate::core::reader::section_header::{SectionHeader, SectionTy};
 use crate::core::reader::span::Span;
-use crate::core::reader::types::export::Export;
-use crate::core::reader::types::global::Global;
-use crate::core::reader::types::import::Import;
+use crate::core::reader::types::const_expr::{
+    const_expr_func_ref, read_const_expr, ConstExprContext,
+};
+use crate::core::reader::types::data::{DataMode, DataSegment};
+use crate::core::reader::types::element::{ElemItems, ElemMode, ElemSegment};
+use crate::core::reader::types::export::{Export, ExportDesc};
+use crate::core::reader::types::global::{Global, GlobalType};
+use crate::core::reader::types::import::{Import, ImportDesc};
 use crate::core::reader::types::{FuncType, MemType, TableType};
 use crate::core::reader::{WasmReadable, WasmReader};
+use crate::core::sidetable::Sidetable;
+use crate::execution::coverage::Decision;
 use crate::{Error, Result};
 
 pub(crate) mod code;
+pub(crate) mod validation_stack;
 
 /// Information collected from validating a module.
 /// This can be used to create a [crate::RuntimeInstance].
 pub struct ValidationInfo<'bytecode> {
     pub(crate) wasm: &'bytecode [u8],
     pub(crate) types: Vec<FuncType>,
-    #[allow(dead_code)]
     pub(crate) imports: Vec<Import>,
+    /// The types of the functions defined in this module, excluding imported functions
     pub(crate) functions: Vec<TypeIdx>,
-    #[allow(dead_code)]
     pub(crate) tables: Vec<TableType>,
     pub(crate) memories: Vec<MemType>,
+    /// The globals defined in this module, excluding imported globals
     pub(crate) globals: Vec<Global>,
     #[allow(dead_code)]
     pub(crate) exports: Vec<Export>,
-    pub(crate) func_blocks: Vec<Span>,
+    pub(crate) elements: Vec<ElemSegment>,
+    pub(crate) data: Vec<DataSegment>,
+    /// The number of data segments, if announced in the data count section
+    #[allow(dead_code)]
+    pub(crate) data_count: Option<u32>,
+    /// The code of each function, together with the index of its first entry in the side-table
+    pub(crate) func_blocks_stps: Vec<(Span, usize)>,
+    pub(crate) sidetable: Sidetable,
+    /// The decisions in the code of all functions, ordered by function and offset
+    pub(crate) decisions: Vec<Decision>,
     /// The start function which is automatically executed during instantiation
     pub(crate) start: Option<FuncIdx>,
 }
 
-pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
+pub fn validate(wasm: &[u8]) -> Result<ValidationInfo<'_>> {
+    validate_with_probe(wasm, None)
+}
+
+/// Validate a module, while recording the state of validation at the instructions that `probe` is
+/// interested in
+pub(crate) fn validate_with_probe<'a>(
+    wasm: &'a [u8],
+    probe: Option<&mut code::InstructionProbe>,
+) -> Result<ValidationInfo<'a>> {
     let mut wasm = WasmReader::new(wasm);
     trace!("Starting validation of bytecode");
 
@@ -76,6 +103,16 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
     })?
     .unwrap_or_default();
 
+    // Imported functions precede the module's own functions in the function index space
+    let imported_functions = imports.iter().filter_map(|import| match import.desc {
+        ImportDesc::Func(type_idx) => Some(type_idx),
+        _ => None,
+    });
+    let all_functions = imported_functions
+        .chain(functions.iter().copied())
+        .collect::<Vec<TypeIdx>>();
+    let num_imported_functions = all_functions.len() - functions.len();
+
     while (skip_section(&mut wasm, &mut header)?).is_some() {}
 
     let tables = handle_section(&mut wasm, &mut header, SectionTy::Table, |wasm, _| {
@@ -95,16 +132,34 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
 
     while (skip_section(&mut wasm, &mut header)?).is_some() {}
 
+    let imported_globals = imports
+        .iter()
+        .filter_map(|import| match import.desc {
+            ImportDesc::Global(ty) => Some(ty),
+            _ => None,
+        })
+        .collect::<Vec<GlobalType>>();
+    let const_expr_ctx = ConstExprContext {
+        imported_globals: &imported_globals,
+        num_funcs: all_functions.len(),
+    };
+
     let globals = handle_section(&mut wasm, &mut header, SectionTy::Global, |wasm, _| {
         wasm.read_vec(|wasm| {
-            // TODO validate instructions in `global.init_expr`. Furthermore all of these instructions need to be constant.
-            //  See https://webassembly.github.io/spec/core/valid/instructions.html#valid-constant
-            //  Maybe we can also execute constant expressions right here so they do not even exist in the runtime environment. <-- Needs further research to check if this is even possible
-            Global::read(wasm)
+            let ty = GlobalType::read(wasm)?;
+            let init_expr = read_const_expr(wasm, &const_expr_ctx, ty.ty)?;
+            Ok(Global { ty, init_expr })
         })
     })?
     .unwrap_or_default();
 
+    // Imported globals precede the module's own globals in the global index space
+    let all_globals = imported_globals
+        .iter()
+        .copied()
+        .chain(globals.iter().map(|global| global.ty))
+        .collect::<Vec<GlobalType>>();
+
     while (skip_section(&mut wasm, &mut header)?).is_some() {}
 
     let exports = handle_section(&mut wasm, &mut header, SectionTy::Export, |wasm, _| {
@@ -120,30 +175,71 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
 
     while (skip_section(&mut wasm, &mut header)?).is_some() {}
 
-    let _: Option<()> = handle_section(&mut wasm, &mut header, SectionTy::Element, |_, _| {
-        todo!("element section not yet supported")
-    })?;
+    let elements = handle_section(&mut wasm, &mut header, SectionTy::Element, |wasm, _| {
+        wasm.read_vec(|wasm| {
+            let segment = ElemSegment::read(wasm, &const_expr_ctx)?;
+            validate_elem_segment(&segment, &tables)?;
+            Ok(segment)
+        })
+    })?
+    .unwrap_or_default();
+
+    let declared_func_refs = declared_func_refs(&wasm, &globals, &exports, &elements);
 
     while (skip_section(&mut wasm, &mut header)?).is_some() {}
 
-    let _: Option<()> = handle_section(&mut wasm, &mut header, SectionTy::DataCount, |_, _| {
-        todo!("data count section not yet supported")
+    let data_count = handle_section(&mut wasm, &mut header, SectionTy::DataCount, |wasm, _| {
+        wasm.read_var_u32()
     })?;
 
     while (skip_section(&mut wasm, &mut header)?).is_some() {}
 
-    let func_blocks = handle_section(&mut wasm, &mut header, SectionTy::Code, |wasm, h| {
-        code::validate_code_section(wasm, h, &types, &functions, &globals)
+    let mut sidetable = Sidetable::new();
+    let mut decisions = Vec::new();
+    let func_blocks_stps = handle_section(&mut wasm, &mut header, SectionTy::Code, |wasm, h| {
+        code::validate_code_section(
+            wasm,
+            h,
+            &types,
+            &all_functions,
+            num_imported_functions,
+            &tables,
+            &memories,
+            &all_globals,
+            &elements,
+            data_count,
+            &declared_func_refs,
+            &mut sidetable,
+            &mut decisions,
+            probe,
+        )
     })?
     .unwrap_or_default();
 
-    assert_eq!(func_blocks.len(), functions.len(), "these should be equal"); // TODO check if this is in the spec
+    assert_eq!(
+        func_blocks_stps.len(),
+        functions.len(),
+        "these should be equal"
+    ); // TODO check if this is in the spec
 
     while (skip_section(&mut wasm, &mut header)?).is_some() {}
 
-    let _: Option<()> = handle_section(&mut wasm, &mut header, SectionTy::Data, |_, _| {
-        todo!("data section not yet supported")
-    })?;
+    let data = handle_section(&mut wasm, &mut header, SectionTy::Data, |wasm, _| {
+        wasm.read_vec(|wasm| {
+            let segment = DataSegment::read(wasm, &const_expr_ctx)?;
+            if let DataMode::Active { mem_idx, .. } = segment.mode {
+                if mem_idx >= memories.len() {
+                    return Err(Error::InvalidMemIdx(mem_idx));
+                }
+            }
+            Ok(segment)
+        })
+    })?
+    .unwrap_or_default();
+
+    if data_count.is_some_and(|count| count as usize != data.len()) {
+        return Err(Error::DataCountMismatch);
+    }
 
     while (skip_section(&mut wasm, &mut header)?).is_some() {}
 
@@ -162,11 +258,57 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
         memories,
         globals,
         exports,
-        func_blocks,
+        elements,
+        data,
+        data_count,
+        func_blocks_stps,
+        sidetable,
+        decisions,
         start,
     })
 }
 
+fn validate_elem_segment(segment: &ElemSegment, tables: &[TableType]) -> Result<()> {
+    if let ElemMode::Active { table_idx, .. } = segment.mode {
+        let table = tables
+            .get(table_idx)
+            .ok_or(Error::InvalidTableIdx(table_idx))?;
+        if table.et != segment.ty {
+            return Err(Error::RefTypeMismatch);
+        }
+    }
+
+    Ok(())
+}
+
+/// Collect the functions which are referenced outside of function bodies, which are the only ones
+/// that `ref.func` instructions in function bodies may refer to
+///
+/// See: <https://webassembly.github.io/spec/core/valid/modules.html#valid-module>
+fn declared_func_refs(
+    wasm: &WasmReader,
+    globals: &[Global],
+    exports: &[Export],
+    elements: &[ElemSegment],
+) -> BTreeSet<FuncIdx> {
+    let in_globals = globals
+        .iter()
+        .filter_map(|global| const_expr_func_ref(wasm, global.init_expr));
+    let in_exports = exports.iter().filter_map(|export| match export.desc {
+        ExportDesc::FuncIdx(func_idx) => Some(func_idx),
+        _ => None,
+    });
+    let in_elements = elements.iter().flat_map(|segment| match &segment.items {
+        ElemItems::Funcs(funcs) => funcs.clone(),
+        ElemItems::Exprs(exprs) => exprs
+            .iter()
+            .filter_map(|expr| const_expr_func_ref(wasm, *expr))
+            .collect(),
+    });
+
+    in_globals.chain(in_exports).chain(in_elements).collect()
+}
+
 fn read_next_header(wasm: &mut WasmReader, header: &mut Option<SectionHeader>) -> Result<()> {
     if header.is_none() && !wasm.remaining_bytes().is_empty() {
         *header = Some(SectionHeader::read(wasm)?);
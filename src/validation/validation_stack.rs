@@ -0,0 +1,267 @@
+//! The operand and control stacks used during validation of a function body.
+//!
+//! This follows the validation algorithm from the appendix of the WASM specification.
+//! See: <https://webassembly.github.io/spec/core/appendix/algorithm.html>
+
+use alloc::vec::Vec;
+
+use crate::core::indices::LabelIdx;
+use crate::core::reader::types::{FuncType, ResultType, ValType};
+use crate::core::sidetable::IncompleteSidetableEntry;
+use crate::{Error, Result};
+
+/// The structured control instruction that a [`CtrlStackEntry`] belongs to
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) enum LabelKind {
+    /// Either a `block` instruction or the implicit block around the function body
+    Block,
+    Loop,
+    If,
+    Else,
+}
+
+/// An entry on the control stack, i.e. one label during validation
+#[derive(Debug)]
+pub(super) struct CtrlStackEntry {
+    pub kind: LabelKind,
+    /// Types of the values this block consumes
+    pub start_types: Vec<ValType>,
+    /// Types of the values this block produces
+    pub end_types: Vec<ValType>,
+    /// Height of the operand stack when this block was entered
+    pub height: usize,
+    /// Whether the remainder of this block is unreachable, i.e. the operand stack is polymorphic
+    pub unreachable: bool,
+    /// PC of the first instruction in this block's body, which is the target of branches to a `loop`
+    pub start_pc: usize,
+    /// Side-table pointer at the first instruction in this block's body
+    pub start_stp: usize,
+    /// Branches to the end of this block, which are completed once its `end` is read
+    pub pending_branches: Vec<IncompleteSidetableEntry>,
+    /// The branch of an `if` instruction to its `else` branch (or `end`), if this block is an `if`
+    pub if_branch: Option<IncompleteSidetableEntry>,
+}
+
+impl CtrlStackEntry {
+    /// Types of the values that a branch to this label must provide
+    pub fn label_types(&self) -> &[ValType] {
+        match self.kind {
+            LabelKind::Loop => &self.start_types,
+            _ => &self.end_types,
+        }
+    }
+}
+
+/// An operand type during validation. After an unconditional branch the operand stack becomes
+/// polymorphic, from then on popped operands may be of an unknown type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) enum ValidationStackEntry {
+    Val(ValType),
+    Unknown,
+}
+
+pub(super) struct ValidationStack {
+    values: Vec<ValidationStackEntry>,
+    ctrl_stack: Vec<CtrlStackEntry>,
+}
+
+impl ValidationStack {
+    /// Create a new stack, with the implicit block around a function body of the given type as
+    /// its only label
+    pub fn new_for_func(func_ty: &FuncType, start_pc: usize, start_stp: usize) -> Self {
+        let mut stack = Self {
+            values: Vec::new(),
+            ctrl_stack: Vec::new(),
+        };
+        stack.push_ctrl(
+            LabelKind::Block,
+            FuncType {
+                params: ResultType {
+                    valtypes: Vec::new(),
+                },
+                returns: func_ty.returns.clone(),
+            },
+            start_pc,
+            start_stp,
+        );
+
+        stack
+    }
+
+    pub fn push_valtype(&mut self, ty: ValType) {
+        self.values.push(ValidationStackEntry::Val(ty));
+    }
+
+    /// Push an operand, which may be of an unknown type
+    pub fn push_entry(&mut self, entry: ValidationStackEntry) {
+        self.values.push(entry);
+    }
+
+    pub fn push_valtypes(&mut self, tys: &[ValType]) {
+        self.values
+            .extend(tys.iter().map(|ty| ValidationStackEntry::Val(*ty)));
+    }
+
+    /// Pop an operand, which may be of an unknown type if the current block is unreachable
+    pub fn pop_any(&mut self) -> Result<ValidationStackEntry> {
+        let ctrl = self.ctrl_stack.last().ok_or(Error::EndInvalidValueStack)?;
+
+        if self.values.len() == ctrl.height {
+            return if ctrl.unreachable {
+                Ok(ValidationStackEntry::Unknown)
+            } else {
+                Err(Error::InvalidValueStackType(None))
+            };
+        }
+
+        Ok(self.values.pop().unwrap())
+    }
+
+    /// Pop an operand and assert that it is of type `expected_ty`
+    pub fn assert_pop_val_type(&mut self, expected_ty: ValType) -> Result<()> {
+        match self.pop_any()? {
+            ValidationStackEntry::Unknown => Ok(()),
+            ValidationStackEntry::Val(ty) if ty == expected_ty => Ok(()),
+            ValidationStackEntry::Val(ty) => Err(Error::InvalidValueStackType(Some(ty))),
+        }
+    }
+
+    /// Pop operands of the given types, with the last type being expected at the top of the stack
+    pub fn assert_pop_val_types(&mut self, expected_tys: &[ValType]) -> Result<()> {
+        expected_tys
+            .iter()
+            .rev()
+            .try_for_each(|ty| self.assert_pop_val_type(*ty))
+    }
+
+    /// Assert that the topmost operands are of the given types, without removing them
+    pub fn assert_val_types_on_top(&mut self, expected_tys: &[ValType]) -> Result<()> {
+        let mut popped = Vec::with_capacity(expected_tys.len());
+        for ty in expected_tys.iter().rev() {
+            let entry = self.pop_any()?;
+            if let ValidationStackEntry::Val(actual_ty) = entry {
+                if actual_ty != *ty {
+                    return Err(Error::InvalidValueStackType(Some(actual_ty)));
+                }
+            }
+            popped.push(entry);
+        }
+
+        self.values.extend(popped.into_iter().rev());
+        Ok(())
+    }
+
+    /// Enter a new block of the given kind and type, pushing its parameters to the operand stack
+    ///
+    /// The parameters must already have been popped by the caller. `start_pc` and `start_stp`
+    /// describe where the block's body begins.
+    pub fn push_ctrl(
+        &mut self,
+        kind: LabelKind,
+        block_ty: FuncType,
+        start_pc: usize,
+        start_stp: usize,
+    ) {
+        let height = self.values.len();
+        self.push_valtypes(&block_ty.params.valtypes);
+
+        self.ctrl_stack.push(CtrlStackEntry {
+            kind,
+            start_types: block_ty.params.valtypes,
+            end_types: block_ty.returns.valtypes,
+            height,
+            unreachable: false,
+            start_pc,
+            start_stp,
+            pending_branches: Vec::new(),
+            if_branch: None,
+        });
+    }
+
+    /// Leave the innermost block, asserting that exactly its results are on the operand stack
+    pub fn pop_ctrl(&mut self) -> Result<CtrlStackEntry> {
+        let end_types = self
+            .ctrl_stack
+            .last()
+            .ok_or(Error::EndInvalidValueStack)?
+            .end_types
+            .clone();
+
+        self.assert_pop_val_types(&end_types)
+            .map_err(|_| Error::EndInvalidValueStack)?;
+
+        let ctrl = self.ctrl_stack.pop().unwrap();
+        if self.values.len() != ctrl.height {
+            error!(
+                "Expected types {:?} on stack, got {:?}",
+                ctrl.end_types,
+                &self.values[ctrl.height..]
+            );
+            return Err(Error::EndInvalidValueStack);
+        }
+
+        Ok(ctrl)
+    }
+
+    /// Get the label `label_idx` levels outwards from the innermost block
+    pub fn get_label(&self, label_idx: LabelIdx) -> Result<&CtrlStackEntry> {
+        self.ctrl_stack
+            .len()
+            .checked_sub(label_idx + 1)
+            .and_then(|idx| self.ctrl_stack.get(idx))
+            .ok_or(Error::InvalidLabelIdx(label_idx))
+    }
+
+    /// Get the label `label_idx` levels outwards from the innermost block as a mutable reference
+    pub fn get_label_mut(&mut self, label_idx: LabelIdx) -> Result<&mut CtrlStackEntry> {
+        self.ctrl_stack
+            .len()
+            .checked_sub(label_idx + 1)
+            .and_then(|idx| self.ctrl_stack.get_mut(idx))
+            .ok_or(Error::InvalidLabelIdx(label_idx))
+    }
+
+    /// Get the innermost block as a mutable reference
+    pub fn innermost_ctrl_mut(&mut self) -> &mut CtrlStackEntry {
+        self.ctrl_stack.last_mut().unwrap()
+    }
+
+    /// Returns the current height of the operand stack
+    pub fn values_len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Mark the remainder of the innermost block as unreachable, making the operand stack
+    /// polymorphic
+    pub fn make_unreachable(&mut self) {
+        let ctrl = self.ctrl_stack.last_mut().unwrap();
+        self.values.truncate(ctrl.height);
+        ctrl.unreachable = true;
+    }
+
+    /// Returns the types of all operands of the function, or `None` if the current instruction is
+    /// unreachable and the operand stack may thus be polymorphic
+    pub fn reachable_val_types(&self) -> Option<Vec<ValType>> {
+        if self.ctrl_stack.iter().any(|ctrl| ctrl.unreachable) {
+            return None;
+        }
+
+        self.values
+            .iter()
+            .map(|entry| match entry {
+                ValidationStackEntry::Val(ty) => Some(*ty),
+                ValidationStackEntry::Unknown => None,
+            })
+            .collect()
+    }
+
+    /// Returns the number of currently open blocks, including the function body
+    pub fn ctrl_stack_len(&self) -> usize {
+        self.ctrl_stack.len()
+    }
+
+    /// Returns whether there are no more blocks to close, i.e. the function body was fully read
+    pub fn ctrl_stack_is_empty(&self) -> bool {
+        self.ctrl_stack.is_empty()
+    }
+}
@@ -1,6 +1,6 @@
 use wasm::{validate, RuntimeInstance};
 
-const MULTIPLY_WAT_TEMPLATE: &'static str = r#"
+const MULTIPLY_WAT_TEMPLATE: &str = r#"
     (module
         (func (export "add_one") (param $x {{TYPE}}) (result {{TYPE}})
             local.get $x
@@ -32,7 +32,7 @@ fn i64_add_one() {
     let validation_info = validate(&wasm_bytes).expect("validation failed");
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
-    assert_eq!(12 as i64, instance.invoke_func(0, 11 as i64).unwrap());
-    assert_eq!(1 as i64, instance.invoke_func(0, 0 as i64).unwrap());
-    assert_eq!(-5 as i64, instance.invoke_func(0, -6 as i64).unwrap());
+    assert_eq!(12_i64, instance.invoke_func(0, 11_i64).unwrap());
+    assert_eq!(1_i64, instance.invoke_func(0, 0_i64).unwrap());
+    assert_eq!(-5_i64, instance.invoke_func(0, -6_i64).unwrap());
 }
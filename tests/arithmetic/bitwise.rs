@@ -1,6 +1,6 @@
 use wasm::{validate, RuntimeInstance};
 
-const BASE_WAT: &'static str = r#"
+const BASE_WAT: &str = r#"
     (module
       (func (export "template") (param $x i32) (param $y i32) (result i32)
           local.get $x
@@ -9,7 +9,7 @@ const BASE_WAT: &'static str = r#"
     )
 "#;
 
-const BASE_COUNT_WAT: &'static str = r#"
+const BASE_COUNT_WAT: &str = r#"
     (module
       (func (export "template") (param $x i32) (result i32)
           local.get $x
@@ -192,7 +192,7 @@ pub fn i32_bitwise_shr_u() {
 
     // Minimum and maximum 32-bit integers
     assert_eq!(
-        (i32::MIN / 2) * (-1),
+        -(i32::MIN / 2),
         instance.invoke_func(0, (i32::MIN, 1)).unwrap()
     );
     assert_eq!(
@@ -401,7 +401,7 @@ pub fn i32_bitwise_popcnt() {
     assert_eq!(0, instance.invoke_func(0, 0).unwrap());
 }
 
-const I64_BASE_WAT: &'static str = r#"
+const I64_BASE_WAT: &str = r#"
     (module
       (func (export "template") (param $x i64) (param $y i64) (result i64)
           local.get $x
@@ -410,7 +410,7 @@ const I64_BASE_WAT: &'static str = r#"
     )
 "#;
 
-const I64_BASE_COUNT_WAT: &'static str = r#"
+const I64_BASE_COUNT_WAT: &str = r#"
     (module
       (func (export "template") (param $x i64) (result i64)
           local.get $x
@@ -429,22 +429,14 @@ pub fn i64_bitwise_and() {
 
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
+    assert_eq!(1_i64, instance.invoke_func(0, (33_i64, 11_i64)).unwrap());
+    assert_eq!(5_i64, instance.invoke_func(0, (77_i64, 23_i64)).unwrap());
     assert_eq!(
-        1 as i64,
-        instance.invoke_func(0, (33 as i64, 11 as i64)).unwrap()
+        180244_i64,
+        instance.invoke_func(0, (192534_i64, 1231412_i64)).unwrap()
     );
     assert_eq!(
-        5 as i64,
-        instance.invoke_func(0, (77 as i64, 23 as i64)).unwrap()
-    );
-    assert_eq!(
-        180244 as i64,
-        instance
-            .invoke_func(0, (192534 as i64, 1231412 as i64))
-            .unwrap()
-    );
-    assert_eq!(
-        0 as i64,
+        0_i64,
         instance.invoke_func(0, (i64::MIN, i64::MAX)).unwrap()
     );
 }
@@ -460,22 +452,14 @@ pub fn i64_bitwise_or() {
 
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
+    assert_eq!(43_i64, instance.invoke_func(0, (33_i64, 11_i64)).unwrap());
+    assert_eq!(95_i64, instance.invoke_func(0, (77_i64, 23_i64)).unwrap());
     assert_eq!(
-        43 as i64,
-        instance.invoke_func(0, (33 as i64, 11 as i64)).unwrap()
+        1243702_i64,
+        instance.invoke_func(0, (192534_i64, 1231412_i64)).unwrap()
     );
     assert_eq!(
-        95 as i64,
-        instance.invoke_func(0, (77 as i64, 23 as i64)).unwrap()
-    );
-    assert_eq!(
-        1243702 as i64,
-        instance
-            .invoke_func(0, (192534 as i64, 1231412 as i64))
-            .unwrap()
-    );
-    assert_eq!(
-        -1 as i64,
+        -1_i64,
         instance.invoke_func(0, (i64::MIN, i64::MAX)).unwrap()
     );
 }
@@ -491,22 +475,14 @@ pub fn i64_bitwise_xor() {
 
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
+    assert_eq!(42_i64, instance.invoke_func(0, (33_i64, 11_i64)).unwrap());
+    assert_eq!(90_i64, instance.invoke_func(0, (77_i64, 23_i64)).unwrap());
     assert_eq!(
-        42 as i64,
-        instance.invoke_func(0, (33 as i64, 11 as i64)).unwrap()
+        1063458_i64,
+        instance.invoke_func(0, (192534_i64, 1231412_i64)).unwrap()
     );
     assert_eq!(
-        90 as i64,
-        instance.invoke_func(0, (77 as i64, 23 as i64)).unwrap()
-    );
-    assert_eq!(
-        1063458 as i64,
-        instance
-            .invoke_func(0, (192534 as i64, 1231412 as i64))
-            .unwrap()
-    );
-    assert_eq!(
-        -1 as i64,
+        -1_i64,
         instance.invoke_func(0, (i64::MIN, i64::MAX)).unwrap()
     );
 }
@@ -523,21 +499,19 @@ pub fn i64_bitwise_shl() {
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
     assert_eq!(
-        67584 as i64,
-        instance.invoke_func(0, (33 as i64, 11 as i64)).unwrap()
+        67584_i64,
+        instance.invoke_func(0, (33_i64, 11_i64)).unwrap()
     );
     assert_eq!(
-        645922816 as i64,
-        instance.invoke_func(0, (77 as i64, 23 as i64)).unwrap()
+        645922816_i64,
+        instance.invoke_func(0, (77_i64, 23_i64)).unwrap()
     );
     assert_eq!(
-        99079191802150912 as i64,
-        instance
-            .invoke_func(0, (192534 as i64, 1231412 as i64))
-            .unwrap()
+        99079191802150912_i64,
+        instance.invoke_func(0, (192534_i64, 1231412_i64)).unwrap()
     );
     assert_eq!(
-        0 as i64,
+        0_i64,
         instance.invoke_func(0, (i64::MIN, i64::MAX)).unwrap()
     );
 }
@@ -554,106 +528,75 @@ pub fn i64_bitwise_shr_s() {
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
     assert_eq!(
-        8881445 as i64,
-        instance
-            .invoke_func(0, (142_103_123 as i64, 4 as i64))
-            .unwrap()
+        8881445_i64,
+        instance.invoke_func(0, (142_103_123_i64, 4_i64)).unwrap()
     );
     assert_eq!(
-        23879 as i64,
-        instance
-            .invoke_func(0, (391_248_921 as i64, 14 as i64))
-            .unwrap()
+        23879_i64,
+        instance.invoke_func(0, (391_248_921_i64, 14_i64)).unwrap()
     );
     assert_eq!(
-        0 as i64,
+        0_i64,
         instance
-            .invoke_func(0, (1_203_910_012 as i64, 33 as i64))
+            .invoke_func(0, (1_203_910_012_i64, 33_i64))
             .unwrap()
     );
     assert_eq!(
-        0 as i64,
+        0_i64,
         instance
-            .invoke_func(0, (2_113_189_231 as i64, 33 as i64))
+            .invoke_func(0, (2_113_189_231_i64, 33_i64))
             .unwrap()
     );
     assert_eq!(
-        -1 as i64,
+        -1_i64,
         instance.invoke_func(0, (i64::MIN, i64::MAX)).unwrap()
     );
 
     // Basic positive number
-    assert_eq!(
-        4 as i64,
-        instance.invoke_func(0, (8 as i64, 1 as i64)).unwrap()
-    );
+    assert_eq!(4_i64, instance.invoke_func(0, (8_i64, 1_i64)).unwrap());
 
     // Shifting by 0 (no shift)
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 0 as i64)).unwrap()
-    );
-    assert_eq!(
-        1 as i64,
-        instance.invoke_func(0, (1 as i64, 0 as i64)).unwrap()
-    );
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 0_i64)).unwrap());
+    assert_eq!(1_i64, instance.invoke_func(0, (1_i64, 0_i64)).unwrap());
 
     // Shifting negative numbers
-    assert_eq!(
-        -4 as i64,
-        instance.invoke_func(0, (-8 as i64, 1 as i64)).unwrap()
-    );
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 1 as i64)).unwrap()
-    );
+    assert_eq!(-4_i64, instance.invoke_func(0, (-8_i64, 1_i64)).unwrap());
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 1_i64)).unwrap());
 
     // Shifting by 31 (maximum shift for 32-bit int)
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 31_i64)).unwrap());
     assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 31 as i64)).unwrap()
+        -4294967296_i64,
+        instance.invoke_func(0, (i64::MIN, 31_i64)).unwrap()
     );
     assert_eq!(
-        -4294967296 as i64,
-        instance.invoke_func(0, (i64::MIN, 31 as i64)).unwrap()
-    );
-    assert_eq!(
-        4294967295 as i64,
-        instance.invoke_func(0, (i64::MAX, 31 as i64)).unwrap()
+        4294967295_i64,
+        instance.invoke_func(0, (i64::MAX, 31_i64)).unwrap()
     );
 
     // Shifting by more than 31
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 32 as i64)).unwrap()
-    );
-    assert_eq!(
-        0 as i64,
-        instance.invoke_func(0, (1 as i64, 32 as i64)).unwrap()
-    );
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 100 as i64)).unwrap()
-    );
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 32_i64)).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, (1_i64, 32_i64)).unwrap());
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 100_i64)).unwrap());
 
     // Minimum and maximum 32-bit integers
     assert_eq!(
         i64::MIN / 2,
-        instance.invoke_func(0, (i64::MIN, 1 as i64)).unwrap()
+        instance.invoke_func(0, (i64::MIN, 1_i64)).unwrap()
     );
     assert_eq!(
         i64::MAX / 2,
-        instance.invoke_func(0, (i64::MAX, 1 as i64)).unwrap()
+        instance.invoke_func(0, (i64::MAX, 1_i64)).unwrap()
     );
 
     // Shifting out all bits except sign
     assert_eq!(
-        -8589934592 as i64,
-        instance.invoke_func(0, (i64::MIN, 30 as i64)).unwrap()
+        -8589934592_i64,
+        instance.invoke_func(0, (i64::MIN, 30_i64)).unwrap()
     );
     assert_eq!(
-        8589934591 as i64,
-        instance.invoke_func(0, (i64::MAX, 30 as i64)).unwrap()
+        8589934591_i64,
+        instance.invoke_func(0, (i64::MAX, 30_i64)).unwrap()
     );
 }
 
@@ -669,106 +612,87 @@ pub fn i64_bitwise_shr_u() {
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
     assert_eq!(
-        8881445 as i64,
-        instance
-            .invoke_func(0, (142_103_123 as i64, 4 as i64))
-            .unwrap()
+        8881445_i64,
+        instance.invoke_func(0, (142_103_123_i64, 4_i64)).unwrap()
     );
     assert_eq!(
-        23879 as i64,
-        instance
-            .invoke_func(0, (391_248_921 as i64, 14 as i64))
-            .unwrap()
+        23879_i64,
+        instance.invoke_func(0, (391_248_921_i64, 14_i64)).unwrap()
     );
     assert_eq!(
-        0 as i64,
+        0_i64,
         instance
-            .invoke_func(0, (1_203_910_012 as i64, 33 as i64))
+            .invoke_func(0, (1_203_910_012_i64, 33_i64))
             .unwrap()
     );
     assert_eq!(
-        0 as i64,
+        0_i64,
         instance
-            .invoke_func(0, (2_113_189_231 as i64, 33 as i64))
+            .invoke_func(0, (2_113_189_231_i64, 33_i64))
             .unwrap()
     );
     assert_eq!(
-        1 as i64,
+        1_i64,
         instance.invoke_func(0, (i64::MIN, i64::MAX)).unwrap()
     );
 
     // Basic positive number
-    assert_eq!(
-        4 as i64,
-        instance.invoke_func(0, (8 as i64, 1 as i64)).unwrap()
-    );
+    assert_eq!(4_i64, instance.invoke_func(0, (8_i64, 1_i64)).unwrap());
 
     // Shifting by 0 (no shift)
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 0 as i64)).unwrap()
-    );
-    assert_eq!(
-        1 as i64,
-        instance.invoke_func(0, (1 as i64, 0 as i64)).unwrap()
-    );
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 0_i64)).unwrap());
+    assert_eq!(1_i64, instance.invoke_func(0, (1_i64, 0_i64)).unwrap());
 
     // Shifting negative numbers
     assert_eq!(
         i64::MAX - 3,
-        instance.invoke_func(0, (-8 as i64, 1 as i64)).unwrap()
-    );
-    assert_eq!(
-        i64::MAX,
-        instance.invoke_func(0, (-1 as i64, 1 as i64)).unwrap()
+        instance.invoke_func(0, (-8_i64, 1_i64)).unwrap()
     );
+    assert_eq!(i64::MAX, instance.invoke_func(0, (-1_i64, 1_i64)).unwrap());
 
     // Shifting by 31 (maximum shift for 32-bit int)
     assert_eq!(
-        8589934591 as i64,
-        instance.invoke_func(0, (-1 as i64, 31 as i64)).unwrap()
+        8589934591_i64,
+        instance.invoke_func(0, (-1_i64, 31_i64)).unwrap()
     );
     assert_eq!(
-        4294967296 as i64,
-        instance.invoke_func(0, (i64::MIN, 31 as i64)).unwrap()
+        4294967296_i64,
+        instance.invoke_func(0, (i64::MIN, 31_i64)).unwrap()
     );
     assert_eq!(
-        4294967295 as i64,
-        instance.invoke_func(0, (i64::MAX, 31 as i64)).unwrap()
+        4294967295_i64,
+        instance.invoke_func(0, (i64::MAX, 31_i64)).unwrap()
     );
 
     // Shifting by more than 31
     assert_eq!(
-        4294967295 as i64,
-        instance.invoke_func(0, (-1 as i64, 32 as i64)).unwrap()
-    );
-    assert_eq!(
-        0 as i64,
-        instance.invoke_func(0, (1 as i64, 32 as i64)).unwrap()
+        4294967295_i64,
+        instance.invoke_func(0, (-1_i64, 32_i64)).unwrap()
     );
+    assert_eq!(0_i64, instance.invoke_func(0, (1_i64, 32_i64)).unwrap());
     assert_eq!(
-        268435455 as i64,
-        instance.invoke_func(0, (-1 as i64, 100 as i64)).unwrap()
+        268435455_i64,
+        instance.invoke_func(0, (-1_i64, 100_i64)).unwrap()
     );
 
     // Minimum and maximum 32-bit integers
     assert_eq!(
-        (i64::MIN / 2) * (-1),
-        instance.invoke_func(0, (i64::MIN, 1 as i64)).unwrap()
+        -(i64::MIN / 2),
+        instance.invoke_func(0, (i64::MIN, 1_i64)).unwrap()
     );
     assert_eq!(
         i64::MAX / 2,
-        instance.invoke_func(0, (i64::MAX, 1 as i64)).unwrap()
+        instance.invoke_func(0, (i64::MAX, 1_i64)).unwrap()
     );
 
     // Shifting out all bits except sign
     assert_eq!(
-        8589934592 as i64,
-        instance.invoke_func(0, (i64::MIN, 30 as i64)).unwrap()
+        8589934592_i64,
+        instance.invoke_func(0, (i64::MIN, 30_i64)).unwrap()
     );
     assert_eq!(
-        8589934591 as i64,
-        instance.invoke_func(0, (i64::MAX, 30 as i64)).unwrap()
+        8589934591_i64,
+        instance.invoke_func(0, (i64::MAX, 30_i64)).unwrap()
     );
 }
 
@@ -784,106 +708,72 @@ pub fn i64_bitwise_rotl() {
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
     assert_eq!(
-        2273649968 as i64,
-        instance
-            .invoke_func(0, (142_103_123 as i64, 4 as i64))
-            .unwrap()
+        2273649968_i64,
+        instance.invoke_func(0, (142_103_123_i64, 4_i64)).unwrap()
     );
     assert_eq!(
-        6410222321664 as i64,
-        instance
-            .invoke_func(0, (391_248_921 as i64, 14 as i64))
-            .unwrap()
+        6410222321664_i64,
+        instance.invoke_func(0, (391_248_921_i64, 14_i64)).unwrap()
     );
     assert_eq!(
-        -8105235815975616512 as i64,
+        -8105235815975616512_i64,
         instance
-            .invoke_func(0, (1_203_910_012 as i64, 33 as i64))
+            .invoke_func(0, (1_203_910_012_i64, 33_i64))
             .unwrap()
     );
     assert_eq!(
-        -294586798900772864 as i64,
+        -294586798900772864_i64,
         instance
-            .invoke_func(0, (2_113_189_231 as i64, 33 as i64))
+            .invoke_func(0, (2_113_189_231_i64, 33_i64))
             .unwrap()
     );
     assert_eq!(
-        4611686018427387904 as i64,
+        4611686018427387904_i64,
         instance.invoke_func(0, (i64::MIN, i64::MAX)).unwrap()
     );
 
     // Basic positive number
-    assert_eq!(
-        16 as i64,
-        instance.invoke_func(0, (8 as i64, 1 as i64)).unwrap()
-    );
+    assert_eq!(16_i64, instance.invoke_func(0, (8_i64, 1_i64)).unwrap());
 
     // Rotating by 0 (no shift)
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 0 as i64)).unwrap()
-    );
-    assert_eq!(
-        1 as i64,
-        instance.invoke_func(0, (1 as i64, 0 as i64)).unwrap()
-    );
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 0_i64)).unwrap());
+    assert_eq!(1_i64, instance.invoke_func(0, (1_i64, 0_i64)).unwrap());
 
     // Shifting negative numbers
-    assert_eq!(
-        -15 as i64,
-        instance.invoke_func(0, (-8 as i64, 1 as i64)).unwrap()
-    );
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 1 as i64)).unwrap()
-    );
+    assert_eq!(-15_i64, instance.invoke_func(0, (-8_i64, 1_i64)).unwrap());
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 1_i64)).unwrap());
 
     // Rotating by 31
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 31_i64)).unwrap());
     assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 31 as i64)).unwrap()
-    );
-    assert_eq!(
-        1073741824 as i64,
-        instance.invoke_func(0, (i64::MIN, 31 as i64)).unwrap()
+        1073741824_i64,
+        instance.invoke_func(0, (i64::MIN, 31_i64)).unwrap()
     );
     assert_eq!(
-        -1073741825 as i64,
-        instance.invoke_func(0, (i64::MAX, 31 as i64)).unwrap()
+        -1073741825_i64,
+        instance.invoke_func(0, (i64::MAX, 31_i64)).unwrap()
     );
 
     // Rotating by more than 31
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 32_i64)).unwrap());
     assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 32 as i64)).unwrap()
-    );
-    assert_eq!(
-        4294967296 as i64,
-        instance.invoke_func(0, (1 as i64, 32 as i64)).unwrap()
-    );
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 100 as i64)).unwrap()
+        4294967296_i64,
+        instance.invoke_func(0, (1_i64, 32_i64)).unwrap()
     );
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 100_i64)).unwrap());
 
     // Minimum and maximum 32-bit integers
-    assert_eq!(
-        1 as i64,
-        instance.invoke_func(0, (i64::MIN, 1 as i64)).unwrap()
-    );
-    assert_eq!(
-        -2 as i64,
-        instance.invoke_func(0, (i64::MAX, 1 as i64)).unwrap()
-    );
+    assert_eq!(1_i64, instance.invoke_func(0, (i64::MIN, 1_i64)).unwrap());
+    assert_eq!(-2_i64, instance.invoke_func(0, (i64::MAX, 1_i64)).unwrap());
 
     // Shifting out all bits except sign
     assert_eq!(
-        536870912 as i64,
-        instance.invoke_func(0, (i64::MIN, 30 as i64)).unwrap()
+        536870912_i64,
+        instance.invoke_func(0, (i64::MIN, 30_i64)).unwrap()
     );
     assert_eq!(
-        -536870913 as i64,
-        instance.invoke_func(0, (i64::MAX, 30 as i64)).unwrap()
+        -536870913_i64,
+        instance.invoke_func(0, (i64::MAX, 30_i64)).unwrap()
     );
 }
 
@@ -899,106 +789,81 @@ pub fn i64_bitwise_rotr() {
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
     assert_eq!(
-        3458764513829422373 as i64,
-        instance
-            .invoke_func(0, (142_103_123 as i64, 4 as i64))
-            .unwrap()
+        3458764513829422373_i64,
+        instance.invoke_func(0, (142_103_123_i64, 4_i64)).unwrap()
     );
     assert_eq!(
-        -1124774006935757497 as i64,
-        instance
-            .invoke_func(0, (391_248_921 as i64, 14 as i64))
-            .unwrap()
+        -1124774006935757497_i64,
+        instance.invoke_func(0, (391_248_921_i64, 14_i64)).unwrap()
     );
     assert_eq!(
-        2585377064433483776 as i64,
+        2585377064433483776_i64,
         instance
-            .invoke_func(0, (1_203_910_012 as i64, 33 as i64))
+            .invoke_func(0, (1_203_910_012_i64, 33_i64))
             .unwrap()
     );
     assert_eq!(
-        4538039318702194688 as i64,
+        4538039318702194688_i64,
         instance
-            .invoke_func(0, (2_113_189_231 as i64, 33 as i64))
+            .invoke_func(0, (2_113_189_231_i64, 33_i64))
             .unwrap()
     );
     assert_eq!(
-        1 as i64,
+        1_i64,
         instance.invoke_func(0, (i64::MIN, i64::MAX)).unwrap()
     );
 
     // Basic positive number
-    assert_eq!(
-        4 as i64,
-        instance.invoke_func(0, (8 as i64, 1 as i64)).unwrap()
-    );
+    assert_eq!(4_i64, instance.invoke_func(0, (8_i64, 1_i64)).unwrap());
 
     // Rotating by 0 (no shift)
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 0 as i64)).unwrap()
-    );
-    assert_eq!(
-        1 as i64,
-        instance.invoke_func(0, (1 as i64, 0 as i64)).unwrap()
-    );
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 0_i64)).unwrap());
+    assert_eq!(1_i64, instance.invoke_func(0, (1_i64, 0_i64)).unwrap());
 
     // Shifting negative numbers
     assert_eq!(
         i64::MAX - 3,
-        instance.invoke_func(0, (-8 as i64, 1 as i64)).unwrap()
-    );
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 1 as i64)).unwrap()
+        instance.invoke_func(0, (-8_i64, 1_i64)).unwrap()
     );
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 1_i64)).unwrap());
 
     // Rotating by 31
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 31_i64)).unwrap());
     assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 31 as i64)).unwrap()
+        4294967296_i64,
+        instance.invoke_func(0, (i64::MIN, 31_i64)).unwrap()
     );
     assert_eq!(
-        4294967296 as i64,
-        instance.invoke_func(0, (i64::MIN, 31 as i64)).unwrap()
-    );
-    assert_eq!(
-        -4294967297 as i64,
-        instance.invoke_func(0, (i64::MAX, 31 as i64)).unwrap()
+        -4294967297_i64,
+        instance.invoke_func(0, (i64::MAX, 31_i64)).unwrap()
     );
 
     // Rotating by more than 31
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 32_i64)).unwrap());
     assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 32 as i64)).unwrap()
-    );
-    assert_eq!(
-        4294967296 as i64,
-        instance.invoke_func(0, (1 as i64, 32 as i64)).unwrap()
-    );
-    assert_eq!(
-        -1 as i64,
-        instance.invoke_func(0, (-1 as i64, 100 as i64)).unwrap()
+        4294967296_i64,
+        instance.invoke_func(0, (1_i64, 32_i64)).unwrap()
     );
+    assert_eq!(-1_i64, instance.invoke_func(0, (-1_i64, 100_i64)).unwrap());
 
     // Minimum and maximum 32-bit integers
     assert_eq!(
         i64::MAX / 2 + 1,
-        instance.invoke_func(0, (i64::MIN, 1 as i64)).unwrap()
+        instance.invoke_func(0, (i64::MIN, 1_i64)).unwrap()
     );
     assert_eq!(
         i64::MIN / 2 - 1,
-        instance.invoke_func(0, (i64::MAX, 1 as i64)).unwrap()
+        instance.invoke_func(0, (i64::MAX, 1_i64)).unwrap()
     );
 
     // Shifting out all bits except sign
     assert_eq!(
-        8589934592 as i64,
-        instance.invoke_func(0, (i64::MIN, 30 as i64)).unwrap()
+        8589934592_i64,
+        instance.invoke_func(0, (i64::MIN, 30_i64)).unwrap()
     );
     assert_eq!(
-        -8589934593 as i64,
-        instance.invoke_func(0, (i64::MAX, 30 as i64)).unwrap()
+        -8589934593_i64,
+        instance.invoke_func(0, (i64::MAX, 30_i64)).unwrap()
     );
 }
 
@@ -1013,12 +878,12 @@ pub fn i64_bitwise_clz() {
 
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
-    assert_eq!(58 as i64, instance.invoke_func(0, 33 as i64).unwrap());
-    assert_eq!(57 as i64, instance.invoke_func(0, 77 as i64).unwrap());
-    assert_eq!(46 as i64, instance.invoke_func(0, 192534 as i64).unwrap());
-    assert_eq!(0 as i64, instance.invoke_func(0, i64::MIN).unwrap());
-    assert_eq!(1 as i64, instance.invoke_func(0, i64::MAX).unwrap());
-    assert_eq!(64 as i64, instance.invoke_func(0, 0 as i64).unwrap());
+    assert_eq!(58_i64, instance.invoke_func(0, 33_i64).unwrap());
+    assert_eq!(57_i64, instance.invoke_func(0, 77_i64).unwrap());
+    assert_eq!(46_i64, instance.invoke_func(0, 192534_i64).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, i64::MIN).unwrap());
+    assert_eq!(1_i64, instance.invoke_func(0, i64::MAX).unwrap());
+    assert_eq!(64_i64, instance.invoke_func(0, 0_i64).unwrap());
 }
 
 /// A simple function to test the i64.ctz bitwise operation
@@ -1032,12 +897,12 @@ pub fn i64_bitwise_ctz() {
 
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
-    assert_eq!(0 as i64, instance.invoke_func(0, 33 as i64).unwrap());
-    assert_eq!(0 as i64, instance.invoke_func(0, 77 as i64).unwrap());
-    assert_eq!(1 as i64, instance.invoke_func(0, 192534 as i64).unwrap());
-    assert_eq!(63 as i64, instance.invoke_func(0, i64::MIN).unwrap());
-    assert_eq!(0 as i64, instance.invoke_func(0, i64::MAX).unwrap());
-    assert_eq!(64 as i64, instance.invoke_func(0, 0 as i64).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, 33_i64).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, 77_i64).unwrap());
+    assert_eq!(1_i64, instance.invoke_func(0, 192534_i64).unwrap());
+    assert_eq!(63_i64, instance.invoke_func(0, i64::MIN).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, i64::MAX).unwrap());
+    assert_eq!(64_i64, instance.invoke_func(0, 0_i64).unwrap());
 }
 
 /// A simple function to test the i64.popcnt bitwise operation
@@ -1051,10 +916,10 @@ pub fn i64_bitwise_popcnt() {
 
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
-    assert_eq!(2 as i64, instance.invoke_func(0, 33 as i64).unwrap());
-    assert_eq!(4 as i64, instance.invoke_func(0, 77 as i64).unwrap());
-    assert_eq!(8 as i64, instance.invoke_func(0, 192534 as i64).unwrap());
-    assert_eq!(1 as i64, instance.invoke_func(0, i64::MIN).unwrap());
-    assert_eq!(63 as i64, instance.invoke_func(0, i64::MAX).unwrap());
-    assert_eq!(0 as i64, instance.invoke_func(0, 0 as i64).unwrap());
+    assert_eq!(2_i64, instance.invoke_func(0, 33_i64).unwrap());
+    assert_eq!(4_i64, instance.invoke_func(0, 77_i64).unwrap());
+    assert_eq!(8_i64, instance.invoke_func(0, 192534_i64).unwrap());
+    assert_eq!(1_i64, instance.invoke_func(0, i64::MIN).unwrap());
+    assert_eq!(63_i64, instance.invoke_func(0, i64::MAX).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, 0_i64).unwrap());
 }
@@ -1,6 +1,6 @@
 use wasm::{validate, RuntimeInstance};
 
-const MULTIPLY_WAT_TEMPLATE: &'static str = r#"
+const MULTIPLY_WAT_TEMPLATE: &str = r#"
     (module
         (func (export "multiply") (param $x {{TYPE}}) (result {{TYPE}})
             local.get $x
@@ -45,9 +45,9 @@ pub fn i64_multiply() {
 
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
-    assert_eq!(33 as i64, instance.invoke_func(0, 11 as i64).unwrap());
-    assert_eq!(0 as i64, instance.invoke_func(0, 0 as i64).unwrap());
-    assert_eq!(-30 as i64, instance.invoke_func(0, -10 as i64).unwrap());
+    assert_eq!(33_i64, instance.invoke_func(0, 11_i64).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, 0_i64).unwrap());
+    assert_eq!(-30_i64, instance.invoke_func(0, -10_i64).unwrap());
 
     assert_eq!(i64::MAX - 5, instance.invoke_func(0, i64::MAX - 1).unwrap());
     assert_eq!(i64::MIN + 3, instance.invoke_func(0, i64::MIN + 1).unwrap());
@@ -1,6 +1,6 @@
 use wasm::RuntimeError;
 use wasm::{validate, RuntimeInstance};
-const REM_S_WAT: &'static str = r#"
+const REM_S_WAT: &str = r#"
     (module
         (func (export "rem_s") (param $divisor {{TYPE}}) (param $dividend {{TYPE}}) (result {{TYPE}})
             local.get $divisor
@@ -9,7 +9,7 @@ const REM_S_WAT: &'static str = r#"
     )
 "#;
 
-const REM_U_WAT: &'static str = r#"
+const REM_U_WAT: &str = r#"
     (module
         (func (export "rem_u") (param $divisor {{TYPE}}) (param $dividend {{TYPE}}) (result {{TYPE}})
             local.get $divisor
@@ -26,44 +26,17 @@ pub fn i64_remainder_signed_simple() {
     let validation_info = validate(&wasm_bytes).expect("validation failed");
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
+    assert_eq!(0_i64, instance.invoke_func(0, (20_i64, 2_i64)).unwrap());
     assert_eq!(
-        0 as i64,
-        instance.invoke_func(0, (20 as i64, 2 as i64)).unwrap()
-    );
-    assert_eq!(
-        999 as i64,
-        instance
-            .invoke_func(0, (10_000 as i64, 9_001 as i64))
-            .unwrap()
-    );
-    assert_eq!(
-        -2 as i64,
-        instance.invoke_func(0, (-20 as i64, 3 as i64)).unwrap()
-    );
-    assert_eq!(
-        -2 as i64,
-        instance.invoke_func(0, (-20 as i64, -3 as i64)).unwrap()
-    );
-    assert_eq!(
-        2 as i64,
-        instance.invoke_func(0, (20 as i64, -3 as i64)).unwrap()
-    );
-    assert_eq!(
-        2 as i64,
-        instance.invoke_func(0, (20 as i64, 3 as i64)).unwrap()
-    );
-    assert_eq!(
-        0 as i64,
-        instance
-            .invoke_func(0, (i64::MIN as i64, -1 as i64))
-            .unwrap()
-    );
-    assert_eq!(
-        0 as i64,
-        instance
-            .invoke_func(0, (i64::MIN as i64, 2 as i64))
-            .unwrap()
+        999_i64,
+        instance.invoke_func(0, (10_000_i64, 9_001_i64)).unwrap()
     );
+    assert_eq!(-2_i64, instance.invoke_func(0, (-20_i64, 3_i64)).unwrap());
+    assert_eq!(-2_i64, instance.invoke_func(0, (-20_i64, -3_i64)).unwrap());
+    assert_eq!(2_i64, instance.invoke_func(0, (20_i64, -3_i64)).unwrap());
+    assert_eq!(2_i64, instance.invoke_func(0, (20_i64, 3_i64)).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, (i64::MIN, -1_i64)).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, (i64::MIN, 2_i64)).unwrap());
 }
 
 /// A simple function to test i64 signed remainder's RuntimeError when dividing by 0
@@ -74,7 +47,7 @@ pub fn i64_remainder_signed_panic_dividend_0() {
     let validation_info = validate(&wasm_bytes).expect("validation failed");
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
-    let result = instance.invoke_func::<(i64, i64), i64>(0, (222 as i64, 0 as i64));
+    let result = instance.invoke_func::<(i64, i64), i64>(0, (222_i64, 0_i64));
 
     assert_eq!(result.unwrap_err(), RuntimeError::DivideBy0);
 }
@@ -87,57 +60,31 @@ pub fn i64_remainder_unsigned_simple() {
     let validation_info = validate(&wasm_bytes).expect("validation failed");
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
-    assert_eq!(
-        0 as i64,
-        instance.invoke_func(0, (i64::MIN, 2 as i64)).unwrap()
-    );
+    assert_eq!(0_i64, instance.invoke_func(0, (i64::MIN, 2_i64)).unwrap());
     assert_eq!(
         i64::MIN,
-        instance.invoke_func(0, (i64::MIN, -2 as i64)).unwrap()
+        instance.invoke_func(0, (i64::MIN, -2_i64)).unwrap()
     );
     assert_eq!(
         (i64::MAX - 1),
-        instance.invoke_func(0, (-2 as i64, i64::MIN)).unwrap()
-    );
-    assert_eq!(
-        2 as i64,
-        instance.invoke_func(0, (2 as i64, i64::MIN)).unwrap()
+        instance.invoke_func(0, (-2_i64, i64::MIN)).unwrap()
     );
+    assert_eq!(2_i64, instance.invoke_func(0, (2_i64, i64::MIN)).unwrap());
 
+    assert_eq!(0_i64, instance.invoke_func(0, (20_i64, 2_i64)).unwrap());
     assert_eq!(
-        0 as i64,
-        instance.invoke_func(0, (20 as i64, 2 as i64)).unwrap()
-    );
-    assert_eq!(
-        999 as i64,
-        instance
-            .invoke_func(0, (10_000 as i64, 9_001 as i64))
-            .unwrap()
-    );
-    assert_eq!(
-        2 as i64,
-        instance.invoke_func(0, (-20 as i64, 3 as i64)).unwrap()
-    );
-    assert_eq!(
-        -20 as i64,
-        instance.invoke_func(0, (-20 as i64, -3 as i64)).unwrap()
-    );
-    assert_eq!(
-        20 as i64,
-        instance.invoke_func(0, (20 as i64, -3 as i64)).unwrap()
-    );
-    assert_eq!(
-        2 as i64,
-        instance.invoke_func(0, (20 as i64, 3 as i64)).unwrap()
+        999_i64,
+        instance.invoke_func(0, (10_000_i64, 9_001_i64)).unwrap()
     );
+    assert_eq!(2_i64, instance.invoke_func(0, (-20_i64, 3_i64)).unwrap());
+    assert_eq!(-20_i64, instance.invoke_func(0, (-20_i64, -3_i64)).unwrap());
+    assert_eq!(20_i64, instance.invoke_func(0, (20_i64, -3_i64)).unwrap());
+    assert_eq!(2_i64, instance.invoke_func(0, (20_i64, 3_i64)).unwrap());
     assert_eq!(
         i64::MIN,
-        instance.invoke_func(0, (i64::MIN, -1 as i64)).unwrap()
-    );
-    assert_eq!(
-        0 as i64,
-        instance.invoke_func(0, (i64::MIN, 2 as i64)).unwrap()
+        instance.invoke_func(0, (i64::MIN, -1_i64)).unwrap()
     );
+    assert_eq!(0_i64, instance.invoke_func(0, (i64::MIN, 2_i64)).unwrap());
 }
 
 /// A simple function to test i64 signed remainder's RuntimeError when dividing by 0
@@ -202,7 +149,7 @@ pub fn i32_remainder_unsigned_simple() {
         instance.invoke_named("rem_u", (i32::MIN, -2)).unwrap()
     );
     assert_eq!(
-        (i32::MIN + 2) * (-1),
+        -(i32::MIN + 2),
         instance.invoke_named("rem_u", (-2, i32::MIN)).unwrap()
     );
     assert_eq!(2, instance.invoke_named("rem_u", (2, i32::MIN)).unwrap());
@@ -1,6 +1,6 @@
 use wasm::{validate, RuntimeInstance};
 
-const WAT_SUBTRACT_TEMPLATE: &'static str = r#"
+const WAT_SUBTRACT_TEMPLATE: &str = r#"
     (module
         (func (export "subtract") (param $x {{TYPE}}) (param $y {{TYPE}}) (result {{TYPE}})
             local.get $x
@@ -21,25 +21,16 @@ pub fn i64_subtract() {
 
     let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
 
-    assert_eq!(
-        -10 as i64,
-        instance.invoke_func(0, (1 as i64, 11 as i64)).unwrap()
-    );
-    assert_eq!(
-        0 as i64,
-        instance.invoke_func(0, (0 as i64, 0 as i64)).unwrap()
-    );
-    assert_eq!(
-        10 as i64,
-        instance.invoke_func(0, (-10 as i64, -20 as i64)).unwrap()
-    );
+    assert_eq!(-10_i64, instance.invoke_func(0, (1_i64, 11_i64)).unwrap());
+    assert_eq!(0_i64, instance.invoke_func(0, (0_i64, 0_i64)).unwrap());
+    assert_eq!(10_i64, instance.invoke_func(0, (-10_i64, -20_i64)).unwrap());
 
     assert_eq!(
         i64::MAX - 1,
-        instance.invoke_func(0, (i64::MAX - 1, 0 as i64)).unwrap()
+        instance.invoke_func(0, (i64::MAX - 1, 0_i64)).unwrap()
     );
     assert_eq!(
         i64::MIN + 3,
-        instance.invoke_func(0, (i64::MIN + 3, 0 as i64)).unwrap()
+        instance.invoke_func(0, (i64::MIN + 3, 0_i64)).unwrap()
     );
 }
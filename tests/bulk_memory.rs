@@ -0,0 +1,351 @@
+use wasm::{validate, Error, RuntimeError, RuntimeInstance};
+
+const MEMORY_WAT: &str = r#"
+    (module
+        (memory 1)
+        (data (i32.const 0) "\01\02\03\04\05\06\07\08")
+        (data $passive "\aa\bb\cc\dd")
+        (func (export "load8") (param i32) (result i32)
+            (i32.load8_u (local.get 0)))
+        (func (export "load") (param i32) (result i32)
+            (i32.load (local.get 0)))
+        (func (export "fill") (param i32 i32 i32)
+            (memory.fill (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "copy") (param i32 i32 i32)
+            (memory.copy (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "init") (param i32 i32 i32)
+            (memory.init $passive (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "init_active") (param i32 i32 i32)
+            (memory.init 0 (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "drop")
+            (data.drop $passive))
+    )
+"#;
+
+#[test_log::test]
+fn memory_fill() {
+    let wasm_bytes = wat::parse_str(MEMORY_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    // Only the lowest byte of the value is used
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("fill", (2, 0x1ff, 3))
+        .unwrap();
+    assert_eq!(
+        0xff_ff_02_01_u32 as i32,
+        instance.invoke_named::<i32, i32>("load", 0).unwrap()
+    );
+    assert_eq!(
+        0x08_07_06_ff,
+        instance.invoke_named::<i32, i32>("load", 4).unwrap()
+    );
+
+    // Filling up to the very end of the memory is allowed, as is filling nothing at the end
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("fill", (65530, 0x42, 6))
+        .unwrap();
+    assert_eq!(
+        0x42,
+        instance.invoke_named::<i32, i32>("load8", 65535).unwrap()
+    );
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("fill", (65536, 0, 0))
+        .unwrap();
+}
+
+#[test_log::test]
+fn memory_copy() {
+    let wasm_bytes = wat::parse_str(MEMORY_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("copy", (100, 0, 8))
+        .unwrap();
+    assert_eq!(
+        0x04_03_02_01,
+        instance.invoke_named::<i32, i32>("load", 100).unwrap()
+    );
+
+    // Overlapping regions are copied as if through an intermediate buffer, in both directions
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("copy", (102, 100, 4))
+        .unwrap();
+    assert_eq!(
+        0x02_01_02_01,
+        instance.invoke_named::<i32, i32>("load", 100).unwrap()
+    );
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("copy", (0, 2, 6))
+        .unwrap();
+    assert_eq!(
+        0x06_05_04_03,
+        instance.invoke_named::<i32, i32>("load", 0).unwrap()
+    );
+}
+
+#[test_log::test]
+fn memory_init_and_data_drop() {
+    let wasm_bytes = wat::parse_str(MEMORY_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("init", (200, 1, 3))
+        .unwrap();
+    assert_eq!(
+        0x00_dd_cc_bb,
+        instance.invoke_named::<i32, i32>("load", 200).unwrap()
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("init", (200, 2, 3))
+    );
+
+    // Dropped segments are empty, so only empty ranges can be copied from them
+    instance.invoke_named::<(), ()>("drop", ()).unwrap();
+    instance.invoke_named::<(), ()>("drop", ()).unwrap();
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("init", (200, 0, 1))
+    );
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("init", (200, 0, 0))
+        .unwrap();
+
+    // Active segments are dropped after instantiation
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("init_active", (200, 0, 1))
+    );
+}
+
+/// Bounds are checked before anything is written, so trapping instructions leave memory unchanged
+#[test_log::test]
+fn memory_bounds_are_checked_before_writing() {
+    let wasm_bytes = wat::parse_str(MEMORY_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("fill", (65530, 0x42, 7))
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("fill", (65537, 0x42, 0))
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("copy", (65532, 0, 5))
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("copy", (0, 65532, 5))
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("init", (65534, 0, 4))
+    );
+    // The addresses are unsigned and do not wrap around
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("fill", (-1, 0x42, 2))
+    );
+
+    assert_eq!(0, instance.invoke_named::<i32, i32>("load", 65532).unwrap());
+    assert_eq!(
+        0x04_03_02_01,
+        instance.invoke_named::<i32, i32>("load", 0).unwrap()
+    );
+}
+
+const TABLE_WAT: &str = r#"
+    (module
+        (type $get (func (result i32)))
+        (table $t0 4 funcref)
+        (table $t1 4 funcref)
+        (elem $passive func $one $two $three)
+        (elem (table $t0) (i32.const 0) func $three)
+        (func $one (result i32) i32.const 1)
+        (func $two (result i32) i32.const 2)
+        (func $three (result i32) i32.const 3)
+        (func (export "call0") (param i32) (result i32)
+            (call_indirect $t0 (type $get) (local.get 0)))
+        (func (export "call1") (param i32) (result i32)
+            (call_indirect $t1 (type $get) (local.get 0)))
+        (func (export "init") (param i32 i32 i32)
+            (table.init $t0 $passive (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "copy") (param i32 i32 i32)
+            (table.copy $t0 $t0 (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "copy_to_t1") (param i32 i32 i32)
+            (table.copy $t1 $t0 (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "drop")
+            (elem.drop $passive))
+    )
+"#;
+
+#[test_log::test]
+fn table_init_and_elem_drop() {
+    let wasm_bytes = wat::parse_str(TABLE_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(3, instance.invoke_named::<i32, i32>("call0", 0).unwrap());
+    assert_eq!(
+        Err(RuntimeError::UninitializedTableElement),
+        instance.invoke_named::<i32, i32>("call0", 1)
+    );
+
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("init", (1, 0, 3))
+        .unwrap();
+    assert_eq!(1, instance.invoke_named::<i32, i32>("call0", 1).unwrap());
+    assert_eq!(2, instance.invoke_named::<i32, i32>("call0", 2).unwrap());
+    assert_eq!(3, instance.invoke_named::<i32, i32>("call0", 3).unwrap());
+
+    // Out of bounds of the table or the segment, nothing is written
+    assert_eq!(
+        Err(RuntimeError::TableAccessOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("init", (2, 0, 3))
+    );
+    assert_eq!(
+        Err(RuntimeError::TableAccessOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("init", (0, 1, 3))
+    );
+    assert_eq!(3, instance.invoke_named::<i32, i32>("call0", 0).unwrap());
+
+    instance.invoke_named::<(), ()>("drop", ()).unwrap();
+    assert_eq!(
+        Err(RuntimeError::TableAccessOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("init", (0, 0, 1))
+    );
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("init", (4, 0, 0))
+        .unwrap();
+}
+
+#[test_log::test]
+fn table_copy() {
+    let wasm_bytes = wat::parse_str(TABLE_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("init", (1, 0, 2))
+        .unwrap();
+
+    // Overlapping copy within a table: [3 1 2 null] -> [3 3 1 2]
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("copy", (1, 0, 3))
+        .unwrap();
+    let entries = (0..4)
+        .map(|idx| instance.invoke_named::<i32, i32>("call0", idx).unwrap())
+        .collect::<Vec<i32>>();
+    assert_eq!(entries, [3, 3, 1, 2]);
+
+    // Copy between tables
+    instance
+        .invoke_named::<(i32, i32, i32), ()>("copy_to_t1", (2, 2, 2))
+        .unwrap();
+    assert_eq!(
+        Err(RuntimeError::UninitializedTableElement),
+        instance.invoke_named::<i32, i32>("call1", 1)
+    );
+    assert_eq!(1, instance.invoke_named::<i32, i32>("call1", 2).unwrap());
+    assert_eq!(2, instance.invoke_named::<i32, i32>("call1", 3).unwrap());
+
+    assert_eq!(
+        Err(RuntimeError::TableAccessOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("copy_to_t1", (0, 2, 3))
+    );
+    assert_eq!(
+        Err(RuntimeError::TableAccessOutOfBounds),
+        instance.invoke_named::<(i32, i32, i32), ()>("copy", (3, 0, 2))
+    );
+    assert_eq!(
+        Err(RuntimeError::UninitializedTableElement),
+        instance.invoke_named::<i32, i32>("call1", 0)
+    );
+}
+
+#[test_log::test]
+fn invalid_bulk_instructions() {
+    let validate_wat = |wat: &str| {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        validate(&wasm_bytes).err()
+    };
+
+    assert_eq!(
+        validate_wat(r#"(module (memory 1) (data "") (func (data.drop 1)))"#),
+        Some(Error::InvalidDataIdx(1))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (data "") (func (memory.init 0 (i32.const 0) (i32.const 0) (i32.const 0))))"#
+        ),
+        Some(Error::InvalidMemIdx(0))
+    );
+    assert_eq!(
+        validate_wat(r#"(module (func (memory.fill (i32.const 0) (i32.const 0) (i32.const 0))))"#),
+        Some(Error::InvalidMemIdx(0))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (memory 1) (func (memory.copy (i32.const 0) (i32.const 0) (i64.const 0))))"#
+        ),
+        Some(Error::InvalidValueStackType(Some(wasm::ValType::NumType(
+            wasm::NumType::I64
+        ))))
+    );
+    assert_eq!(
+        validate_wat(r#"(module (table 1 funcref) (func (elem.drop 0)))"#),
+        Some(Error::InvalidElemIdx(0))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (table 1 funcref) (elem funcref) (func (table.init 1 0 (i32.const 0) (i32.const 0) (i32.const 0))))"#
+        ),
+        Some(Error::InvalidTableIdx(1))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (table 1 externref) (elem funcref) (func (table.init 0 (i32.const 0) (i32.const 0) (i32.const 0))))"#
+        ),
+        Some(Error::RefTypeMismatch)
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (table 1 externref) (table 1 funcref) (func (table.copy 0 1 (i32.const 0) (i32.const 0) (i32.const 0))))"#
+        ),
+        Some(Error::RefTypeMismatch)
+    );
+}
+
+/// Data segments can only be referenced from code if there is a data count section
+#[test_log::test]
+fn data_count_required() {
+    #[rustfmt::skip]
+    let wasm_bytes = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        // type section: [] -> []
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+        // function section: one function of type 0
+        0x03, 0x02, 0x01, 0x00,
+        // memory section: one memory with min 1
+        0x05, 0x03, 0x01, 0x00, 0x01,
+        // code section: one function without locals containing `data.drop 0`
+        0x0a, 0x07, 0x01, 0x05, 0x00, 0xfc, 0x09, 0x00, 0x0b,
+        // data section: one passive segment containing a single byte
+        0x0b, 0x04, 0x01, 0x01, 0x01, 0x2a,
+    ];
+    assert_eq!(validate(&wasm_bytes).err(), Some(Error::DataCountMissing));
+
+    // The same module with a data count section announcing the segment
+    let mut valid_bytes = wasm_bytes[..23].to_vec();
+    valid_bytes.extend_from_slice(&[0x0c, 0x01, 0x01]);
+    valid_bytes.extend_from_slice(&wasm_bytes[23..]);
+    let validation_info = validate(&valid_bytes).expect("validation failed");
+    assert!(RuntimeInstance::new(&validation_info).is_ok());
+}
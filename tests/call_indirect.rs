@@ -0,0 +1,196 @@
+use wasm::linker::Linker;
+use wasm::{validate, NumType, RuntimeError, RuntimeInstance, ValType, Value};
+
+/// Dispatches to one of three functions, where the entry at index 2 is a function with a different
+/// signature and index 3 remains uninitialized
+const DISPATCH: &str = r#"
+    (module
+        (type $binop (func (param i32 i32) (result i32)))
+        (table 4 funcref)
+        (elem (i32.const 0) $add $sub $negate)
+        (func $add (type $binop)
+            local.get 0
+            local.get 1
+            i32.add
+        )
+        (func $sub (type $binop)
+            local.get 0
+            local.get 1
+            i32.sub
+        )
+        (func $negate (param i32) (result i32)
+            i32.const 0
+            local.get 0
+            i32.sub
+        )
+        (func (export "dispatch") (param $op i32) (param $x i32) (param $y i32) (result i32)
+            local.get $x
+            local.get $y
+            local.get $op
+            call_indirect (type $binop)
+        )
+    )
+"#;
+
+#[test_log::test]
+fn call_through_table() {
+    let wasm_bytes = wat::parse_str(DISPATCH).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        12,
+        instance
+            .invoke_named::<(i32, i32, i32), i32>("dispatch", (0, 7, 5))
+            .unwrap()
+    );
+    assert_eq!(
+        2,
+        instance
+            .invoke_named::<(i32, i32, i32), i32>("dispatch", (1, 7, 5))
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn trap_on_signature_mismatch() {
+    let wasm_bytes = wat::parse_str(DISPATCH).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        instance
+            .invoke_named::<(i32, i32, i32), i32>("dispatch", (2, 7, 5))
+            .err(),
+        Some(RuntimeError::IndirectCallTypeMismatch)
+    );
+}
+
+#[test_log::test]
+fn trap_on_null_entry() {
+    let wasm_bytes = wat::parse_str(DISPATCH).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        instance
+            .invoke_named::<(i32, i32, i32), i32>("dispatch", (3, 7, 5))
+            .err(),
+        Some(RuntimeError::UninitializedTableElement)
+    );
+}
+
+#[test_log::test]
+fn trap_on_out_of_bounds_index() {
+    let wasm_bytes = wat::parse_str(DISPATCH).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    for op in [4, -1] {
+        assert_eq!(
+            instance
+                .invoke_named::<(i32, i32, i32), i32>("dispatch", (op, 7, 5))
+                .err(),
+            Some(RuntimeError::UndefinedTableElement)
+        );
+    }
+}
+
+/// Element segments with explicit table indices and element expressions, including null references
+/// and imported functions
+#[test_log::test]
+fn element_expressions() {
+    let wat = r#"
+        (module
+            (import "env" "double" (func $double (param i32) (result i32)))
+            (table $unused 1 funcref)
+            (table $funcs 3 funcref)
+            (elem (table $funcs) (i32.const 1) funcref (ref.func $double) (ref.null func))
+            (elem declare func $increment)
+            (elem funcref (ref.func $increment))
+            (func $increment (param i32) (result i32)
+                local.get 0
+                i32.const 1
+                i32.add
+            )
+            (func (export "apply") (param $idx i32) (param $x i32) (result i32)
+                local.get $x
+                local.get $idx
+                call_indirect $funcs (param i32) (result i32)
+            )
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let i32_ty = ValType::NumType(NumType::I32);
+    let mut linker = Linker::new();
+    linker.define_func("env", "double", &[i32_ty], &[i32_ty], |_, params| {
+        let x: u32 = params[0].into();
+        Ok(vec![Value::I32(x * 2)])
+    });
+    let mut instance =
+        RuntimeInstance::new_with_linker(&validation_info, linker).expect("instantiation failed");
+
+    assert_eq!(
+        42,
+        instance
+            .invoke_named::<(i32, i32), i32>("apply", (1, 21))
+            .unwrap()
+    );
+    for idx in [0, 2] {
+        assert_eq!(
+            instance
+                .invoke_named::<(i32, i32), i32>("apply", (idx, 21))
+                .err(),
+            Some(RuntimeError::UninitializedTableElement)
+        );
+    }
+}
+
+#[test_log::test]
+fn element_segment_out_of_bounds() {
+    let wat = r#"
+        (module
+            (table 2 funcref)
+            (elem (i32.const 1) $f $f)
+            (func $f)
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    assert_eq!(
+        RuntimeInstance::new(&validation_info).err(),
+        Some(RuntimeError::TableAccessOutOfBounds)
+    );
+}
+
+#[test_log::test]
+fn element_segment_invalid_indices() {
+    let invalid_func = r#"
+        (module
+            (table 2 funcref)
+            (elem (i32.const 0) func 1)
+            (func $f)
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(invalid_func).unwrap();
+    assert_eq!(
+        validate(&wasm_bytes).err(),
+        Some(wasm::Error::InvalidFuncIdx(1))
+    );
+
+    let invalid_table = r#"
+        (module
+            (table 2 funcref)
+            (elem (table 1) (i32.const 0) func $f)
+            (func $f)
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(invalid_table).unwrap();
+    assert_eq!(
+        validate(&wasm_bytes).err(),
+        Some(wasm::Error::InvalidTableIdx(1))
+    );
+}
@@ -0,0 +1,469 @@
+use wasm::{validate, Error, RuntimeError, RuntimeInstance};
+
+const CONVERSIONS_WAT: &str = r#"
+    (module
+        (func (export "i32.wrap_i64") (param i64) (result i32) (i32.wrap_i64 (local.get 0)))
+        (func (export "i32.trunc_f32_s") (param f32) (result i32) (i32.trunc_f32_s (local.get 0)))
+        (func (export "i32.trunc_f32_u") (param f32) (result i32) (i32.trunc_f32_u (local.get 0)))
+        (func (export "i32.trunc_f64_s") (param f64) (result i32) (i32.trunc_f64_s (local.get 0)))
+        (func (export "i32.trunc_f64_u") (param f64) (result i32) (i32.trunc_f64_u (local.get 0)))
+        (func (export "i64.extend_i32_s") (param i32) (result i64) (i64.extend_i32_s (local.get 0)))
+        (func (export "i64.extend_i32_u") (param i32) (result i64) (i64.extend_i32_u (local.get 0)))
+        (func (export "i64.trunc_f32_s") (param f32) (result i64) (i64.trunc_f32_s (local.get 0)))
+        (func (export "i64.trunc_f32_u") (param f32) (result i64) (i64.trunc_f32_u (local.get 0)))
+        (func (export "i64.trunc_f64_s") (param f64) (result i64) (i64.trunc_f64_s (local.get 0)))
+        (func (export "i64.trunc_f64_u") (param f64) (result i64) (i64.trunc_f64_u (local.get 0)))
+        (func (export "f32.demote_f64") (param f64) (result f32) (f32.demote_f64 (local.get 0)))
+        (func (export "f64.convert_i32_s") (param i32) (result f64) (f64.convert_i32_s (local.get 0)))
+        (func (export "f64.convert_i32_u") (param i32) (result f64) (f64.convert_i32_u (local.get 0)))
+        (func (export "f64.convert_i64_s") (param i64) (result f64) (f64.convert_i64_s (local.get 0)))
+        (func (export "f64.convert_i64_u") (param i64) (result f64) (f64.convert_i64_u (local.get 0)))
+        (func (export "f64.promote_f32") (param f32) (result f64) (f64.promote_f32 (local.get 0)))
+        (func (export "i32.reinterpret_f32") (param f32) (result i32) (i32.reinterpret_f32 (local.get 0)))
+        (func (export "i64.reinterpret_f64") (param f64) (result i64) (i64.reinterpret_f64 (local.get 0)))
+        (func (export "f64.reinterpret_i64") (param i64) (result f64) (f64.reinterpret_i64 (local.get 0)))
+    )
+"#;
+
+#[test_log::test]
+fn wrap_and_extend() {
+    let wasm_bytes = wat::parse_str(CONVERSIONS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let test_cases: Vec<(i64, i32)> = vec![
+        (-1, -1),
+        (0x1_0000_0000, 0),
+        (0x1234_5678_9ABC_DEF0, 0x9ABC_DEF0_u32 as i32),
+        (0x7FFF_FFFF, 0x7FFF_FFFF),
+    ];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i32.wrap_i64", input).unwrap()
+        );
+    }
+
+    assert_eq!(
+        -1_i64,
+        instance.invoke_named("i64.extend_i32_s", -1_i32).unwrap()
+    );
+    assert_eq!(
+        0xFFFF_FFFF_i64,
+        instance.invoke_named("i64.extend_i32_u", -1_i32).unwrap()
+    );
+    assert_eq!(
+        i32::MIN as i64,
+        instance.invoke_named("i64.extend_i32_s", i32::MIN).unwrap()
+    );
+    assert_eq!(
+        0x8000_0000_i64,
+        instance.invoke_named("i64.extend_i32_u", i32::MIN).unwrap()
+    );
+}
+
+/// Truncations round towards zero and trap outside of the integer's range
+#[test_log::test]
+fn truncate_to_i32() {
+    let wasm_bytes = wat::parse_str(CONVERSIONS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let test_cases: Vec<(f32, i32)> = vec![
+        (-0.0, 0),
+        (1.9, 1),
+        (-1.9, -1),
+        (2147483520.0, 2147483520),
+        (-2147483648.0, i32::MIN),
+    ];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i32.trunc_f32_s", input).unwrap()
+        );
+    }
+    for input in [2147483648.0, -2147483904.0, f32::INFINITY] {
+        assert_eq!(
+            Err(RuntimeError::UnrepresentableResult),
+            instance.invoke_named::<f32, i32>("i32.trunc_f32_s", input)
+        );
+    }
+
+    assert_eq!(
+        0,
+        instance
+            .invoke_named::<f32, i32>("i32.trunc_f32_u", -0.9)
+            .unwrap()
+    );
+    assert_eq!(
+        0xFFFF_FF00_u32 as i32,
+        instance
+            .invoke_named::<f32, i32>("i32.trunc_f32_u", 4294967040.0)
+            .unwrap()
+    );
+    for input in [-1.0, 4294967296.0] {
+        assert_eq!(
+            Err(RuntimeError::UnrepresentableResult),
+            instance.invoke_named::<f32, i32>("i32.trunc_f32_u", input)
+        );
+    }
+
+    let test_cases: Vec<(f64, i32)> = vec![
+        (2147483647.9, i32::MAX),
+        (-2147483648.9, i32::MIN),
+        (-1.5, -1),
+    ];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i32.trunc_f64_s", input).unwrap()
+        );
+    }
+    for input in [2147483648.0, -2147483649.0, f64::NEG_INFINITY] {
+        assert_eq!(
+            Err(RuntimeError::UnrepresentableResult),
+            instance.invoke_named::<f64, i32>("i32.trunc_f64_s", input)
+        );
+    }
+
+    assert_eq!(
+        -1,
+        instance
+            .invoke_named::<f64, i32>("i32.trunc_f64_u", 4294967295.9)
+            .unwrap()
+    );
+    for input in [-1.0, 4294967296.0] {
+        assert_eq!(
+            Err(RuntimeError::UnrepresentableResult),
+            instance.invoke_named::<f64, i32>("i32.trunc_f64_u", input)
+        );
+    }
+}
+
+#[test_log::test]
+fn truncate_to_i64() {
+    let wasm_bytes = wat::parse_str(CONVERSIONS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        i64::MIN,
+        instance
+            .invoke_named::<f32, i64>("i64.trunc_f32_s", -9223372036854775808.0)
+            .unwrap()
+    );
+    assert_eq!(
+        -4,
+        instance
+            .invoke_named::<f32, i64>("i64.trunc_f32_s", -4.5)
+            .unwrap()
+    );
+    for input in [9223372036854775808.0, -9223373136366403584.0] {
+        assert_eq!(
+            Err(RuntimeError::UnrepresentableResult),
+            instance.invoke_named::<f32, i64>("i64.trunc_f32_s", input)
+        );
+    }
+
+    assert_eq!(
+        0xFFFF_FF00_0000_0000_u64 as i64,
+        instance
+            .invoke_named::<f32, i64>("i64.trunc_f32_u", 18446742974197923840.0)
+            .unwrap()
+    );
+    for input in [-1.0, 18446744073709551616.0] {
+        assert_eq!(
+            Err(RuntimeError::UnrepresentableResult),
+            instance.invoke_named::<f32, i64>("i64.trunc_f32_u", input)
+        );
+    }
+
+    assert_eq!(
+        9223372036854774784,
+        instance
+            .invoke_named::<f64, i64>("i64.trunc_f64_s", 9223372036854774784.0)
+            .unwrap()
+    );
+    for input in [9223372036854775808.0, -9223372036854777856.0] {
+        assert_eq!(
+            Err(RuntimeError::UnrepresentableResult),
+            instance.invoke_named::<f64, i64>("i64.trunc_f64_s", input)
+        );
+    }
+
+    assert_eq!(
+        0xFFFF_FFFF_FFFF_F800_u64 as i64,
+        instance
+            .invoke_named::<f64, i64>("i64.trunc_f64_u", 18446744073709549568.0)
+            .unwrap()
+    );
+    for input in [-1.0, 18446744073709551616.0] {
+        assert_eq!(
+            Err(RuntimeError::UnrepresentableResult),
+            instance.invoke_named::<f64, i64>("i64.trunc_f64_u", input)
+        );
+    }
+}
+
+/// NaN can not be converted to any integer
+#[test_log::test]
+fn truncate_nan() {
+    let wasm_bytes = wat::parse_str(CONVERSIONS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    for name in ["i32.trunc_f32_s", "i32.trunc_f32_u"] {
+        assert_eq!(
+            Err(RuntimeError::BadConversionToInteger),
+            instance.invoke_named::<f32, i32>(name, f32::NAN)
+        );
+    }
+    for name in ["i32.trunc_f64_s", "i32.trunc_f64_u"] {
+        assert_eq!(
+            Err(RuntimeError::BadConversionToInteger),
+            instance.invoke_named::<f64, i32>(name, -f64::NAN)
+        );
+    }
+    for name in ["i64.trunc_f32_s", "i64.trunc_f32_u"] {
+        assert_eq!(
+            Err(RuntimeError::BadConversionToInteger),
+            instance.invoke_named::<f32, i64>(name, f32::NAN)
+        );
+    }
+    for name in ["i64.trunc_f64_s", "i64.trunc_f64_u"] {
+        assert_eq!(
+            Err(RuntimeError::BadConversionToInteger),
+            instance.invoke_named::<f64, i64>(name, f64::NAN)
+        );
+    }
+}
+
+#[test_log::test]
+fn float_conversions() {
+    let wasm_bytes = wat::parse_str(CONVERSIONS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        -2147483648.0_f64,
+        instance
+            .invoke_named("f64.convert_i32_s", i32::MIN)
+            .unwrap()
+    );
+    assert_eq!(
+        2147483648.0_f64,
+        instance
+            .invoke_named("f64.convert_i32_u", i32::MIN)
+            .unwrap()
+    );
+    assert_eq!(
+        -1.0_f64,
+        instance.invoke_named("f64.convert_i64_s", -1_i64).unwrap()
+    );
+    assert_eq!(
+        18446744073709551616.0_f64,
+        instance.invoke_named("f64.convert_i64_u", -1_i64).unwrap()
+    );
+    // Rounds to the nearest representable value, with ties to even
+    assert_eq!(
+        9007199254740992.0_f64,
+        instance
+            .invoke_named("f64.convert_i64_s", 9007199254740993_i64)
+            .unwrap()
+    );
+
+    assert_eq!(
+        1.5_f32,
+        instance.invoke_named("f32.demote_f64", 1.5_f64).unwrap()
+    );
+    assert_eq!(
+        f32::INFINITY,
+        instance.invoke_named("f32.demote_f64", 1e300_f64).unwrap()
+    );
+    assert_eq!(
+        0.1_f32,
+        instance.invoke_named("f32.demote_f64", 0.1_f64).unwrap()
+    );
+    assert!(instance
+        .invoke_named::<f64, f32>("f32.demote_f64", f64::NAN)
+        .unwrap()
+        .is_nan());
+
+    assert_eq!(
+        0.1_f32 as f64,
+        instance.invoke_named("f64.promote_f32", 0.1_f32).unwrap()
+    );
+    assert_eq!(
+        f64::NEG_INFINITY,
+        instance
+            .invoke_named("f64.promote_f32", f32::NEG_INFINITY)
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn reinterpret() {
+    let wasm_bytes = wat::parse_str(CONVERSIONS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        0x3FC0_0000,
+        instance
+            .invoke_named::<f32, i32>("i32.reinterpret_f32", 1.5)
+            .unwrap()
+    );
+    assert_eq!(
+        i32::MIN,
+        instance
+            .invoke_named::<f32, i32>("i32.reinterpret_f32", -0.0)
+            .unwrap()
+    );
+    assert_eq!(
+        0x3FF8_0000_0000_0000,
+        instance
+            .invoke_named::<f64, i64>("i64.reinterpret_f64", 1.5)
+            .unwrap()
+    );
+    assert_eq!(
+        -2.0_f64,
+        instance
+            .invoke_named("f64.reinterpret_i64", 0xC000_0000_0000_0000_u64 as i64)
+            .unwrap()
+    );
+
+    // The bits of NaNs are preserved
+    let nan_bits = 0x7FF4_0000_0000_0001_u64 as i64;
+    let nan: f64 = instance
+        .invoke_named("f64.reinterpret_i64", nan_bits)
+        .unwrap();
+    assert_eq!(nan_bits, nan.to_bits() as i64);
+}
+
+const TRUNC_SAT_WAT: &str = r#"
+    (module
+        (func (export "i32.trunc_sat_f32_s") (param f32) (result i32) (i32.trunc_sat_f32_s (local.get 0)))
+        (func (export "i32.trunc_sat_f32_u") (param f32) (result i32) (i32.trunc_sat_f32_u (local.get 0)))
+        (func (export "i32.trunc_sat_f64_s") (param f64) (result i32) (i32.trunc_sat_f64_s (local.get 0)))
+        (func (export "i32.trunc_sat_f64_u") (param f64) (result i32) (i32.trunc_sat_f64_u (local.get 0)))
+        (func (export "i64.trunc_sat_f32_s") (param f32) (result i64) (i64.trunc_sat_f32_s (local.get 0)))
+        (func (export "i64.trunc_sat_f32_u") (param f32) (result i64) (i64.trunc_sat_f32_u (local.get 0)))
+        (func (export "i64.trunc_sat_f64_s") (param f64) (result i64) (i64.trunc_sat_f64_s (local.get 0)))
+        (func (export "i64.trunc_sat_f64_u") (param f64) (result i64) (i64.trunc_sat_f64_u (local.get 0)))
+    )
+"#;
+
+/// Saturating truncations clamp to the integer's range and convert NaN to 0 instead of trapping
+#[test_log::test]
+fn saturating_truncations() {
+    let wasm_bytes = wat::parse_str(TRUNC_SAT_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let test_cases: Vec<(f32, i32)> = vec![
+        (-1.9, -1),
+        (3e9, i32::MAX),
+        (-3e9, i32::MIN),
+        (f32::INFINITY, i32::MAX),
+        (f32::NAN, 0),
+    ];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i32.trunc_sat_f32_s", input).unwrap()
+        );
+    }
+
+    let test_cases: Vec<(f32, i32)> = vec![
+        (-1.9, 0),
+        (3e9, 3_000_000_000_u32 as i32),
+        (5e9, -1),
+        (f32::NEG_INFINITY, 0),
+        (f32::NAN, 0),
+    ];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i32.trunc_sat_f32_u", input).unwrap()
+        );
+    }
+
+    let test_cases: Vec<(f64, i32)> = vec![
+        (2147483647.9, i32::MAX),
+        (2147483648.0, i32::MAX),
+        (-2147483649.0, i32::MIN),
+        (-f64::NAN, 0),
+    ];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i32.trunc_sat_f64_s", input).unwrap()
+        );
+    }
+
+    let test_cases: Vec<(f64, i32)> = vec![(4294967295.9, -1), (1e100, -1), (-0.5, 0)];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i32.trunc_sat_f64_u", input).unwrap()
+        );
+    }
+
+    let test_cases: Vec<(f32, i64)> = vec![(1e19, i64::MAX), (-1e19, i64::MIN), (-4.5, -4)];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i64.trunc_sat_f32_s", input).unwrap()
+        );
+    }
+
+    let test_cases: Vec<(f32, i64)> = vec![(1e20, -1), (-1e20, 0), (f32::NAN, 0)];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i64.trunc_sat_f32_u", input).unwrap()
+        );
+    }
+
+    let test_cases: Vec<(f64, i64)> = vec![
+        (9223372036854775808.0, i64::MAX),
+        (-9223372036854777856.0, i64::MIN),
+        (f64::NAN, 0),
+    ];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i64.trunc_sat_f64_s", input).unwrap()
+        );
+    }
+
+    let test_cases: Vec<(f64, i64)> = vec![
+        (18446744073709549568.0, 0xFFFF_FFFF_FFFF_F800_u64 as i64),
+        (f64::INFINITY, -1),
+        (-1.0, 0),
+    ];
+    for (input, expected) in test_cases {
+        assert_eq!(
+            expected,
+            instance.invoke_named("i64.trunc_sat_f64_u", input).unwrap()
+        );
+    }
+}
+
+#[test_log::test]
+fn invalid_multi_byte_instruction() {
+    // Replace the sub-opcode of the saturating truncation with an unknown one
+    let wat = r#"(module (func (result i32) f32.const 0 i32.trunc_sat_f32_s))"#;
+    let mut wasm_bytes = wat::parse_str(wat).unwrap();
+    let sub_opcode = wasm_bytes
+        .windows(2)
+        .position(|window| window == [0xFC, 0x00])
+        .expect("the module to contain i32.trunc_sat_f32_s")
+        + 1;
+    wasm_bytes[sub_opcode] = 0x7F;
+
+    assert_eq!(
+        validate(&wasm_bytes).err(),
+        Some(Error::InvalidMultiByteInstr(0xFC, 0x7F))
+    );
+}
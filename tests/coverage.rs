@@ -0,0 +1,312 @@
+#![cfg(feature = "hooks")]
+
+use std::collections::BTreeSet;
+
+use wasm::coverage::{
+    CoverageMap, Decision, DecisionCoverage, DecisionKind, DecisionMap, McdcCoverage,
+    StatementCoverage,
+};
+use wasm::hooks::{BranchOutcome, Location};
+use wasm::{validate, RuntimeInstance, Value};
+
+const ABS_WAT: &str = r#"
+    (module
+        (func (export "abs") (param i32) (result i32)
+            local.get 0         ;; offset 0
+            i32.const 0         ;; offset 2
+            i32.lt_s            ;; offset 4
+            if (result i32)     ;; offset 5
+                i32.const 0     ;; offset 7
+                local.get 0     ;; offset 9
+                i32.sub         ;; offset 11
+            else                ;; offset 12
+                local.get 0     ;; offset 13
+            end                 ;; offset 15
+        )                       ;; offset 16
+    )
+"#;
+
+fn at(offset: usize) -> Location {
+    Location {
+        func_idx: 0,
+        offset,
+    }
+}
+
+fn covered_offsets(map: &CoverageMap) -> Vec<usize> {
+    map.func(0).unwrap().keys().copied().collect()
+}
+
+#[test_log::test]
+fn statement_coverage() {
+    let wasm_bytes = wat::parse_str(ABS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_hooks(&validation_info, StatementCoverage::default())
+            .expect("instantiation failed");
+    assert!(instance.hook_set.map().is_empty());
+
+    assert_eq!(5, instance.invoke_named::<i32, i32>("abs", -5).unwrap());
+    let map = instance.hook_set.map();
+    // The else-branch was not executed, `else` and `end` are no statements
+    assert_eq!(vec![0, 2, 4, 5, 7, 9, 11], covered_offsets(map));
+    assert!(!map.is_covered(at(13)));
+    assert!(!map.is_covered(at(12)));
+    assert_eq!(None, map.func(1));
+
+    // Hits are counted
+    assert_eq!(3, instance.invoke_named::<i32, i32>("abs", -3).unwrap());
+    let map = instance.hook_set.take_map();
+    assert_eq!(2, map.hits(at(7)));
+    assert_eq!(0, map.hits(at(13)));
+    assert!(instance.hook_set.map().is_empty());
+}
+
+/// Maps recorded by different instances of the same module can be merged
+#[test_log::test]
+fn merge_coverage_maps() {
+    let wasm_bytes = wat::parse_str(ABS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let [negative, positive] = [-5, 5].map(|x| {
+        let mut instance =
+            RuntimeInstance::new_with_hooks(&validation_info, StatementCoverage::default())
+                .expect("instantiation failed");
+        assert_eq!(5, instance.invoke_named::<i32, i32>("abs", x).unwrap());
+        instance.hook_set.take_map()
+    });
+    assert_eq!(vec![0, 2, 4, 5, 13], covered_offsets(&positive));
+
+    let mut merged = negative.clone();
+    merged.merge(&positive);
+    assert_eq!(vec![0, 2, 4, 5, 7, 9, 11, 13], covered_offsets(&merged));
+    assert_eq!(8, merged.len());
+    assert_eq!(2, merged.hits(at(5)));
+    assert_eq!(1, merged.hits(at(13)));
+
+    // Merging is commutative
+    let mut reverse_merged = positive;
+    reverse_merged.merge(&negative);
+    assert_eq!(merged, reverse_merged);
+
+    // Maps can be exported and restored as pairs of locations and hits
+    let exported = merged.iter().collect::<Vec<(Location, u64)>>();
+    assert_eq!((at(0), 2), exported[0]);
+    assert_eq!(merged, exported.into_iter().collect::<CoverageMap>());
+}
+
+const DECISIONS_WAT: &str = r#"
+    (module
+        (func (export "in_range") (param i32) (result i32)
+            local.get 0         ;; offset 0
+            i32.const 0         ;; offset 2
+            i32.ge_s            ;; offset 4
+            local.get 0         ;; offset 5
+            i32.const 10        ;; offset 7
+            i32.lt_s            ;; offset 9
+            i32.and             ;; offset 10
+            if (result i32)     ;; offset 11
+                i32.const 1     ;; offset 13
+            else                ;; offset 15
+                i32.const 0     ;; offset 16
+            end                 ;; offset 18
+        )                       ;; offset 19
+        (func (export "clamp") (param i32) (result i32)
+            local.get 0
+            i32.const 0
+            local.get 0
+            i32.const 0
+            i32.gt_s
+            select
+        )
+        (func (export "classify") (param i32) (result i32)
+            v128.const i64x2 1 2
+            f64.const 1.5
+            i64.const -1
+            drop
+            drop
+            drop
+            block
+                block
+                    local.get 0
+                    br_table 0 1
+                end
+                i32.const 10
+                return
+            end
+            i32.const 20
+        )
+        (func (export "count") (param i32) (result i32)
+            loop
+                local.get 0
+                i32.const 1
+                i32.sub
+                local.tee 0
+                br_if 0
+            end
+            local.get 0
+        )
+    )
+"#;
+
+fn decision_at(func_idx: usize, offset: usize, kind: DecisionKind) -> Decision {
+    Decision {
+        location: Location { func_idx, offset },
+        kind,
+    }
+}
+
+#[test_log::test]
+fn decisions_are_found() {
+    let wasm_bytes = wat::parse_str(DECISIONS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        vec![
+            decision_at(0, 11, DecisionKind::If),
+            decision_at(1, 9, DecisionKind::Select),
+            // the immediates of the vector and float constants before it are skipped
+            decision_at(2, 38, DecisionKind::BrTable { num_labels: 1 }),
+            decision_at(3, 9, DecisionKind::BrIf),
+        ],
+        instance.decisions()
+    );
+}
+
+#[test_log::test]
+fn decision_coverage() {
+    use BranchOutcome::*;
+
+    let wasm_bytes = wat::parse_str(DECISIONS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_hooks(&validation_info, DecisionCoverage::default())
+            .expect("instantiation failed");
+    let decisions = instance.decisions();
+
+    assert_eq!(1, instance.invoke_named::<i32, i32>("in_range", 5).unwrap());
+    assert_eq!(0, instance.invoke_named::<i32, i32>("clamp", -3).unwrap());
+    assert_eq!(
+        10,
+        instance.invoke_named::<i32, i32>("classify", 0).unwrap()
+    );
+    assert_eq!(
+        20,
+        instance.invoke_named::<i32, i32>("classify", 7).unwrap()
+    );
+    assert_eq!(0, instance.invoke_named::<i32, i32>("count", 3).unwrap());
+
+    let map = instance.hook_set.map();
+    assert_eq!(1, map.hits(at(11), Taken));
+    assert_eq!(0, map.hits(at(11), NotTaken));
+    // the out-of-range index selects the default label
+    assert_eq!(
+        1,
+        map.hits(
+            Location {
+                func_idx: 2,
+                offset: 38
+            },
+            Table(1)
+        )
+    );
+    assert_eq!(
+        Some(&[(Taken, 2), (NotTaken, 1)].into_iter().collect()),
+        map.outcomes(Location {
+            func_idx: 3,
+            offset: 9
+        })
+    );
+
+    let report = map.report(&decisions);
+    assert!(!report.is_complete());
+    assert_eq!(8, report.num_outcomes());
+    assert_eq!(6, report.num_covered_outcomes());
+    assert_eq!(vec![NotTaken], report.func(0)[0].missing);
+    assert_eq!(vec![Taken], report.func(1)[0].missing);
+    assert!(report.func(2).is_empty());
+    assert!(report.func(3).is_empty());
+    assert_eq!(
+        "6 of 8 decision outcomes covered\n\
+         function 0:\n  if at offset 11: missing [NotTaken]\n\
+         function 1:\n  select at offset 9: missing [Taken]\n",
+        report.to_string()
+    );
+
+    // Merging a map that covers the missing outcomes completes the report
+    let mut other = RuntimeInstance::new_with_hooks(&validation_info, DecisionCoverage::default())
+        .expect("instantiation failed");
+    assert_eq!(0, other.invoke_named::<i32, i32>("in_range", 10).unwrap());
+    assert_eq!(3, other.invoke_named::<i32, i32>("clamp", 3).unwrap());
+    let mut merged = instance.hook_set.take_map();
+    merged.merge(other.hook_set.map());
+    assert!(merged.report(&decisions).is_complete());
+
+    // Maps can be exported and restored as triples of locations, outcomes and hits
+    let exported = merged.iter().collect::<Vec<_>>();
+    assert_eq!((at(11), Taken, 1), exported[0]);
+    assert_eq!(merged, exported.into_iter().collect::<DecisionMap>());
+}
+
+#[test_log::test]
+fn mcdc_coverage() {
+    let wasm_bytes = wat::parse_str(DECISIONS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new_with_hooks(&validation_info, McdcCoverage::default())
+        .expect("instantiation failed");
+    let (decision, lower_bound, upper_bound) = (at(11), at(4), at(9));
+
+    // Both conditions are true
+    assert_eq!(1, instance.invoke_named::<i32, i32>("in_range", 5).unwrap());
+    assert_eq!(1, instance.invoke_named::<i32, i32>("in_range", 6).unwrap());
+    let test_vectors = instance.hook_set.map().test_vectors(decision);
+    assert_eq!(1, test_vectors.len());
+    assert_eq!(2, test_vectors[0].hits);
+    assert_eq!(BranchOutcome::Taken, test_vectors[0].outcome);
+    // the operands of the first evaluation are kept
+    assert_eq!(
+        vec![Value::I32(5), Value::I32(0)],
+        test_vectors[0].conditions[0].operands
+    );
+    assert_eq!(Some(true), test_vectors[0].result_of(upper_bound));
+
+    // Only the lower bound changes, which changes the outcome
+    assert_eq!(
+        0,
+        instance.invoke_named::<i32, i32>("in_range", -1).unwrap()
+    );
+    let map = instance.hook_set.map();
+    assert_eq!(
+        BTreeSet::from([lower_bound, upper_bound]),
+        map.conditions(decision)
+    );
+    assert!(map.is_independent(decision, lower_bound));
+    assert!(!map.is_independent(decision, upper_bound));
+
+    // Only the upper bound changes, which changes the outcome
+    assert_eq!(
+        0,
+        instance.invoke_named::<i32, i32>("in_range", 20).unwrap()
+    );
+    let map = instance.hook_set.take_map();
+    let (a, b) = map.independence_pair(decision, upper_bound).unwrap();
+    assert_eq!(Some(true), a.result_of(upper_bound));
+    assert_eq!(Some(false), b.result_of(upper_bound));
+    assert_eq!(
+        vec![Value::I32(20), Value::I32(10)],
+        b.conditions[1].operands
+    );
+    assert!(map.is_independent(decision, lower_bound));
+
+    // Each decision has its own test vectors
+    assert_eq!(0, instance.invoke_named::<i32, i32>("clamp", -3).unwrap());
+    let map = instance.hook_set.map();
+    let test_vectors = map.test_vectors(Location {
+        func_idx: 1,
+        offset: 9,
+    });
+    assert_eq!(1, test_vectors[0].conditions.len());
+    assert_eq!(BranchOutcome::NotTaken, test_vectors[0].outcome);
+    assert!(map.test_vectors(decision).is_empty());
+}
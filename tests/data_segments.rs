@@ -0,0 +1,103 @@
+use wasm::{validate, Error, RuntimeError, RuntimeInstance};
+
+#[test_log::test]
+fn active_data_segments() {
+    let wat = r#"
+        (module
+            (memory 1)
+            (data (i32.const 16) "\01\02\03\04")
+            (data (memory 0) (i32.const 18) "\ff\ff\ff\ff")
+            (data "passive segments are not copied")
+            (func (export "load") (param $addr i32) (result i32)
+                local.get $addr
+                i32.load
+            )
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    // Later segments overwrite earlier ones
+    assert_eq!(
+        0xFFFF_0201_u32 as i32,
+        instance.invoke_named::<i32, i32>("load", 16).unwrap()
+    );
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("load", 18).unwrap());
+    assert_eq!(0, instance.invoke_named::<i32, i32>("load", 0).unwrap());
+    assert_eq!(0, instance.invoke_named::<i32, i32>("load", 22).unwrap());
+}
+
+/// A segment at the very end of the memory fits, one byte further is an instantiation error
+#[test_log::test]
+fn data_segment_out_of_bounds() {
+    let fits = r#"
+        (module
+            (memory 1)
+            (data (i32.const 65532) "\01\02\03\04")
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(fits).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    assert!(RuntimeInstance::new(&validation_info).is_ok());
+
+    let out_of_bounds = r#"
+        (module
+            (memory 1)
+            (data (i32.const 65533) "\01\02\03\04")
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(out_of_bounds).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    assert_eq!(
+        RuntimeInstance::new(&validation_info).err(),
+        Some(RuntimeError::MemoryOutOfBounds)
+    );
+
+    // Negative offsets are interpreted as unsigned
+    let negative_offset = r#"
+        (module
+            (memory 1)
+            (data (i32.const -1) "\01")
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(negative_offset).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    assert_eq!(
+        RuntimeInstance::new(&validation_info).err(),
+        Some(RuntimeError::MemoryOutOfBounds)
+    );
+}
+
+#[test_log::test]
+fn data_segment_without_memory() {
+    let wat = r#"
+        (module
+            (data (i32.const 0) "\01")
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    assert_eq!(validate(&wasm_bytes).err(), Some(Error::InvalidMemIdx(0)));
+}
+
+#[test_log::test]
+fn data_count_mismatch() {
+    // A module with a memory, a data count section announcing two segments and a single data
+    // segment
+    #[rustfmt::skip]
+    let wasm_bytes = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        // memory section: one memory with min 1
+        0x05, 0x03, 0x01, 0x00, 0x01,
+        // data count section: 2
+        0x0c, 0x01, 0x02,
+        // data section: one passive segment containing a single byte
+        0x0b, 0x04, 0x01, 0x01, 0x01, 0x2a,
+    ];
+    assert_eq!(validate(&wasm_bytes).err(), Some(Error::DataCountMismatch));
+
+    let mut valid_bytes = wasm_bytes;
+    valid_bytes[15] = 0x01;
+    let validation_info = validate(&valid_bytes).expect("validation failed");
+    assert!(RuntimeInstance::new(&validation_info).is_ok());
+}
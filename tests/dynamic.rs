@@ -38,3 +38,41 @@ fn dynamic_add() {
         .expect("invocation failed");
     assert_eq!(vec![Value::I32(-5i32 as u32)], res);
 }
+
+/// Invocations of missing functions or with mismatching types are reported as errors
+#[test_log::test]
+fn dynamic_invalid_invocations() {
+    use wasm::{validate, RuntimeError, RuntimeInstance};
+    use wasm::{ValType, Value};
+
+    let wat = r#"
+    (module
+        (func (export "add") (param $x i32) (param $y i32) (result i32)
+            local.get $x
+            local.get $y
+            i32.add)
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let i32_ty = ValType::NumType(NumType::I32);
+    assert_eq!(
+        instance.invoke_dynamic(1, vec![Value::I32(1), Value::I32(2)], &[i32_ty]),
+        Err(RuntimeError::FunctionNotFound)
+    );
+    assert_eq!(
+        instance.invoke_dynamic(0, vec![Value::I32(1), Value::I64(2)], &[i32_ty]),
+        Err(RuntimeError::FunctionTypeMismatch)
+    );
+    assert_eq!(
+        instance.invoke_dynamic(0, vec![Value::I32(1)], &[i32_ty]),
+        Err(RuntimeError::FunctionTypeMismatch)
+    );
+    assert_eq!(
+        instance.invoke_dynamic(0, vec![Value::I32(1), Value::I32(2)], &[]),
+        Err(RuntimeError::FunctionTypeMismatch)
+    );
+}
@@ -1,3 +1,6 @@
+// Float literals in these tests are deliberate inputs, not approximations of well-known constants
+#![allow(clippy::approx_constant, clippy::excessive_precision)]
+
 use core::f32;
 
 use wasm::{validate, RuntimeInstance};
@@ -22,7 +25,7 @@ pub fn f32_const() {
     assert_eq!(3.14159274_f32, instance.invoke_func(0, ()).unwrap());
 }
 
-const WAT_2_ARGS_RETURN_I32: &'static str = r#"
+const WAT_2_ARGS_RETURN_I32: &str = r#"
     (module
         (func (export "f32_{{0}}") (param $x f32) (param $y f32) (result i32)
             local.get $x
@@ -117,7 +120,7 @@ pub fn f32_ge() {
     assert_eq!(1, instance.invoke_func(0, (1.0_f32, 1.0_f32)).unwrap());
 }
 
-const WAT_1_ARG_RETURN_F32: &'static str = r#"
+const WAT_1_ARG_RETURN_F32: &str = r#"
     (module
       (func (export "f32_{{0}}") (param $x f32) (result f32)
           local.get $x
@@ -273,7 +276,7 @@ pub fn f32_sqrt() {
         .is_nan());
 }
 
-const WAT_2_ARGS_RETURN_F32: &'static str = r#"
+const WAT_2_ARGS_RETURN_F32: &str = r#"
     (module
       (func (export "f32_{{0}}") (param $x f32) (param $y f32) (result f32)
           local.get $x
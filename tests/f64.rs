@@ -1,3 +1,6 @@
+// Float literals in these tests are deliberate inputs, not approximations of well-known constants
+#![allow(clippy::approx_constant, clippy::excessive_precision)]
+
 use core::f64;
 
 use wasm::{validate, RuntimeInstance};
@@ -0,0 +1,162 @@
+use wasm::fuel::{ExecutionOutcome, FuelCostTable};
+use wasm::{validate, RuntimeInstance};
+
+const ADD_ONE: &str = r#"
+    (module
+        (func (export "add_one") (param $x i32) (result i32)
+            local.get $x
+            i32.const 1
+            i32.add
+        )
+    )
+"#;
+
+/// An invocation with enough fuel yields the function's results, while one with too little fuel
+/// halts. `add_one` consists of four instructions, including the final `end`.
+#[test_log::test]
+fn fuel_suffices_exactly() {
+    let wasm_bytes = wat::parse_str(ADD_ONE).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        ExecutionOutcome::Finished(42),
+        instance.invoke_named_with_fuel("add_one", 41, 4).unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::Finished(42),
+        instance
+            .invoke_named_with_fuel("add_one", 41, 1000)
+            .unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::<i32>::OutOfFuel,
+        instance.invoke_named_with_fuel("add_one", 41, 3).unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::<i32>::OutOfFuel,
+        instance.invoke_named_with_fuel("add_one", 41, 0).unwrap()
+    );
+}
+
+/// Instructions consume fuel according to the instance's cost table
+#[test_log::test]
+fn custom_fuel_costs() {
+    let wasm_bytes = wat::parse_str(ADD_ONE).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut fuel_cost_table = FuelCostTable::uniform(0);
+    // i32.add
+    fuel_cost_table.set_cost(0x6A, 10);
+    instance.fuel_cost_table = fuel_cost_table;
+
+    assert_eq!(
+        ExecutionOutcome::Finished(42),
+        instance.invoke_named_with_fuel("add_one", 41, 10).unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::<i32>::OutOfFuel,
+        instance.invoke_named_with_fuel("add_one", 41, 9).unwrap()
+    );
+}
+
+/// A guest that never terminates still yields back to the host once its fuel is used up
+#[test_log::test]
+fn infinite_loop_runs_out_of_fuel() {
+    let wat = r#"
+    (module
+        (func $spin (param $x i32) (result i32)
+            (loop $forever
+                local.get $x
+                i32.const 1
+                i32.add
+                local.set $x
+                br $forever
+            )
+            local.get $x
+        )
+        (func (export "spin_nested") (param $x i32) (result i32)
+            local.get $x
+            call $spin
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        ExecutionOutcome::<i32>::OutOfFuel,
+        instance.invoke_func_with_fuel(0, 0, 100_000).unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::<i32>::OutOfFuel,
+        instance
+            .invoke_named_with_fuel("spin_nested", 0, 100_000)
+            .unwrap()
+    );
+}
+
+const PREFIXED: &str = r#"
+    (module
+        (memory 1)
+        (func (export "fill") (param $n i32)
+            (memory.fill (i32.const 0) (i32.const 7) (local.get $n))
+        )
+        (func (export "add_lanes") (result i32)
+            (i32x4.extract_lane 0
+                (i32x4.add (i32x4.splat (i32.const 1)) (i32x4.splat (i32.const 2))))
+        )
+    )
+"#;
+
+/// Instructions with a prefix byte have individual costs, and bulk instructions consume fuel for
+/// each byte they access
+#[test_log::test]
+fn prefixed_fuel_costs() {
+    let wasm_bytes = wat::parse_str(PREFIXED).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut fuel_cost_table = FuelCostTable::uniform(1);
+    // memory.fill
+    fuel_cost_table.set_prefixed_cost(0xFC, 0x0B, 10);
+    // i32x4.add
+    fuel_cost_table.set_prefixed_cost(0xFD, 0xAE, 5);
+    fuel_cost_table.set_bulk_cost_per_unit(1);
+    instance.fuel_cost_table = fuel_cost_table;
+
+    // four other instructions, memory.fill and one unit per byte
+    assert_eq!(
+        ExecutionOutcome::Finished(()),
+        instance.invoke_named_with_fuel("fill", 100, 114).unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::<()>::OutOfFuel,
+        instance.invoke_named_with_fuel("fill", 100, 113).unwrap()
+    );
+
+    // i32x4.add is more expensive than the other vector instructions
+    assert_eq!(
+        ExecutionOutcome::Finished(3),
+        instance
+            .invoke_named_with_fuel("add_lanes", (), 11)
+            .unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::<i32>::OutOfFuel,
+        instance
+            .invoke_named_with_fuel("add_lanes", (), 10)
+            .unwrap()
+    );
+
+    // Setting the cost of a prefix byte sets the cost of all instructions with that prefix
+    instance.fuel_cost_table.set_cost(0xFD, 2);
+    assert_eq!(2, instance.fuel_cost_table.prefixed_cost(0xFD, 0xAE));
+    assert_eq!(10, instance.fuel_cost_table.prefixed_cost(0xFC, 0x0B));
+}
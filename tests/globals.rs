@@ -1,24 +1,25 @@
+use wasm::linker::Linker;
+use wasm::value::Ref;
+use wasm::{validate, Error, RefType, RuntimeError, RuntimeInstance, ValType, Value};
+
 /// The WASM program has one mutable global initialized with a constant 3.
 /// It exports two methods:
 ///  - Setting the global's value and returning its previous value
 ///  - Getting the global's current value
-#[ignore] // globals are not yet fully implemented
 #[test_log::test]
 fn globals() {
-    use wasm::{validate, RuntimeInstance};
-
     let wat = r#"
     (module
         (global $my_global (mut i32) (i32.const 3))
 
         ;; Set global to a value and return the previous one
-        (func $set (param i32) (result i32)
+        (func $set (export "set") (param i32) (result i32)
             global.get $my_global
             local.get 0
             global.set $my_global)
 
         ;; Returns the global's current value
-        (func $get (result i32)
+        (func $get (export "get") (result i32)
             global.get $my_global)
     )
     "#;
@@ -33,3 +34,179 @@ fn globals() {
     // Now 17 will be returned when getting the global
     assert_eq!(17, instance.invoke_named("get", ()).unwrap());
 }
+
+/// Every kind of constant instruction initializes its global
+#[test_log::test]
+fn constant_initializers() {
+    let wat = r#"
+    (module
+        (global $i32 i32 (i32.const -42))
+        (global $i64 i64 (i64.const 1099511627776))
+        (global $f32 f32 (f32.const 1.5))
+        (global $f64 f64 (f64.const -0.25))
+        (global $func funcref (ref.func $get_i32))
+        (global $null funcref (ref.null func))
+
+        (func $get_i32 (export "get_i32") (result i32) global.get $i32)
+        (func (export "get_i64") (result i64) global.get $i64)
+        (func (export "get_f32") (result f32) global.get $f32)
+        (func (export "get_f64") (result f64) global.get $f64)
+        (func (export "get_func") (result funcref) global.get $func)
+        (func (export "get_null") (result funcref) global.get $null)
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        -42,
+        instance.invoke_named::<(), i32>("get_i32", ()).unwrap()
+    );
+    assert_eq!(
+        1 << 40,
+        instance.invoke_named::<(), i64>("get_i64", ()).unwrap()
+    );
+    assert_eq!(
+        1.5,
+        instance.invoke_named::<(), f32>("get_f32", ()).unwrap()
+    );
+    assert_eq!(
+        -0.25,
+        instance.invoke_named::<(), f64>("get_f64", ()).unwrap()
+    );
+
+    let funcref = [ValType::RefType(RefType::FuncRef)];
+    assert_eq!(
+        vec![Value::Ref(Ref::Func(0))],
+        instance.invoke_dynamic(4, vec![], &funcref).unwrap()
+    );
+    assert_eq!(
+        vec![Value::Ref(Ref::Null(RefType::FuncRef))],
+        instance.invoke_dynamic(5, vec![], &funcref).unwrap()
+    );
+}
+
+/// Imported immutable globals can be used to initialize globals and the offsets of segments
+#[test_log::test]
+fn imported_global_in_initializers() {
+    let wat = r#"
+    (module
+        (import "env" "base" (global $base i32))
+        (global $copy i32 (global.get $base))
+        (memory 1)
+        (data (global.get $base) "\2a")
+        (table 8 funcref)
+        (elem (global.get $base) $get_copy)
+
+        (func $get_copy (export "get_copy") (result i32) global.get $copy)
+        (func (export "load") (param i32) (result i32)
+            local.get 0
+            i32.load
+        )
+        (func (export "call") (param i32) (result i32)
+            local.get 0
+            call_indirect (result i32)
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut linker = Linker::new();
+    linker.define_global("env", "base", Value::I32(5), false);
+    let mut instance =
+        RuntimeInstance::new_with_linker(&validation_info, linker).expect("instantiation failed");
+
+    assert_eq!(5, instance.invoke_named::<(), i32>("get_copy", ()).unwrap());
+    assert_eq!(42, instance.invoke_named::<i32, i32>("load", 5).unwrap());
+    assert_eq!(5, instance.invoke_named::<i32, i32>("call", 5).unwrap());
+}
+
+#[test_log::test]
+fn global_import_errors() {
+    let wat = r#"
+    (module
+        (import "env" "base" (global $base i32))
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    assert_eq!(
+        RuntimeInstance::new(&validation_info).err(),
+        Some(RuntimeError::UnknownImport {
+            module_name: "env".into(),
+            name: "base".into(),
+        })
+    );
+
+    let mut linker = Linker::new();
+    linker.define_global("env", "base", Value::I32(5), true);
+    assert!(matches!(
+        RuntimeInstance::new_with_linker(&validation_info, linker),
+        Err(RuntimeError::GlobalImportTypeMismatch { .. })
+    ));
+
+    let mut linker = Linker::new();
+    linker.define_global("env", "base", Value::I64(5), false);
+    assert!(matches!(
+        RuntimeInstance::new_with_linker(&validation_info, linker),
+        Err(RuntimeError::GlobalImportTypeMismatch { .. })
+    ));
+}
+
+#[test_log::test]
+fn invalid_constant_expressions() {
+    let validate_wat = |wat: &str| {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        validate(&wasm_bytes).err()
+    };
+
+    // Only imported globals may be read
+    assert_eq!(
+        validate_wat(
+            r#"(module
+                (global $a i32 (i32.const 1))
+                (global $b i32 (global.get $a))
+            )"#
+        ),
+        Some(Error::InvalidGlobalIdx(0))
+    );
+
+    // Only immutable globals may be read
+    assert_eq!(
+        validate_wat(
+            r#"(module
+                (import "env" "a" (global $a (mut i32)))
+                (global $b i32 (global.get $a))
+            )"#
+        ),
+        Some(Error::MutableGlobalInConstExpr(0))
+    );
+
+    // Arithmetic is not constant
+    assert_eq!(
+        validate_wat(r#"(module (global i32 (i32.add (i32.const 1) (i32.const 2))))"#),
+        Some(Error::InvalidConstInstr(0x6A))
+    );
+
+    // The result must match the global's type
+    assert_eq!(
+        validate_wat(r#"(module (global i32 (i64.const 1)))"#),
+        Some(Error::EndInvalidValueStack)
+    );
+    assert_eq!(
+        validate_wat(r#"(module (global i32 (i32.const 1) (i32.const 2)))"#),
+        Some(Error::EndInvalidValueStack)
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module
+                (memory 1)
+                (data (i64.const 0) "")
+            )"#
+        ),
+        Some(Error::EndInvalidValueStack)
+    );
+}
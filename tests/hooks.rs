@@ -0,0 +1,579 @@
+#![cfg(feature = "hooks")]
+
+use wasm::fuel::ExecutionOutcome;
+use wasm::hooks::{BranchOutcome, HookAction, HookSet, Location};
+use wasm::linker::Linker;
+use wasm::{validate, NumType, RuntimeError, RuntimeInstance, ValType, ValidationInfo, Value};
+
+const HOOKS_WAT: &str = r#"
+    (module
+        (global $started (mut i32) (i32.const 0))
+        (func $start
+            i32.const 1
+            global.set $started)
+        (func (export "add") (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            i32.add)
+        (func (export "count_down") (param i32)
+            loop
+                local.get 0
+                i32.const 1
+                i32.sub
+                local.tee 0
+                br_if 0
+            end)
+        (start $start)
+    )
+"#;
+
+/// Records the opcode of every executed instruction
+#[derive(Default)]
+struct OpcodeRecorder {
+    opcodes: Vec<u8>,
+}
+
+impl HookSet for OpcodeRecorder {
+    fn instruction_hook(&mut self, bytecode: &[u8], pc: usize, _location: Location) -> HookAction {
+        self.opcodes.push(bytecode[pc]);
+        HookAction::Continue
+    }
+}
+
+#[test_log::test]
+fn instruction_hook_fires() {
+    let wasm_bytes = wat::parse_str(HOOKS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new_with_hooks(&validation_info, OpcodeRecorder::default())
+        .expect("instantiation failed");
+
+    // The start function already ran during instantiation
+    assert_eq!(vec![0x41, 0x24, 0x0B], instance.hook_set.opcodes);
+    instance.hook_set.opcodes.clear();
+
+    assert_eq!(
+        3,
+        instance
+            .invoke_named::<(i32, i32), i32>("add", (1, 2))
+            .unwrap()
+    );
+    assert_eq!(vec![0x20, 0x20, 0x6A, 0x0B], instance.hook_set.opcodes);
+    instance.hook_set.opcodes.clear();
+
+    assert_eq!(
+        vec![Value::I32(5)],
+        instance
+            .invoke_dynamic(
+                1,
+                vec![Value::I32(2), Value::I32(3)],
+                &[ValType::NumType(NumType::I32)]
+            )
+            .unwrap()
+    );
+    assert_eq!(vec![0x20, 0x20, 0x6A, 0x0B], instance.hook_set.opcodes);
+}
+
+/// Counts executed instructions
+#[derive(Default)]
+struct InstructionCounter {
+    count: usize,
+}
+
+impl HookSet for InstructionCounter {
+    fn instruction_hook(
+        &mut self,
+        _bytecode: &[u8],
+        _pc: usize,
+        _location: Location,
+    ) -> HookAction {
+        self.count += 1;
+        HookAction::Continue
+    }
+}
+
+/// The hook set belongs to the instance, so its state is kept across invocations
+#[test_log::test]
+fn hook_state_persists_across_invocations() {
+    let wasm_bytes = wat::parse_str(HOOKS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_hooks(&validation_info, InstructionCounter::default())
+            .expect("instantiation failed");
+    assert_eq!(3, instance.hook_set.count);
+
+    instance
+        .invoke_named::<(i32, i32), i32>("add", (1, 2))
+        .unwrap();
+    instance
+        .invoke_named::<(i32, i32), i32>("add", (3, 4))
+        .unwrap();
+    assert_eq!(3 + 2 * 4, instance.hook_set.count);
+
+    // 1 loop, 5 iterations of 5 instructions and 2 ends
+    instance.invoke_named::<i32, ()>("count_down", 5).unwrap();
+    assert_eq!(3 + 2 * 4 + 28, instance.hook_set.count);
+}
+
+/// Pausing an invocation neither skips nor repeats any hook calls
+#[test_log::test]
+fn hooks_fire_in_resumable_invocations() {
+    let wasm_bytes = wat::parse_str(HOOKS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_hooks(&validation_info, InstructionCounter::default())
+            .expect("instantiation failed");
+    instance.hook_set.count = 0;
+
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, ()>("count_down", 5)
+        .unwrap();
+    let mut pauses = 0;
+    while resumable.resume(3).unwrap() == ExecutionOutcome::OutOfFuel {
+        pauses += 1;
+    }
+    assert!(pauses > 0);
+    assert_eq!(28, instance.hook_set.count);
+}
+
+const EVENTS_WAT: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i32 i32) (result i32)))
+        (memory 1)
+        (global $counter (mut i32) (i32.const 0))
+        ;; function 1
+        (func (export "bump") (param i32) (result i32)
+            global.get $counter ;; offset 0
+            local.get 0         ;; offset 2
+            call $add           ;; offset 4
+            global.set $counter ;; offset 6
+            global.get $counter ;; offset 8
+        )                       ;; offset 10
+        ;; function 2
+        (func (export "copy") (param i32)
+            i32.const 0         ;; offset 0
+            local.get 0         ;; offset 2
+            i32.store           ;; offset 4
+            i32.const 4         ;; offset 7
+            i32.const 0         ;; offset 9
+            i32.const 4         ;; offset 11
+            memory.copy         ;; offset 13
+        )                       ;; offset 17
+        ;; function 3
+        (func (export "classify") (param i32) (result i32)
+            block               ;; offset 0
+                block           ;; offset 2
+                    local.get 0 ;; offset 4
+                    br_table 0 1 ;; offset 6
+                end             ;; offset 10
+                i32.const 10    ;; offset 11
+                return          ;; offset 13
+            end                 ;; offset 14
+            i32.const 1         ;; offset 15
+            local.get 0         ;; offset 17
+            i32.sub             ;; offset 19
+            if (result i32)     ;; offset 20
+                i32.const 20    ;; offset 22
+            else                ;; offset 24
+                i32.const 30    ;; offset 25
+            end                 ;; offset 27
+        )                       ;; offset 28
+        ;; function 4
+        (func (export "divide") (param i32 i32) (result i32)
+            local.get 0         ;; offset 0
+            local.get 1         ;; offset 2
+            i32.div_u           ;; offset 4
+        )
+        (table 1 funcref)
+        (elem (i32.const 0) 1)
+        ;; function 5
+        (func (export "bump_indirect") (param i32) (result i32)
+            local.get 0         ;; offset 0
+            i32.const 0         ;; offset 2
+            call_indirect (param i32) (result i32) ;; offset 4
+        )
+    )
+"#;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Call(Option<Location>, usize, Vec<Value>),
+    Return(Location, usize, Vec<Value>),
+    Trap(Location, RuntimeError),
+    Load(Location, usize, Vec<u8>),
+    Store(Location, usize, Vec<u8>),
+    GlobalWrite(Location, usize, Value),
+    Branch(Location, BranchOutcome),
+}
+
+/// Records all events except for executed instructions
+#[derive(Default)]
+struct EventRecorder {
+    events: Vec<Event>,
+}
+
+impl HookSet for EventRecorder {
+    fn call_hook(
+        &mut self,
+        location: Option<Location>,
+        callee: usize,
+        args: &[Value],
+    ) -> HookAction {
+        self.events
+            .push(Event::Call(location, callee, args.to_vec()));
+        HookAction::Continue
+    }
+
+    fn return_hook(&mut self, location: Location, callee: usize, results: &[Value]) {
+        self.events
+            .push(Event::Return(location, callee, results.to_vec()));
+    }
+
+    fn trap_hook(&mut self, location: Location, error: &RuntimeError) {
+        self.events.push(Event::Trap(location, error.clone()));
+    }
+
+    fn memory_load_hook(&mut self, location: Location, address: usize, bytes: &[u8]) {
+        self.events
+            .push(Event::Load(location, address, bytes.to_vec()));
+    }
+
+    fn memory_store_hook(&mut self, location: Location, address: usize, bytes: &[u8]) {
+        self.events
+            .push(Event::Store(location, address, bytes.to_vec()));
+    }
+
+    fn global_write_hook(&mut self, location: Location, global_idx: usize, value: Value) {
+        self.events
+            .push(Event::GlobalWrite(location, global_idx, value));
+    }
+
+    fn branch_hook(&mut self, location: Location, outcome: BranchOutcome) {
+        self.events.push(Event::Branch(location, outcome));
+    }
+}
+
+fn event_instance<'b>(validation_info: &ValidationInfo<'b>) -> RuntimeInstance<'b, EventRecorder> {
+    let i32_ty = ValType::NumType(NumType::I32);
+    let mut linker = Linker::new();
+    linker.define_func("env", "add", &[i32_ty, i32_ty], &[i32_ty], |_, params| {
+        let x: u32 = params[0].into();
+        let y: u32 = params[1].into();
+        Ok(vec![Value::I32(x.wrapping_add(y))])
+    });
+    RuntimeInstance::new_with_linker_and_hooks(validation_info, linker, EventRecorder::default())
+        .expect("instantiation failed")
+}
+
+fn at(func_idx: usize, offset: usize) -> Location {
+    Location { func_idx, offset }
+}
+
+#[test_log::test]
+fn call_return_and_global_hooks() {
+    let wasm_bytes = wat::parse_str(EVENTS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = event_instance(&validation_info);
+
+    assert_eq!(5, instance.invoke_named::<i32, i32>("bump", 5).unwrap());
+    assert_eq!(
+        vec![
+            Event::Call(None, 1, vec![Value::I32(5)]),
+            Event::Call(Some(at(1, 4)), 0, vec![Value::I32(0), Value::I32(5)]),
+            Event::Return(at(1, 4), 0, vec![Value::I32(5)]),
+            Event::GlobalWrite(at(1, 6), 0, Value::I32(5)),
+            Event::Return(at(1, 10), 1, vec![Value::I32(5)]),
+        ],
+        instance.hook_set.events
+    );
+}
+
+#[test_log::test]
+fn memory_hooks() {
+    let wasm_bytes = wat::parse_str(EVENTS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = event_instance(&validation_info);
+
+    instance
+        .invoke_named::<i32, ()>("copy", 0x11223344)
+        .unwrap();
+    let bytes = vec![0x44, 0x33, 0x22, 0x11];
+    assert_eq!(
+        vec![
+            Event::Call(None, 2, vec![Value::I32(0x11223344)]),
+            Event::Store(at(2, 4), 0, bytes.clone()),
+            Event::Load(at(2, 13), 0, bytes.clone()),
+            Event::Store(at(2, 13), 4, bytes),
+            Event::Return(at(2, 17), 2, vec![]),
+        ],
+        instance.hook_set.events
+    );
+}
+
+#[test_log::test]
+fn branch_hooks() {
+    let wasm_bytes = wat::parse_str(EVENTS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = event_instance(&validation_info);
+
+    assert_eq!(
+        10,
+        instance.invoke_named::<i32, i32>("classify", 0).unwrap()
+    );
+    assert_eq!(
+        30,
+        instance.invoke_named::<i32, i32>("classify", 1).unwrap()
+    );
+    assert_eq!(
+        20,
+        instance.invoke_named::<i32, i32>("classify", 7).unwrap()
+    );
+    assert_eq!(
+        vec![
+            Event::Call(None, 3, vec![Value::I32(0)]),
+            Event::Branch(at(3, 6), BranchOutcome::Table(0)),
+            Event::Return(at(3, 13), 3, vec![Value::I32(10)]),
+            Event::Call(None, 3, vec![Value::I32(1)]),
+            Event::Branch(at(3, 6), BranchOutcome::Table(1)),
+            Event::Branch(at(3, 20), BranchOutcome::NotTaken),
+            Event::Return(at(3, 28), 3, vec![Value::I32(30)]),
+            Event::Call(None, 3, vec![Value::I32(7)]),
+            Event::Branch(at(3, 6), BranchOutcome::Table(1)),
+            Event::Branch(at(3, 20), BranchOutcome::Taken),
+            Event::Return(at(3, 28), 3, vec![Value::I32(20)]),
+        ],
+        instance.hook_set.events
+    );
+}
+
+#[test_log::test]
+fn trap_hook() {
+    let wasm_bytes = wat::parse_str(EVENTS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = event_instance(&validation_info);
+
+    assert_eq!(
+        Err(RuntimeError::DivideBy0),
+        instance.invoke_named::<(i32, i32), i32>("divide", (1, 0))
+    );
+    assert_eq!(
+        vec![
+            Event::Call(None, 4, vec![Value::I32(1), Value::I32(0)]),
+            Event::Trap(at(4, 4), RuntimeError::DivideBy0),
+        ],
+        instance.hook_set.events
+    );
+}
+
+/// Aborts execution once a budget of instructions is used up
+#[derive(Default)]
+struct Watchdog {
+    remaining: usize,
+}
+
+impl HookSet for Watchdog {
+    fn instruction_hook(
+        &mut self,
+        _bytecode: &[u8],
+        _pc: usize,
+        _location: Location,
+    ) -> HookAction {
+        if self.remaining == 0 {
+            return HookAction::Abort(42);
+        }
+        self.remaining -= 1;
+        HookAction::Continue
+    }
+}
+
+#[test_log::test]
+fn instruction_hook_aborts() {
+    let wasm_bytes = wat::parse_str(HOOKS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let watchdog = Watchdog { remaining: 100 };
+    let mut instance =
+        RuntimeInstance::new_with_hooks(&validation_info, watchdog).expect("instantiation failed");
+
+    instance.invoke_named::<i32, ()>("count_down", 5).unwrap();
+    assert_eq!(
+        Err(RuntimeError::HookAbort(42)),
+        instance.invoke_named::<i32, ()>("count_down", 1000)
+    );
+}
+
+/// Pauses before each execution of the instruction at a location
+///
+/// It does not keep any state, as the hook is not called again for the instruction it paused at.
+#[derive(Default)]
+struct Breakpoint {
+    location: Location,
+}
+
+impl HookSet for Breakpoint {
+    fn instruction_hook(&mut self, _bytecode: &[u8], _pc: usize, location: Location) -> HookAction {
+        if location == self.location {
+            HookAction::Pause
+        } else {
+            HookAction::Continue
+        }
+    }
+}
+
+#[test_log::test]
+fn instruction_hook_pauses() {
+    let wasm_bytes = wat::parse_str(HOOKS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    // `local.tee` inside the loop of `count_down`
+    let breakpoint = Breakpoint { location: at(2, 7) };
+    let mut instance = RuntimeInstance::new_with_hooks(&validation_info, breakpoint)
+        .expect("instantiation failed");
+
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, ()>("count_down", 3)
+        .unwrap();
+    for _ in 0..3 {
+        assert_eq!(
+            ExecutionOutcome::Paused,
+            resumable.resume(u64::MAX).unwrap()
+        );
+        assert_eq!(2, resumable.current_func_idx());
+    }
+    assert_eq!(
+        ExecutionOutcome::Finished(()),
+        resumable.resume(u64::MAX).unwrap()
+    );
+
+    // A restored invocation does not pause at the same breakpoint again either
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, ()>("count_down", 2)
+        .unwrap();
+    assert_eq!(
+        ExecutionOutcome::Paused,
+        resumable.resume(u64::MAX).unwrap()
+    );
+    let snapshot = resumable.serialize();
+    resumable.cancel();
+    let mut restored = instance.restore_resumable::<()>(&snapshot).unwrap();
+    assert_eq!(ExecutionOutcome::Paused, restored.resume(u64::MAX).unwrap());
+    assert_eq!(
+        ExecutionOutcome::Finished(()),
+        restored.resume(u64::MAX).unwrap()
+    );
+
+    // Invocations that are not resumable can not be paused
+    assert_eq!(
+        Err(RuntimeError::UnresumablePause),
+        instance.invoke_named::<i32, ()>("count_down", 3)
+    );
+}
+
+/// Pauses before each call of a function, and aborts calls of another function
+#[derive(Default)]
+struct CallGate {
+    pause: usize,
+    abort: usize,
+    calls: Vec<(Option<Location>, usize, Vec<Value>)>,
+}
+
+impl HookSet for CallGate {
+    fn call_hook(
+        &mut self,
+        location: Option<Location>,
+        callee: usize,
+        args: &[Value],
+    ) -> HookAction {
+        self.calls.push((location, callee, args.to_vec()));
+        if callee == self.abort {
+            return HookAction::Abort(7);
+        }
+        if callee == self.pause {
+            return HookAction::Pause;
+        }
+        HookAction::Continue
+    }
+}
+
+fn call_gate_instance<'b>(
+    validation_info: &ValidationInfo<'b>,
+    pause: usize,
+    abort: usize,
+) -> RuntimeInstance<'b, CallGate> {
+    let i32_ty = ValType::NumType(NumType::I32);
+    let mut linker = Linker::new();
+    linker.define_func("env", "add", &[i32_ty, i32_ty], &[i32_ty], |_, params| {
+        let x: u32 = params[0].into();
+        let y: u32 = params[1].into();
+        Ok(vec![Value::I32(x.wrapping_add(y))])
+    });
+    let call_gate = CallGate {
+        pause,
+        abort,
+        ..CallGate::default()
+    };
+    RuntimeInstance::new_with_linker_and_hooks(validation_info, linker, call_gate)
+        .expect("instantiation failed")
+}
+
+#[test_log::test]
+fn call_hook_pauses() {
+    let wasm_bytes = wat::parse_str(EVENTS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    // Pausing on the invoked function itself, before any instruction is executed
+    let mut instance = call_gate_instance(&validation_info, 5, usize::MAX);
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("bump_indirect", 3)
+        .unwrap();
+    assert_eq!(
+        ExecutionOutcome::Paused,
+        resumable.resume(u64::MAX).unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::Finished(3),
+        resumable.resume(u64::MAX).unwrap()
+    );
+
+    // Pausing on an indirect call, whose hook is not called again upon resumption
+    let mut instance = call_gate_instance(&validation_info, 1, usize::MAX);
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("bump_indirect", 3)
+        .unwrap();
+    assert_eq!(
+        ExecutionOutcome::Paused,
+        resumable.resume(u64::MAX).unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::Finished(3),
+        resumable.resume(u64::MAX).unwrap()
+    );
+    assert_eq!(
+        vec![
+            (None, 5, vec![Value::I32(3)]),
+            (Some(at(5, 4)), 1, vec![Value::I32(3)]),
+            (Some(at(1, 4)), 0, vec![Value::I32(0), Value::I32(3)]),
+        ],
+        instance.hook_set.calls
+    );
+}
+
+#[test_log::test]
+fn call_hook_aborts() {
+    let wasm_bytes = wat::parse_str(EVENTS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    // Host functions are not called if the call is aborted
+    let mut instance = call_gate_instance(&validation_info, usize::MAX, 0);
+    assert_eq!(
+        Err(RuntimeError::HookAbort(7)),
+        instance.invoke_named::<i32, i32>("bump", 3)
+    );
+
+    let mut instance = call_gate_instance(&validation_info, usize::MAX, 1);
+    assert_eq!(
+        Err(RuntimeError::HookAbort(7)),
+        instance.invoke_named::<i32, i32>("bump", 3)
+    );
+    assert_eq!(
+        vec![(None, 1, vec![Value::I32(3)])],
+        instance.hook_set.calls
+    );
+}
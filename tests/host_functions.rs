@@ -0,0 +1,194 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm::linker::Linker;
+use wasm::{validate, NumType, RuntimeError, RuntimeInstance, ValType, Value};
+
+const I32: ValType = ValType::NumType(NumType::I32);
+const I64: ValType = ValType::NumType(NumType::I64);
+
+const ADD_WITH_HOST: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i32 i32) (result i32)))
+        (func (export "add_twice") (param $x i32) (param $y i32) (result i32)
+            local.get $x
+            local.get $y
+            call $add
+            local.get $y
+            call $add
+        )
+    )
+"#;
+
+fn add_linker() -> Linker {
+    let mut linker = Linker::new();
+    linker.define_func("env", "add", &[I32, I32], &[I32], |_, params| {
+        let x: u32 = params[0].into();
+        let y: u32 = params[1].into();
+        Ok(vec![Value::I32(x.wrapping_add(y))])
+    });
+    linker
+}
+
+/// A function called from WASM is dispatched to the host
+#[test_log::test]
+fn call_host_function() {
+    let wasm_bytes = wat::parse_str(ADD_WITH_HOST).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new_with_linker(&validation_info, add_linker())
+        .expect("instantiation failed");
+
+    assert_eq!(
+        12,
+        instance
+            .invoke_named::<(i32, i32), i32>("add_twice", (2, 5))
+            .unwrap()
+    );
+}
+
+/// An imported function can be invoked directly, which calls the host function
+#[test_log::test]
+fn invoke_imported_function() {
+    let wasm_bytes = wat::parse_str(ADD_WITH_HOST).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new_with_linker(&validation_info, add_linker())
+        .expect("instantiation failed");
+
+    assert_eq!(
+        vec![Value::I32(7)],
+        instance
+            .invoke_dynamic(0, vec![Value::I32(2), Value::I32(5)], &[I32])
+            .unwrap()
+    );
+    assert_eq!(
+        7,
+        instance.invoke_func::<(i32, i32), i32>(0, (2, 5)).unwrap()
+    );
+    assert_eq!(
+        Err(RuntimeError::FunctionTypeMismatch),
+        instance.invoke_dynamic(0, vec![Value::I32(2)], &[I32])
+    );
+}
+
+/// Host functions can keep state and access the calling instance's memory
+#[test_log::test]
+fn host_function_accesses_memory() {
+    let wat = r#"
+        (module
+            (import "env" "fill" (func $fill (param i32)))
+            (memory 1)
+            (func (export "run") (param $value i32) (result i32)
+                local.get $value
+                call $fill
+                i32.const 0
+                i32.load
+            )
+        )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let calls = Rc::new(RefCell::new(0));
+    let mut linker = Linker::new();
+    let host_calls = Rc::clone(&calls);
+    linker.define_func("env", "fill", &[I32], &[], move |ctx, params| {
+        *host_calls.borrow_mut() += 1;
+        let byte = u32::from(params[0]) as u8;
+        let memory = ctx.memory().ok_or(RuntimeError::HostTrap)?;
+        memory[..16].fill(byte);
+        Ok(vec![])
+    });
+
+    let mut instance =
+        RuntimeInstance::new_with_linker(&validation_info, linker).expect("instantiation failed");
+    assert_eq!(
+        0x0101_0101,
+        instance.invoke_named::<i32, i32>("run", 0x01).unwrap()
+    );
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("run", 0xFF).unwrap());
+    assert_eq!(2, *calls.borrow());
+}
+
+#[test_log::test]
+fn missing_import() {
+    let wasm_bytes = wat::parse_str(ADD_WITH_HOST).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut linker = Linker::new();
+    linker.define_func("other", "add", &[I32, I32], &[I32], |_, _| Ok(vec![]));
+
+    assert_eq!(
+        RuntimeInstance::new_with_linker(&validation_info, linker).err(),
+        Some(RuntimeError::UnknownImport {
+            module_name: "env".into(),
+            name: "add".into(),
+        })
+    );
+    assert!(matches!(
+        RuntimeInstance::new(&validation_info),
+        Err(RuntimeError::UnknownImport { .. })
+    ));
+}
+
+#[test_log::test]
+fn import_type_mismatch() {
+    let wasm_bytes = wat::parse_str(ADD_WITH_HOST).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut linker = Linker::new();
+    linker.define_func("env", "add", &[I64, I64], &[I64], |_, _| Ok(vec![]));
+
+    let Err(RuntimeError::ImportTypeMismatch {
+        module_name,
+        name,
+        expected,
+        actual,
+    }) = RuntimeInstance::new_with_linker(&validation_info, linker)
+    else {
+        panic!("expected an import type mismatch");
+    };
+    assert_eq!((module_name.as_str(), name.as_str()), ("env", "add"));
+    assert_eq!(expected.params.valtypes, [I32, I32]);
+    assert_eq!(actual.params.valtypes, [I64, I64]);
+}
+
+/// An error returned by a host function aborts the invocation
+#[test_log::test]
+fn host_function_traps() {
+    let wasm_bytes = wat::parse_str(ADD_WITH_HOST).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut linker = Linker::new();
+    linker.define_func("env", "add", &[I32, I32], &[I32], |_, _| {
+        Err(RuntimeError::HostTrap)
+    });
+    let mut instance =
+        RuntimeInstance::new_with_linker(&validation_info, linker).expect("instantiation failed");
+
+    assert_eq!(
+        instance
+            .invoke_named::<(i32, i32), i32>("add_twice", (2, 5))
+            .err(),
+        Some(RuntimeError::HostTrap)
+    );
+}
+
+#[test_log::test]
+fn host_function_returns_wrong_types() {
+    let wasm_bytes = wat::parse_str(ADD_WITH_HOST).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut linker = Linker::new();
+    linker.define_func("env", "add", &[I32, I32], &[I32], |_, _| {
+        Ok(vec![Value::I64(0)])
+    });
+    let mut instance =
+        RuntimeInstance::new_with_linker(&validation_info, linker).expect("instantiation failed");
+
+    assert_eq!(
+        instance
+            .invoke_named::<(i32, i32), i32>("add_twice", (2, 5))
+            .err(),
+        Some(RuntimeError::InvalidHostFunctionResults)
+    );
+}
@@ -1,6 +1,6 @@
 use wasm::{validate, RuntimeInstance};
 
-const WAT: &'static str = r#"
+const WAT: &str = r#"
       (module
       (func (export "i32_{{0}}") (param $x i32) (param $y i32) (result i32)
           local.get $x
@@ -23,7 +23,7 @@ pub fn i32_add() {
     assert_eq!(0, instance.invoke_func(0, (-1, 1)).unwrap());
     // Chaned the following value from the spec:
     // - 0x80000000 to -2147483648 = (0x80000000 as u32) as i32
-    let i32_min = (0x80000000 as u32) as i32;
+    let i32_min = 0x80000000_u32 as i32;
 
     assert_eq!(i32_min, instance.invoke_func(0, (0x7fffffff, 1)).unwrap());
     assert_eq!(0x7fffffff, instance.invoke_func(0, (i32_min, -1)).unwrap());
@@ -47,7 +47,7 @@ pub fn i32_sub() {
     assert_eq!(0, instance.invoke_func(0, (-1, -1)).unwrap());
     // Chaned the following value from the spec:
     // - 0x80000000 to -2147483648 = (0x80000000 as u32) as i32
-    let i32_min = (0x80000000 as u32) as i32;
+    let i32_min = 0x80000000_u32 as i32;
 
     assert_eq!(i32_min, instance.invoke_func(0, (0x7fffffff, -1)).unwrap());
     assert_eq!(0x7fffffff, instance.invoke_func(0, (i32_min, 1)).unwrap());
@@ -688,3 +688,51 @@ pub fn i32_ge_u() {
             .unwrap()
     );
 }
+
+const UNOP_WAT: &str = r#"
+    (module
+      (func (export "i32_{{0}}") (param $x i32) (result i32)
+          local.get $x
+          i32.{{0}})
+    )
+"#;
+
+/// A function to test the i32.extend8_s implementation using the cases of the WASM test suite
+#[test_log::test]
+pub fn i32_extend8_s() {
+    let wat = String::from(UNOP_WAT).replace("{{0}}", "extend8_s");
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(0, instance.invoke_func(0, 0).unwrap());
+    assert_eq!(127, instance.invoke_func(0, 0x7f).unwrap());
+    assert_eq!(-128, instance.invoke_func(0, 0x80).unwrap());
+    assert_eq!(-1, instance.invoke_func(0, 0xff).unwrap());
+    assert_eq!(0, instance.invoke_func(0, 0x0123_4500).unwrap());
+    assert_eq!(
+        -0x80,
+        instance.invoke_func(0, 0xfedc_ba80_u32 as i32).unwrap()
+    );
+    assert_eq!(-1, instance.invoke_func(0, -1).unwrap());
+}
+
+/// A function to test the i32.extend16_s implementation using the cases of the WASM test suite
+#[test_log::test]
+pub fn i32_extend16_s() {
+    let wat = String::from(UNOP_WAT).replace("{{0}}", "extend16_s");
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(0, instance.invoke_func(0, 0).unwrap());
+    assert_eq!(32767, instance.invoke_func(0, 0x7fff).unwrap());
+    assert_eq!(-32768, instance.invoke_func(0, 0x8000).unwrap());
+    assert_eq!(-1, instance.invoke_func(0, 0xffff).unwrap());
+    assert_eq!(0, instance.invoke_func(0, 0x0123_0000).unwrap());
+    assert_eq!(
+        -0x8000,
+        instance.invoke_func(0, 0xfedc_8000_u32 as i32).unwrap()
+    );
+    assert_eq!(-1, instance.invoke_func(0, -1).unwrap());
+}
@@ -1,6 +1,6 @@
 use wasm::{validate, RuntimeInstance};
 
-const WAT: &'static str = r#"
+const WAT: &str = r#"
       (module
       (func (export "i64_{{0}}") (param $x i64) (param $y i64) (result i32)
           local.get $x
@@ -874,3 +874,92 @@ pub fn i64_ge_u() {
             .unwrap()
     );
 }
+
+const UNOP_WAT: &str = r#"
+    (module
+      (func (export "i64_{{0}}") (param $x i64) (result i64)
+          local.get $x
+          i64.{{0}})
+    )
+"#;
+
+/// A function to test the i64.extend8_s implementation using the cases of the WASM test suite
+#[test_log::test]
+pub fn i64_extend8_s() {
+    let wat = String::from(UNOP_WAT).replace("{{0}}", "extend8_s");
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(0_i64, instance.invoke_func(0, 0_i64).unwrap());
+    assert_eq!(127_i64, instance.invoke_func(0, 0x7f_i64).unwrap());
+    assert_eq!(-128_i64, instance.invoke_func(0, 0x80_i64).unwrap());
+    assert_eq!(-1_i64, instance.invoke_func(0, 0xff_i64).unwrap());
+    assert_eq!(
+        0_i64,
+        instance.invoke_func(0, 0x0123_4567_89ab_cd00_i64).unwrap()
+    );
+    assert_eq!(
+        -0x80_i64,
+        instance
+            .invoke_func(0, 0xfedc_ba98_7654_3280_u64 as i64)
+            .unwrap()
+    );
+    assert_eq!(-1_i64, instance.invoke_func(0, -1_i64).unwrap());
+}
+
+/// A function to test the i64.extend16_s implementation using the cases of the WASM test suite
+#[test_log::test]
+pub fn i64_extend16_s() {
+    let wat = String::from(UNOP_WAT).replace("{{0}}", "extend16_s");
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(0_i64, instance.invoke_func(0, 0_i64).unwrap());
+    assert_eq!(32767_i64, instance.invoke_func(0, 0x7fff_i64).unwrap());
+    assert_eq!(-32768_i64, instance.invoke_func(0, 0x8000_i64).unwrap());
+    assert_eq!(-1_i64, instance.invoke_func(0, 0xffff_i64).unwrap());
+    assert_eq!(
+        0_i64,
+        instance.invoke_func(0, 0x1234_5678_9abc_0000_i64).unwrap()
+    );
+    assert_eq!(
+        -0x8000_i64,
+        instance
+            .invoke_func(0, 0xfedc_ba98_7654_8000_u64 as i64)
+            .unwrap()
+    );
+    assert_eq!(-1_i64, instance.invoke_func(0, -1_i64).unwrap());
+}
+
+/// A function to test the i64.extend32_s implementation using the cases of the WASM test suite
+#[test_log::test]
+pub fn i64_extend32_s() {
+    let wat = String::from(UNOP_WAT).replace("{{0}}", "extend32_s");
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(0_i64, instance.invoke_func(0, 0_i64).unwrap());
+    assert_eq!(
+        0x7fffffff_i64,
+        instance.invoke_func(0, 0x7fffffff_i64).unwrap()
+    );
+    assert_eq!(
+        -0x80000000_i64,
+        instance.invoke_func(0, 0x80000000_i64).unwrap()
+    );
+    assert_eq!(-1_i64, instance.invoke_func(0, 0xffffffff_i64).unwrap());
+    assert_eq!(
+        0_i64,
+        instance.invoke_func(0, 0x01234567_00000000_i64).unwrap()
+    );
+    assert_eq!(
+        -0x80000000_i64,
+        instance
+            .invoke_func(0, 0xfedcba98_80000000_u64 as i64)
+            .unwrap()
+    );
+    assert_eq!(-1_i64, instance.invoke_func(0, -1_i64).unwrap());
+}
@@ -0,0 +1,231 @@
+use wasm::{validate, Error, RuntimeError, RuntimeInstance};
+
+const LOAD_STORE_WAT: &str = r#"
+    (module
+        (memory 1)
+        (data (i32.const 0) "\80\ff\7f\01\fe\ff\ff\ff\00\00\00\80")
+
+        (func (export "i32_load8_s") (param i32) (result i32) (i32.load8_s (local.get 0)))
+        (func (export "i32_load8_u") (param i32) (result i32) (i32.load8_u (local.get 0)))
+        (func (export "i32_load16_s") (param i32) (result i32) (i32.load16_s (local.get 0)))
+        (func (export "i32_load16_u") (param i32) (result i32) (i32.load16_u (local.get 0)))
+        (func (export "i64_load8_s") (param i32) (result i64) (i64.load8_s (local.get 0)))
+        (func (export "i64_load8_u") (param i32) (result i64) (i64.load8_u (local.get 0)))
+        (func (export "i64_load16_s") (param i32) (result i64) (i64.load16_s (local.get 0)))
+        (func (export "i64_load16_u") (param i32) (result i64) (i64.load16_u (local.get 0)))
+        (func (export "i64_load32_s") (param i32) (result i64) (i64.load32_s (local.get 0)))
+        (func (export "i64_load32_u") (param i32) (result i64) (i64.load32_u (local.get 0)))
+        (func (export "i64_load") (param i32) (result i64) (i64.load (local.get 0)))
+        (func (export "f64_load") (param i32) (result f64) (f64.load (local.get 0)))
+
+        (func (export "i32_store8") (param i32 i32) (i32.store8 (local.get 0) (local.get 1)))
+        (func (export "i32_store16") (param i32 i32) (i32.store16 (local.get 0) (local.get 1)))
+        (func (export "i64_store8") (param i32 i64) (i64.store8 (local.get 0) (local.get 1)))
+        (func (export "i64_store16") (param i32 i64) (i64.store16 (local.get 0) (local.get 1)))
+        (func (export "i64_store32") (param i32 i64) (i64.store32 (local.get 0) (local.get 1)))
+        (func (export "i64_store") (param i32 i64) (i64.store (local.get 0) (local.get 1)))
+        (func (export "f64_store") (param i32 f64) (f64.store (local.get 0) (local.get 1)))
+    )
+"#;
+
+/// Narrow loads sign- or zero-extend the bytes they read
+#[test_log::test]
+fn narrow_loads() {
+    let wasm_bytes = wat::parse_str(LOAD_STORE_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        -128,
+        instance.invoke_named::<i32, i32>("i32_load8_s", 0).unwrap()
+    );
+    assert_eq!(
+        128,
+        instance.invoke_named::<i32, i32>("i32_load8_u", 0).unwrap()
+    );
+    assert_eq!(
+        127,
+        instance.invoke_named::<i32, i32>("i32_load8_s", 2).unwrap()
+    );
+    assert_eq!(
+        -128,
+        instance
+            .invoke_named::<i32, i32>("i32_load16_s", 0)
+            .unwrap()
+    );
+    assert_eq!(
+        0xFF80,
+        instance
+            .invoke_named::<i32, i32>("i32_load16_u", 0)
+            .unwrap()
+    );
+    assert_eq!(
+        0x017F,
+        instance
+            .invoke_named::<i32, i32>("i32_load16_u", 2)
+            .unwrap()
+    );
+
+    assert_eq!(
+        -128,
+        instance.invoke_named::<i32, i64>("i64_load8_s", 0).unwrap()
+    );
+    assert_eq!(
+        128,
+        instance.invoke_named::<i32, i64>("i64_load8_u", 0).unwrap()
+    );
+    assert_eq!(
+        -128,
+        instance
+            .invoke_named::<i32, i64>("i64_load16_s", 0)
+            .unwrap()
+    );
+    assert_eq!(
+        0xFF80,
+        instance
+            .invoke_named::<i32, i64>("i64_load16_u", 0)
+            .unwrap()
+    );
+    assert_eq!(
+        -2,
+        instance
+            .invoke_named::<i32, i64>("i64_load32_s", 4)
+            .unwrap()
+    );
+    assert_eq!(
+        0xFFFF_FFFE,
+        instance
+            .invoke_named::<i32, i64>("i64_load32_u", 4)
+            .unwrap()
+    );
+    assert_eq!(
+        i64::MIN >> 32,
+        instance.invoke_named::<i32, i64>("i64_load", 4).unwrap() >> 32
+    );
+}
+
+/// Narrow stores only write the low bytes of the value
+#[test_log::test]
+fn narrow_stores() {
+    let wasm_bytes = wat::parse_str(LOAD_STORE_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance
+        .invoke_named::<(i32, i64), ()>("i64_store", (16, -1))
+        .unwrap();
+    instance
+        .invoke_named::<(i32, i32), ()>("i32_store8", (16, 0x1234))
+        .unwrap();
+    instance
+        .invoke_named::<(i32, i32), ()>("i32_store16", (17, 0x5678_9ABC))
+        .unwrap();
+    assert_eq!(
+        0xFFFF_FFFF_FF9A_BC34_u64 as i64,
+        instance.invoke_named::<i32, i64>("i64_load", 16).unwrap()
+    );
+
+    instance
+        .invoke_named::<(i32, i64), ()>("i64_store", (16, 0))
+        .unwrap();
+    instance
+        .invoke_named::<(i32, i64), ()>("i64_store8", (16, 0x1122))
+        .unwrap();
+    instance
+        .invoke_named::<(i32, i64), ()>("i64_store16", (17, 0x3344_5566))
+        .unwrap();
+    instance
+        .invoke_named::<(i32, i64), ()>("i64_store32", (19, 0x7788_99AA_BBCC_DDEE))
+        .unwrap();
+    assert_eq!(
+        0x00BB_CCDD_EE55_6622,
+        instance.invoke_named::<i32, i64>("i64_load", 16).unwrap()
+    );
+
+    instance
+        .invoke_named::<(i32, f64), ()>("f64_store", (24, -1.25))
+        .unwrap();
+    assert_eq!(
+        -1.25,
+        instance.invoke_named::<i32, f64>("f64_load", 24).unwrap()
+    );
+}
+
+#[test_log::test]
+fn out_of_bounds_narrow_accesses() {
+    let wasm_bytes = wat::parse_str(LOAD_STORE_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        0,
+        instance
+            .invoke_named::<i32, i32>("i32_load8_u", 65535)
+            .unwrap()
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<i32, i32>("i32_load8_u", 65536)
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<i32, i64>("i64_load32_s", 65533)
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i64), ()>("i64_store16", (65535, 0))
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<i32, f64>("f64_load", 65529)
+    );
+}
+
+#[test_log::test]
+fn alignment_is_validated() {
+    let validate_wat = |wat: &str| {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        validate(&wasm_bytes).err()
+    };
+
+    // The natural alignment is allowed, as well as any smaller one
+    assert_eq!(
+        validate_wat(
+            r#"(module (memory 1)
+                (func (result i64) (i64.load align=8 (i32.const 0)))
+                (func (result i64) (i64.load align=1 (i32.const 0)))
+                (func (i32.store16 align=2 (i32.const 0) (i32.const 0)))
+                (func (result i32) (i32.load8_u align=1 (i32.const 0)))
+            )"#
+        ),
+        None
+    );
+
+    assert_eq!(
+        validate_wat(r#"(module (memory 1) (func (result i32) (i32.load align=8 (i32.const 0))))"#),
+        Some(Error::InvalidAlignment(3))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (memory 1) (func (result i64) (i64.load16_s align=4 (i32.const 0))))"#
+        ),
+        Some(Error::InvalidAlignment(2))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (memory 1) (func (i32.store8 align=2 (i32.const 0) (i32.const 0))))"#
+        ),
+        Some(Error::InvalidAlignment(1))
+    );
+}
+
+#[test_log::test]
+fn memory_must_exist() {
+    let wasm_bytes =
+        wat::parse_str(r#"(module (func (result f64) (f64.load (i32.const 0))))"#).unwrap();
+    assert_eq!(validate(&wasm_bytes).err(), Some(Error::InvalidMemIdx(0)));
+
+    let wasm_bytes =
+        wat::parse_str(r#"(module (func (i64.store32 (i32.const 0) (i64.const 0))))"#).unwrap();
+    assert_eq!(validate(&wasm_bytes).err(), Some(Error::InvalidMemIdx(0)));
+}
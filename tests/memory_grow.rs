@@ -0,0 +1,148 @@
+use wasm::memory_growth::{MaxPages, NoGrowth};
+use wasm::{validate, Error, Limits, RuntimeError, RuntimeInstance};
+
+const GROW_WAT: &str = r#"
+    (module
+        (memory {{LIMITS}})
+        (func (export "size") (result i32)
+            memory.size)
+        (func (export "grow") (param i32) (result i32)
+            local.get 0
+            memory.grow)
+        (func (export "load") (param i32) (result i32)
+            local.get 0
+            i32.load)
+        (func (export "store") (param i32 i32)
+            local.get 0
+            local.get 1
+            i32.store)
+    )
+"#;
+
+fn memory_module(limits: &str) -> Vec<u8> {
+    wat::parse_str(GROW_WAT.replace("{{LIMITS}}", limits)).unwrap()
+}
+
+/// Growing returns the previous size and makes the new pages accessible and zeroed
+#[test_log::test]
+fn grow_memory() {
+    let wasm_bytes = memory_module("1 3");
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(1, instance.invoke_named::<(), i32>("size", ()).unwrap());
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<i32, i32>("load", 65536)
+    );
+    instance
+        .invoke_named::<(i32, i32), ()>("store", (65532, 42))
+        .unwrap();
+
+    assert_eq!(1, instance.invoke_named::<i32, i32>("grow", 1).unwrap());
+    assert_eq!(2, instance.invoke_named::<(), i32>("size", ()).unwrap());
+    assert_eq!(0, instance.invoke_named::<i32, i32>("load", 65536).unwrap());
+    assert_eq!(
+        42,
+        instance.invoke_named::<i32, i32>("load", 65532).unwrap()
+    );
+
+    // Growing by zero pages returns the current size
+    assert_eq!(2, instance.invoke_named::<i32, i32>("grow", 0).unwrap());
+}
+
+/// Growth beyond the maximum fails with -1 and leaves the memory unchanged
+#[test_log::test]
+fn grow_beyond_maximum() {
+    let wasm_bytes = memory_module("1 3");
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("grow", 3).unwrap());
+    assert_eq!(1, instance.invoke_named::<(), i32>("size", ()).unwrap());
+    assert_eq!(1, instance.invoke_named::<i32, i32>("grow", 2).unwrap());
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("grow", 1).unwrap());
+    assert_eq!(3, instance.invoke_named::<(), i32>("size", ()).unwrap());
+
+    // Without a maximum, memories are limited to 65536 pages
+    let wasm_bytes = memory_module("0");
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        -1,
+        instance.invoke_named::<i32, i32>("grow", 65537).unwrap()
+    );
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("grow", -1).unwrap());
+    assert_eq!(0, instance.invoke_named::<(), i32>("size", ()).unwrap());
+}
+
+#[test_log::test]
+fn growth_policy() {
+    let wasm_bytes = memory_module("1");
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    instance.set_memory_growth_policy(MaxPages(2));
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("grow", 2).unwrap());
+    assert_eq!(1, instance.invoke_named::<i32, i32>("grow", 1).unwrap());
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("grow", 1).unwrap());
+    assert_eq!(2, instance.invoke_named::<(), i32>("size", ()).unwrap());
+
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    instance.set_memory_growth_policy(NoGrowth);
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("grow", 1).unwrap());
+    assert_eq!(1, instance.invoke_named::<(), i32>("size", ()).unwrap());
+
+    // Closures are policies as well, which are only asked about growth within the limits
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    instance.set_memory_growth_policy(|current_pages, requested_pages| {
+        assert!(requested_pages <= 65536);
+        requested_pages - current_pages <= 4
+    });
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("grow", 5).unwrap());
+    assert_eq!(1, instance.invoke_named::<i32, i32>("grow", 4).unwrap());
+    assert_eq!(
+        -1,
+        instance.invoke_named::<i32, i32>("grow", 65536).unwrap()
+    );
+    assert_eq!(5, instance.invoke_named::<(), i32>("size", ()).unwrap());
+}
+
+#[test_log::test]
+fn invalid_memory_limits() {
+    let validate_limits = |limits: &str| validate(&memory_module(limits)).err();
+
+    assert_eq!(None, validate_limits("65536 65536"));
+    assert_eq!(
+        Some(Error::InvalidLimits(Limits {
+            min: 2,
+            max: Some(1)
+        })),
+        validate_limits("2 1")
+    );
+    assert_eq!(
+        Some(Error::InvalidLimits(Limits {
+            min: 65537,
+            max: None
+        })),
+        validate_limits("65537")
+    );
+    assert_eq!(
+        Some(Error::InvalidLimits(Limits {
+            min: 0,
+            max: Some(65537)
+        })),
+        validate_limits("0 65537")
+    );
+}
+
+#[test_log::test]
+fn memory_instructions_without_memory() {
+    let wasm_bytes = wat::parse_str(r#"(module (func (result i32) memory.size))"#).unwrap();
+    assert_eq!(validate(&wasm_bytes).err(), Some(Error::InvalidMemIdx(0)));
+
+    let wasm_bytes =
+        wat::parse_str(r#"(module (func (result i32) (memory.grow (i32.const 1))))"#).unwrap();
+    assert_eq!(validate(&wasm_bytes).err(), Some(Error::InvalidMemIdx(0)));
+}
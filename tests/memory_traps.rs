@@ -0,0 +1,165 @@
+use wasm::{validate, RuntimeError, RuntimeInstance};
+
+const MEMORY_WAT: &str = r#"
+    (module
+        (memory 1)
+        (func (export "i32_load") (param i32) (result i32)
+            local.get 0
+            i32.load)
+        (func (export "i32_load_offset") (param i32) (result i32)
+            local.get 0
+            i32.load offset=4294967295)
+        (func (export "f32_load") (param i32) (result f32)
+            local.get 0
+            f32.load)
+        (func (export "i32_store") (param i32 i32)
+            local.get 0
+            local.get 1
+            i32.store)
+        (func (export "i32_store_offset") (param i32 i32)
+            local.get 0
+            local.get 1
+            i32.store offset=65532)
+        (func (export "f32_store") (param i32 f32)
+            local.get 0
+            local.get 1
+            f32.store)
+        (func (export "f64_store") (param i32 f64)
+            local.get 0
+            local.get 1
+            f64.store)
+    )
+"#;
+
+/// Accesses up to the last byte of the memory succeed, one byte further traps
+#[test_log::test]
+fn out_of_bounds_accesses_trap() {
+    let wasm_bytes = wat::parse_str(MEMORY_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance
+        .invoke_named::<(i32, i32), ()>("i32_store", (65532, 42))
+        .unwrap();
+    assert_eq!(
+        42,
+        instance
+            .invoke_named::<i32, i32>("i32_load", 65532)
+            .unwrap()
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<i32, i32>("i32_load", 65533)
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32), ()>("i32_store", (65533, 42))
+    );
+
+    instance
+        .invoke_named::<(i32, f32), ()>("f32_store", (65532, 1.5))
+        .unwrap();
+    assert_eq!(
+        1.5,
+        instance
+            .invoke_named::<i32, f32>("f32_load", 65532)
+            .unwrap()
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<i32, f32>("f32_load", 65533)
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, f32), ()>("f32_store", (65533, 1.5))
+    );
+
+    instance
+        .invoke_named::<(i32, f64), ()>("f64_store", (65528, 1.0))
+        .unwrap();
+    assert_eq!(
+        0x3FF0_0000,
+        instance
+            .invoke_named::<i32, i32>("i32_load", 65532)
+            .unwrap()
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, f64), ()>("f64_store", (65529, 1.0))
+    );
+}
+
+/// The effective address is a 33-bit integer, so neither the address nor its sum with the offset
+/// wrap around
+#[test_log::test]
+fn effective_address_does_not_wrap() {
+    let wasm_bytes = wat::parse_str(MEMORY_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance
+        .invoke_named::<(i32, i32), ()>("i32_store", (0, 42))
+        .unwrap();
+
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<i32, i32>("i32_load", -1)
+    );
+    // 0xFFFF_FFFF + 1 would be 0 if it wrapped around
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<i32, i32>("i32_load_offset", 1)
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32), ()>("i32_store_offset", (1, 42))
+    );
+    instance
+        .invoke_named::<(i32, i32), ()>("i32_store_offset", (0, 17))
+        .unwrap();
+    assert_eq!(
+        17,
+        instance
+            .invoke_named::<i32, i32>("i32_load", 65532)
+            .unwrap()
+    );
+}
+
+/// A trap does not modify memory and the instance can still be used afterwards
+#[test_log::test]
+fn instance_is_reusable_after_trap() {
+    let wasm_bytes = wat::parse_str(MEMORY_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance
+        .invoke_named::<(i32, i32), ()>("i32_store", (65532, 7))
+        .unwrap();
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, i32), ()>("i32_store", (65534, -1))
+    );
+    assert_eq!(
+        7,
+        instance
+            .invoke_named::<i32, i32>("i32_load", 65532)
+            .unwrap()
+    );
+
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("i32_load", 65536)
+        .unwrap();
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        resumable.resume(1000).map(|_| ())
+    );
+    assert!(resumable.is_finished());
+    resumable.cancel();
+
+    assert_eq!(
+        7,
+        instance
+            .invoke_named::<i32, i32>("i32_load", 65532)
+            .unwrap()
+    );
+}
@@ -0,0 +1,162 @@
+use wasm::{validate, Error, RuntimeInstance};
+
+const MULTI_VALUE_WAT: &str = r#"
+    (module
+        (type $i32_to_i32x2 (func (param i32) (result i32 i32)))
+        (type $i32x2_to_i32 (func (param i32 i32) (result i32)))
+        (func (export "swap") (param i32 i32) (result i32 i32)
+            local.get 1
+            local.get 0)
+        (func (export "block_params") (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            block (type $i32x2_to_i32)
+                i32.sub
+            end)
+        (func (export "block_results") (param i32) (result i32 i32)
+            local.get 0
+            block (type $i32_to_i32x2)
+                i32.const 1
+                i32.add
+                local.get 0
+            end)
+        (func (export "br_results") (param i32) (result i32 i32)
+            local.get 0
+            block (type $i32_to_i32x2)
+                i32.const 7
+                br 0
+            end)
+        ;; Sums the numbers from 1 to n, keeping the accumulator and counter on the stack
+        (func (export "loop_params") (param i32) (result i32)
+            i32.const 0
+            local.get 0
+            loop (param i32 i32) (result i32)
+                local.set 0
+                local.get 0
+                i32.add
+                local.get 0
+                i32.const 1
+                i32.sub
+                local.tee 0
+                local.get 0
+                br_if 0
+                drop
+            end)
+        (func (export "if_params") (param i32 i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            local.get 2
+            if (param i32 i32) (result i32)
+                i32.add
+            else
+                i32.mul
+            end)
+        (func (export "twelve") (param i32) (result i32 i64 f32 f64 i32 i64 f32 f64 i32 i64 f32 f64)
+            (i32.const 1) (i64.const 2) (f32.const 3) (f64.const 4)
+            (i32.const 5) (i64.const 6) (f32.const 7) (f64.const 8)
+            (i32.const 9) (i64.const 10) (f32.const 11) (f64.const 12)
+            local.get 0
+            br_if 0
+            return)
+    )
+"#;
+
+#[test_log::test]
+fn multi_value_functions() {
+    let wasm_bytes = wat::parse_str(MULTI_VALUE_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        (2, 1),
+        instance
+            .invoke_named::<(i32, i32), (i32, i32)>("swap", (1, 2))
+            .unwrap()
+    );
+    assert_eq!(
+        (1, 2, 3.0, 4.0, 5, 6, 7.0, 8.0, 9, 10, 11.0, 12.0),
+        instance
+            .invoke_named::<i32, (i32, i64, f32, f64, i32, i64, f32, f64, i32, i64, f32, f64)>(
+                "twelve", 0
+            )
+            .unwrap()
+    );
+    // Branching out of the function returns the same values
+    assert_eq!(
+        (1, 2, 3.0, 4.0, 5, 6, 7.0, 8.0, 9, 10, 11.0, 12.0),
+        instance
+            .invoke_named::<i32, (i32, i64, f32, f64, i32, i64, f32, f64, i32, i64, f32, f64)>(
+                "twelve", 1
+            )
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn multi_value_blocks() {
+    let wasm_bytes = wat::parse_str(MULTI_VALUE_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        3,
+        instance
+            .invoke_named::<(i32, i32), i32>("block_params", (5, 2))
+            .unwrap()
+    );
+    assert_eq!(
+        (6, 5),
+        instance
+            .invoke_named::<i32, (i32, i32)>("block_results", 5)
+            .unwrap()
+    );
+    assert_eq!(
+        (5, 7),
+        instance
+            .invoke_named::<i32, (i32, i32)>("br_results", 5)
+            .unwrap()
+    );
+    assert_eq!(
+        55,
+        instance
+            .invoke_named::<i32, i32>("loop_params", 10)
+            .unwrap()
+    );
+    assert_eq!(
+        7,
+        instance
+            .invoke_named::<(i32, i32, i32), i32>("if_params", (3, 4, 1))
+            .unwrap()
+    );
+    assert_eq!(
+        12,
+        instance
+            .invoke_named::<(i32, i32, i32), i32>("if_params", (3, 4, 0))
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn invalid_multi_value_blocks() {
+    let validate_wat = |wat: &str| {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        validate(&wasm_bytes).err()
+    };
+
+    assert_eq!(
+        validate_wat(r#"(module (func block (type 1) end))"#),
+        Some(Error::InvalidTypeIdx(1))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (result i32) (i32.const 1) (if (param i32) (result i32) (i32.const 0) (then))))"#
+        ),
+        None
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (result i32 i32) (i32.const 1) (if (param i32) (result i32 i32) (i32.const 0) (then (i32.const 2)))))"#
+        ),
+        Some(Error::IfWithoutMatchingElse)
+    );
+}
@@ -0,0 +1,192 @@
+use wasm::{validate, Error, NumType, RuntimeError, RuntimeInstance, ValType};
+
+const PARAMETRIC_WAT: &str = r#"
+    (module
+        (func (export "drop") (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            drop)
+        (func (export "select_i32") (param i32 i32 i32) (result i32)
+            (select (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "select_f64") (param f64 f64 i32) (result f64)
+            (select (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "select_typed") (param i64 i64 i32) (result i64)
+            (select (result i64) (local.get 0) (local.get 1) (local.get 2)))
+        (func (export "unreachable") (param i32) (result i32)
+            (if (local.get 0) (then unreachable))
+            i32.const 42)
+    )
+"#;
+
+#[test_log::test]
+fn drop() {
+    let wasm_bytes = wat::parse_str(PARAMETRIC_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        1,
+        instance
+            .invoke_named::<(i32, i32), i32>("drop", (1, 2))
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn select() {
+    let wasm_bytes = wat::parse_str(PARAMETRIC_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        1,
+        instance
+            .invoke_named::<(i32, i32, i32), i32>("select_i32", (1, 2, 1))
+            .unwrap()
+    );
+    assert_eq!(
+        2,
+        instance
+            .invoke_named::<(i32, i32, i32), i32>("select_i32", (1, 2, 0))
+            .unwrap()
+    );
+    assert_eq!(
+        1,
+        instance
+            .invoke_named::<(i32, i32, i32), i32>("select_i32", (1, 2, -1))
+            .unwrap()
+    );
+    assert_eq!(
+        -0.5,
+        instance
+            .invoke_named::<(f64, f64, i32), f64>("select_f64", (1.5, -0.5, 0))
+            .unwrap()
+    );
+    assert_eq!(
+        i64::MAX,
+        instance
+            .invoke_named::<(i64, i64, i32), i64>("select_typed", (i64::MAX, 0, 7))
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn unreachable_traps() {
+    let wasm_bytes = wat::parse_str(PARAMETRIC_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        42,
+        instance.invoke_named::<i32, i32>("unreachable", 0).unwrap()
+    );
+    assert_eq!(
+        Err(RuntimeError::Unreachable),
+        instance.invoke_named::<i32, i32>("unreachable", 1)
+    );
+    // The instance can still be used after the trap
+    assert_eq!(
+        42,
+        instance.invoke_named::<i32, i32>("unreachable", 0).unwrap()
+    );
+}
+
+/// After `unreachable` the operand stack is polymorphic until the end of the block
+#[test_log::test]
+fn unreachable_is_polymorphic() {
+    let validate_wat = |wat: &str| {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        validate(&wasm_bytes).err()
+    };
+
+    assert_eq!(
+        validate_wat(r#"(module (func (result i32) unreachable i32.add))"#),
+        None
+    );
+    assert_eq!(
+        validate_wat(r#"(module (func (result f64) unreachable select))"#),
+        None
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (result i64) unreachable (i64.const 1) (i32.const 0) select))"#
+        ),
+        None
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (result i32)
+                (block (result i64) unreachable drop drop)
+                drop
+                i32.const 0))"#
+        ),
+        None
+    );
+
+    // Known operands must still have the right types
+    assert_eq!(
+        validate_wat(r#"(module (func (result i32) unreachable (f32.const 1) i32.add))"#),
+        Some(Error::InvalidValueStackType(Some(ValType::NumType(
+            NumType::F32
+        ))))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (result i32) unreachable (i64.const 1) (i32.const 0) select))"#
+        ),
+        Some(Error::EndInvalidValueStack)
+    );
+    // The stack is only polymorphic until the end of the block
+    assert_eq!(
+        validate_wat(r#"(module (func (block unreachable) drop))"#),
+        Some(Error::InvalidValueStackType(None))
+    );
+}
+
+#[test_log::test]
+fn invalid_select() {
+    let validate_wat = |wat: &str| {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        validate(&wasm_bytes).err()
+    };
+
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (result i32) (select (i32.const 1) (i64.const 2) (i32.const 0))))"#
+        ),
+        Some(Error::InvalidValueStackType(Some(ValType::NumType(
+            NumType::I64
+        ))))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (result i32) (select (i32.const 1) (i32.const 2) (i64.const 0))))"#
+        ),
+        Some(Error::InvalidValueStackType(Some(ValType::NumType(
+            NumType::I64
+        ))))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (param funcref funcref) (result funcref)
+                (select (local.get 0) (local.get 1) (i32.const 0))))"#
+        ),
+        Some(Error::InvalidSelectOperand(ValType::RefType(
+            wasm::RefType::FuncRef
+        )))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (param funcref funcref) (result funcref)
+                (select (result funcref) (local.get 0) (local.get 1) (i32.const 0))))"#
+        ),
+        None
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (result i32)
+                (select (result i32 i32) (i32.const 1) (i32.const 2) (i32.const 0))))"#
+        ),
+        Some(Error::InvalidSelectArity(2))
+    );
+}
@@ -0,0 +1,262 @@
+use wasm::value::{ExternAddr, Ref};
+use wasm::{validate, Error, NumType, RefType, RuntimeError, RuntimeInstance, ValType, Value};
+
+const REFS_WAT: &str = r#"
+    (module
+        (type $get (func (result i32)))
+        (table $funcs 2 4 funcref)
+        (table $externs 0 externref)
+        (elem declare func $one)
+        (func $one (result i32) i32.const 1)
+        (func $two (export "two") (result i32) i32.const 2)
+        ;; function 2
+        (func (export "is_null_func") (result i32)
+            (ref.is_null (ref.null func)))
+        ;; function 3
+        (func (export "is_null_ref_func") (result i32)
+            (ref.is_null (ref.func $one)))
+        ;; function 4
+        (func (export "is_null_local") (result i32)
+            (local externref)
+            (ref.is_null (local.get 0)))
+        ;; function 5
+        (func (export "set_func") (param i32 i32)
+            (table.set $funcs
+                (local.get 0)
+                (if (result funcref) (local.get 1)
+                    (then (ref.func $one))
+                    (else (ref.func $two)))))
+        ;; function 6
+        (func (export "call") (param i32) (result i32)
+            (call_indirect $funcs (type $get) (local.get 0)))
+        ;; function 7
+        (func (export "get_func") (param i32) (result funcref)
+            (table.get $funcs (local.get 0)))
+        ;; function 8
+        (func (export "push_extern") (param externref) (result i32)
+            (table.grow $externs (local.get 0) (i32.const 1)))
+        ;; function 9
+        (func (export "get_extern") (param i32) (result externref)
+            (table.get $externs (local.get 0)))
+        ;; function 10
+        (func (export "is_null_extern") (param externref) (result i32)
+            (ref.is_null (local.get 0)))
+        ;; function 11
+        (func (export "size") (result i32)
+            (table.size $funcs))
+        ;; function 12
+        (func (export "grow") (param i32) (result i32)
+            (table.grow $funcs (ref.func $two) (local.get 0)))
+        ;; function 13
+        (func (export "fill") (param i32 i32)
+            (table.fill $funcs (local.get 0) (ref.func $one) (local.get 1)))
+    )
+"#;
+
+#[test_log::test]
+fn ref_null_and_is_null() {
+    let wasm_bytes = wat::parse_str(REFS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        1,
+        instance
+            .invoke_named::<(), i32>("is_null_func", ())
+            .unwrap()
+    );
+    assert_eq!(
+        0,
+        instance
+            .invoke_named::<(), i32>("is_null_ref_func", ())
+            .unwrap()
+    );
+    // Locals of reference types are initialized to null
+    assert_eq!(
+        1,
+        instance
+            .invoke_named::<(), i32>("is_null_local", ())
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn table_get_and_set() {
+    let wasm_bytes = wat::parse_str(REFS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let funcref = [ValType::RefType(RefType::FuncRef)];
+
+    assert_eq!(
+        vec![Value::Ref(Ref::Null(RefType::FuncRef))],
+        instance
+            .invoke_dynamic(7, vec![Value::I32(0)], &funcref)
+            .unwrap()
+    );
+
+    instance
+        .invoke_named::<(i32, i32), ()>("set_func", (0, 1))
+        .unwrap();
+    instance
+        .invoke_named::<(i32, i32), ()>("set_func", (1, 0))
+        .unwrap();
+    assert_eq!(1, instance.invoke_named::<i32, i32>("call", 0).unwrap());
+    assert_eq!(2, instance.invoke_named::<i32, i32>("call", 1).unwrap());
+    assert_eq!(
+        vec![Value::Ref(Ref::Func(1))],
+        instance
+            .invoke_dynamic(7, vec![Value::I32(1)], &funcref)
+            .unwrap()
+    );
+
+    assert_eq!(
+        Err(RuntimeError::TableAccessOutOfBounds),
+        instance.invoke_named::<(i32, i32), ()>("set_func", (2, 0))
+    );
+    assert_eq!(
+        Err(RuntimeError::TableAccessOutOfBounds),
+        instance.invoke_dynamic(7, vec![Value::I32(2)], &funcref)
+    );
+}
+
+/// Hosts can pass opaque handles into and out of WASM code as `externref`s
+#[test_log::test]
+fn extern_refs() {
+    let wasm_bytes = wat::parse_str(REFS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let i32_ty = [ValType::NumType(NumType::I32)];
+    let externref = [ValType::RefType(RefType::ExternRef)];
+
+    let handle = Value::Ref(Ref::Extern(ExternAddr(42)));
+    let null = Value::Ref(Ref::Null(RefType::ExternRef));
+
+    assert_eq!(
+        vec![Value::I32(0)],
+        instance.invoke_dynamic(8, vec![handle], &i32_ty).unwrap()
+    );
+    assert_eq!(
+        vec![Value::I32(1)],
+        instance.invoke_dynamic(8, vec![null], &i32_ty).unwrap()
+    );
+    assert_eq!(
+        vec![handle],
+        instance
+            .invoke_dynamic(9, vec![Value::I32(0)], &externref)
+            .unwrap()
+    );
+    assert_eq!(
+        vec![null],
+        instance
+            .invoke_dynamic(9, vec![Value::I32(1)], &externref)
+            .unwrap()
+    );
+
+    assert_eq!(
+        vec![Value::I32(0)],
+        instance.invoke_dynamic(10, vec![handle], &i32_ty).unwrap()
+    );
+    assert_eq!(
+        vec![Value::I32(1)],
+        instance.invoke_dynamic(10, vec![null], &i32_ty).unwrap()
+    );
+}
+
+#[test_log::test]
+fn table_size_grow_and_fill() {
+    let wasm_bytes = wat::parse_str(REFS_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(2, instance.invoke_named::<(), i32>("size", ()).unwrap());
+    assert_eq!(2, instance.invoke_named::<i32, i32>("grow", 1).unwrap());
+    assert_eq!(3, instance.invoke_named::<(), i32>("size", ()).unwrap());
+    assert_eq!(2, instance.invoke_named::<i32, i32>("call", 2).unwrap());
+
+    // Growth beyond the maximum fails with -1 and leaves the table unchanged
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("grow", 2).unwrap());
+    assert_eq!(-1, instance.invoke_named::<i32, i32>("grow", -1).unwrap());
+    assert_eq!(3, instance.invoke_named::<(), i32>("size", ()).unwrap());
+
+    instance
+        .invoke_named::<(i32, i32), ()>("fill", (1, 2))
+        .unwrap();
+    assert_eq!(
+        Err(RuntimeError::UninitializedTableElement),
+        instance.invoke_named::<i32, i32>("call", 0)
+    );
+    assert_eq!(1, instance.invoke_named::<i32, i32>("call", 1).unwrap());
+    assert_eq!(1, instance.invoke_named::<i32, i32>("call", 2).unwrap());
+
+    // Bounds are checked before anything is written
+    assert_eq!(
+        Err(RuntimeError::TableAccessOutOfBounds),
+        instance.invoke_named::<(i32, i32), ()>("fill", (0, 4))
+    );
+    assert_eq!(
+        Err(RuntimeError::UninitializedTableElement),
+        instance.invoke_named::<i32, i32>("call", 0)
+    );
+    instance
+        .invoke_named::<(i32, i32), ()>("fill", (3, 0))
+        .unwrap();
+}
+
+#[test_log::test]
+fn invalid_reference_instructions() {
+    let validate_wat = |wat: &str| {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        validate(&wasm_bytes).err()
+    };
+
+    // Functions must be referenced outside of function bodies to be used by `ref.func`
+    assert_eq!(
+        validate_wat(r#"(module (func $f (result funcref) (ref.func $f)))"#),
+        Some(Error::UndeclaredFuncRef(0))
+    );
+    assert_eq!(
+        validate_wat(r#"(module (func $f (export "f") (result funcref) (ref.func $f)))"#),
+        None
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (global funcref (ref.func $f)) (func $f (result funcref) (ref.func $f)))"#
+        ),
+        None
+    );
+    assert_eq!(
+        validate_wat(r#"(module (func (result funcref) (ref.func 1)))"#),
+        Some(Error::InvalidFuncIdx(1))
+    );
+
+    assert_eq!(
+        validate_wat(r#"(module (func (result i32) (ref.is_null (i32.const 0))))"#),
+        Some(Error::InvalidValueStackType(Some(ValType::NumType(
+            NumType::I32
+        ))))
+    );
+    assert_eq!(
+        validate_wat(r#"(module (func (result i32) unreachable ref.is_null))"#),
+        None
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (table 1 funcref) (func (table.set 0 (i32.const 0) (ref.null extern))))"#
+        ),
+        Some(Error::InvalidValueStackType(Some(ValType::RefType(
+            RefType::ExternRef
+        ))))
+    );
+    assert_eq!(
+        validate_wat(r#"(module (func (result funcref) (table.get 0 (i32.const 0))))"#),
+        Some(Error::InvalidTableIdx(0))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (table 1 externref) (func (result i32) (table.grow 0 (ref.null func) (i32.const 1))))"#
+        ),
+        Some(Error::InvalidValueStackType(Some(ValType::RefType(
+            RefType::FuncRef
+        ))))
+    );
+}
@@ -0,0 +1,231 @@
+use wasm::fuel::ExecutionOutcome;
+use wasm::linker::Linker;
+use wasm::{validate, NumType, RuntimeError, RuntimeInstance, ValType, Value};
+
+const FIBONACCI: &str = r#"
+    (module
+        (func $fib (export "fib") (param $n i32) (result i32)
+            local.get $n
+            i32.const 2
+            i32.lt_s
+            (if (result i32)
+                (then
+                    local.get $n
+                )
+                (else
+                    local.get $n
+                    i32.const 1
+                    i32.sub
+                    call $fib
+                    local.get $n
+                    i32.const 2
+                    i32.sub
+                    call $fib
+                    i32.add
+                )
+            )
+        )
+    )
+"#;
+
+/// Resuming an invocation many times with little fuel yields the same result as running it at once
+#[test_log::test]
+fn resume_until_finished() {
+    let wasm_bytes = wat::parse_str(FIBONACCI).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("fib", 15)
+        .unwrap();
+
+    let mut pauses = 0;
+    let result = loop {
+        match resumable.resume(7).unwrap() {
+            ExecutionOutcome::Finished(result) => break result,
+            ExecutionOutcome::OutOfFuel => pauses += 1,
+            ExecutionOutcome::Paused => unreachable!("there are no hooks that pause execution"),
+        }
+    };
+
+    assert_eq!(610, result);
+    assert!(pauses > 100);
+    assert!(resumable.is_finished());
+    assert_eq!(610, instance.invoke_named::<i32, i32>("fib", 15).unwrap());
+}
+
+/// Without any fuel, not a single instruction is executed
+#[test_log::test]
+fn resume_without_fuel() {
+    let wasm_bytes = wat::parse_str(FIBONACCI).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut resumable = instance.invoke_func_resumable::<i32, i32>(0, 1).unwrap();
+    let pc = resumable.pc();
+
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(0).unwrap());
+    assert_eq!(pc, resumable.pc());
+    assert_eq!(
+        ExecutionOutcome::Finished(1),
+        resumable.resume(100).unwrap()
+    );
+}
+
+/// The paused state can be inspected
+#[test_log::test]
+fn inspect_paused_invocation() {
+    let wat = r#"
+    (module
+        (func $inner (param $x i32) (result i32)
+            local.get $x
+            i32.const 2
+            i32.mul
+        )
+        (func (export "outer") (param $x i32) (result i32)
+            i32.const 1
+            local.get $x
+            call $inner
+            i32.add
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("outer", 20)
+        .unwrap();
+    assert_eq!(1, resumable.current_func_idx());
+    assert_eq!(1, resumable.call_depth());
+    assert!(resumable.values().is_empty());
+
+    // i32.const, local.get, call, local.get
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(4).unwrap());
+    assert_eq!(0, resumable.current_func_idx());
+    assert_eq!(2, resumable.call_depth());
+    assert_eq!(&[Value::I32(1), Value::I32(20)], resumable.values());
+    assert!(!resumable.is_finished());
+
+    assert_eq!(
+        ExecutionOutcome::Finished(41),
+        resumable.resume(100).unwrap()
+    );
+}
+
+/// Cancelling a paused invocation keeps the changes it made to the instance
+#[test_log::test]
+fn cancel_paused_invocation() {
+    let wat = r#"
+    (module
+        (memory 1)
+        (func (export "count_forever")
+            (loop $forever
+                i32.const 0
+                i32.const 0
+                i32.load
+                i32.const 1
+                i32.add
+                i32.store
+                br $forever
+            )
+        )
+        (func (export "get_counter") (result i32)
+            i32.const 0
+            i32.load
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut resumable = instance
+        .invoke_named_resumable::<(), ()>("count_forever", ())
+        .unwrap();
+    // the loop instruction, followed by three iterations of seven instructions each
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(22).unwrap());
+    resumable.cancel();
+
+    assert_eq!(
+        3,
+        instance.invoke_named::<(), i32>("get_counter", ()).unwrap()
+    );
+}
+
+/// Invocations that can not be prepared are reported as errors instead of panicking
+#[test_log::test]
+fn invalid_resumable_invocations() {
+    let wat = r#"
+    (module
+        (import "env" "get" (func $get (result i32)))
+        (func (export "fib") (param $n i32) (result i32)
+            local.get $n
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let mut linker = Linker::new();
+    linker.define_func(
+        "env",
+        "get",
+        &[],
+        &[ValType::NumType(NumType::I32)],
+        |_, _| Ok(vec![Value::I32(1)]),
+    );
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_linker(&validation_info, linker).expect("instantiation failed");
+
+    assert_eq!(
+        RuntimeError::FunctionNotFound,
+        instance
+            .invoke_func_resumable::<i32, i32>(2, 1)
+            .err()
+            .unwrap()
+    );
+    assert_eq!(
+        RuntimeError::HostFunctionNotResumable,
+        instance
+            .invoke_func_resumable::<(), i32>(0, ())
+            .err()
+            .unwrap()
+    );
+    assert_eq!(
+        RuntimeError::FunctionTypeMismatch,
+        instance
+            .invoke_named_resumable::<i64, i32>("fib", 1)
+            .err()
+            .unwrap()
+    );
+    assert_eq!(
+        RuntimeError::FunctionTypeMismatch,
+        instance
+            .invoke_named_resumable::<i32, ()>("fib", 1)
+            .err()
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+#[should_panic]
+fn resume_after_finish() {
+    let wasm_bytes = wat::parse_str(FIBONACCI).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut resumable = instance.invoke_func_resumable::<i32, i32>(0, 1).unwrap();
+    assert_eq!(
+        ExecutionOutcome::Finished(1),
+        resumable.resume(100).unwrap()
+    );
+
+    let _ = resumable.resume(100);
+}
@@ -0,0 +1,485 @@
+use wasm::{validate, Error, RuntimeError, RuntimeInstance, ValType, Value};
+
+const SIMD_WAT: &str = r#"
+    (module
+        (memory 1)
+        (data (i32.const 0) "\01\02\03\04\05\06\07\08\f9\fa\fb\fc\fd\fe\ff\00")
+        (global $ones v128 (v128.const i32x4 1 1 1 1))
+
+        (func (export "i32x4_add") (param v128 v128) (result v128)
+            (i32x4.add (local.get 0) (local.get 1)))
+        (func (export "i8x16_add_sat_s") (param v128 v128) (result v128)
+            (i8x16.add_sat_s (local.get 0) (local.get 1)))
+        (func (export "i16x8_mul") (param v128 v128) (result v128)
+            (i16x8.mul (local.get 0) (local.get 1)))
+        (func (export "f32x4_mul_add") (param v128 v128 v128) (result v128)
+            (f32x4.add (f32x4.mul (local.get 0) (local.get 1)) (local.get 2)))
+        (func (export "f64x2_div") (param v128 v128) (result v128)
+            (f64x2.div (local.get 0) (local.get 1)))
+        (func (export "i32x4_lt_s") (param v128 v128) (result v128)
+            (i32x4.lt_s (local.get 0) (local.get 1)))
+        (func (export "i8x16_bitmask") (param v128) (result i32)
+            (i8x16.bitmask (local.get 0)))
+        (func (export "i32x4_all_true") (param v128) (result i32)
+            (i32x4.all_true (local.get 0)))
+        (func (export "i16x8_shr_s") (param v128 i32) (result v128)
+            (i16x8.shr_s (local.get 0) (local.get 1)))
+        (func (export "i8x16_narrow_i16x8_u") (param v128 v128) (result v128)
+            (i8x16.narrow_i16x8_u (local.get 0) (local.get 1)))
+        (func (export "i32x4_dot_i16x8_s") (param v128 v128) (result v128)
+            (i32x4.dot_i16x8_s (local.get 0) (local.get 1)))
+        (func (export "i16x8_q15mulr_sat_s") (param v128 v128) (result v128)
+            (i16x8.q15mulr_sat_s (local.get 0) (local.get 1)))
+        (func (export "i64x2_extmul_high_i32x4_u") (param v128 v128) (result v128)
+            (i64x2.extmul_high_i32x4_u (local.get 0) (local.get 1)))
+        (func (export "i32x4_trunc_sat_f32x4_s") (param v128) (result v128)
+            (i32x4.trunc_sat_f32x4_s (local.get 0)))
+        (func (export "f32x4_nearest") (param v128) (result v128)
+            (f32x4.nearest (local.get 0)))
+        (func (export "bitselect") (param v128 v128 v128) (result v128)
+            (v128.bitselect (local.get 0) (local.get 1) (local.get 2)))
+
+        (func (export "splat_and_replace") (param i32 i32) (result v128)
+            (i16x8.replace_lane 7 (i16x8.splat (local.get 0)) (local.get 1)))
+        (func (export "extract_lanes") (param v128) (result i32 i32 i64 f64)
+            (i8x16.extract_lane_s 15 (local.get 0))
+            (i8x16.extract_lane_u 15 (local.get 0))
+            (i64x2.extract_lane 0 (local.get 0))
+            (f64x2.extract_lane 1 (local.get 0)))
+        (func (export "shuffle") (param v128 v128) (result v128)
+            (i8x16.shuffle 0 16 1 17 2 18 3 19 4 20 5 21 6 22 7 23 (local.get 0) (local.get 1)))
+        (func (export "swizzle") (param v128 v128) (result v128)
+            (i8x16.swizzle (local.get 0) (local.get 1)))
+
+        (func (export "load") (param i32) (result v128)
+            (v128.load (local.get 0)))
+        (func (export "load8x8_s") (param i32) (result v128)
+            (v128.load8x8_s (local.get 0)))
+        (func (export "load16_splat") (param i32) (result v128)
+            (v128.load16_splat (local.get 0)))
+        (func (export "load32_zero") (param i32) (result v128)
+            (v128.load32_zero (local.get 0)))
+        (func (export "load8_lane") (param i32 v128) (result v128)
+            (v128.load8_lane 3 (local.get 0) (local.get 1)))
+        (func (export "store") (param i32 v128)
+            (v128.store (local.get 0) (local.get 1)))
+        (func (export "store32_lane") (param i32 v128)
+            (v128.store32_lane 2 (local.get 0) (local.get 1)))
+        (func (export "load_i32") (param i32) (result i32)
+            (i32.load (local.get 0)))
+
+        (func (export "global_and_local") (param i32) (result v128)
+            (local v128)
+            (select (global.get $ones) (local.get 1) (local.get 0)))
+
+        ;; Sums n floats starting at address `ptr`, four at a time
+        (func (export "sum_f32") (param $ptr i32) (param $n i32) (result f32)
+            (local $acc v128)
+            (block $done
+                (loop $next
+                    (br_if $done (i32.eqz (local.get $n)))
+                    (local.set $acc (f32x4.add (local.get $acc) (v128.load (local.get $ptr))))
+                    (local.set $ptr (i32.add (local.get $ptr) (i32.const 16)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 4)))
+                    (br $next)))
+            (f32.add
+                (f32.add (f32x4.extract_lane 0 (local.get $acc)) (f32x4.extract_lane 1 (local.get $acc)))
+                (f32.add (f32x4.extract_lane 2 (local.get $acc)) (f32x4.extract_lane 3 (local.get $acc)))))
+    )
+"#;
+
+fn i8x16(lanes: [i8; 16]) -> u128 {
+    u128::from_le_bytes(lanes.map(|lane| lane as u8))
+}
+
+fn i16x8(lanes: [i16; 8]) -> u128 {
+    lanes
+        .iter()
+        .rev()
+        .fold(0, |v, lane| (v << 16) | u128::from(*lane as u16))
+}
+
+fn i32x4(lanes: [i32; 4]) -> u128 {
+    lanes
+        .iter()
+        .rev()
+        .fold(0, |v, lane| (v << 32) | u128::from(*lane as u32))
+}
+
+fn i64x2(lanes: [i64; 2]) -> u128 {
+    (u128::from(lanes[1] as u64) << 64) | u128::from(lanes[0] as u64)
+}
+
+fn f32x4(lanes: [f32; 4]) -> u128 {
+    i32x4(lanes.map(|lane| lane.to_bits() as i32))
+}
+
+fn f64x2(lanes: [f64; 2]) -> u128 {
+    i64x2(lanes.map(|lane| lane.to_bits() as i64))
+}
+
+#[test_log::test]
+fn lane_arithmetic() {
+    let wasm_bytes = wat::parse_str(SIMD_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut binop = |name: &str, v1: u128, v2: u128| {
+        instance
+            .invoke_named::<(u128, u128), u128>(name, (v1, v2))
+            .unwrap()
+    };
+
+    assert_eq!(
+        i32x4([3, -1, i32::MIN, 0]),
+        binop(
+            "i32x4_add",
+            i32x4([1, -3, i32::MAX, 5]),
+            i32x4([2, 2, 1, -5])
+        )
+    );
+    assert_eq!(
+        i8x16([127, -128, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        binop(
+            "i8x16_add_sat_s",
+            i8x16([100, -100, 50, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            i8x16([100, -100, -50, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])
+        )
+    );
+    assert_eq!(
+        i16x8([6, -6, 0, 0, 0, 0, 0, -32768]),
+        binop(
+            "i16x8_mul",
+            i16x8([2, -2, 0, 0, 0, 0, 0, 16384]),
+            i16x8([3, 3, 0, 0, 0, 0, 0, 2])
+        )
+    );
+    assert_eq!(
+        f64x2([0.5, f64::INFINITY]),
+        binop("f64x2_div", f64x2([1.0, 1.0]), f64x2([2.0, 0.0]))
+    );
+    assert_eq!(
+        i32x4([-1, 0, 0, -1]),
+        binop("i32x4_lt_s", i32x4([1, 2, 3, -4]), i32x4([2, 2, 2, 4]))
+    );
+    assert_eq!(
+        i8x16([0, 0, -1, -1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]),
+        binop(
+            "i8x16_narrow_i16x8_u",
+            i16x8([-5, 0, 255, 256, 0, 0, 0, 0]),
+            i16x8([1, 0, 0, 0, 0, 0, 0, 0])
+        )
+    );
+    assert_eq!(
+        i32x4([11, i32::MIN, 0, 0]),
+        binop(
+            "i32x4_dot_i16x8_s",
+            i16x8([1, 2, i16::MIN, i16::MIN, 0, 0, 0, 0]),
+            i16x8([3, 4, i16::MIN, i16::MIN, 0, 0, 0, 0])
+        )
+    );
+    assert_eq!(
+        i16x8([i16::MAX, 8192, -8192, 0, 0, 0, 0, 0]),
+        binop(
+            "i16x8_q15mulr_sat_s",
+            i16x8([i16::MIN, 16384, 16384, 0, 0, 0, 0, 0]),
+            i16x8([i16::MIN, 16384, -16384, 0, 0, 0, 0, 0])
+        )
+    );
+    assert_eq!(
+        i64x2([0xFFFF_FFFE_0000_0001u64 as i64, 6]),
+        binop(
+            "i64x2_extmul_high_i32x4_u",
+            i32x4([7, 7, -1, 2]),
+            i32x4([7, 7, -1, 3])
+        )
+    );
+    assert_eq!(
+        0x0F0F_0F0F_0F0F_0F0F_3333_3333_5555_5555,
+        instance
+            .invoke_named::<(u128, u128, u128), u128>(
+                "bitselect",
+                (u128::MAX, 0, 0x0F0F_0F0F_0F0F_0F0F_3333_3333_5555_5555)
+            )
+            .unwrap()
+    );
+
+    assert_eq!(
+        f32x4([7.0, -1.0, 2.5, 1.0]),
+        instance
+            .invoke_named::<(u128, u128, u128), u128>(
+                "f32x4_mul_add",
+                (
+                    f32x4([2.0, -1.0, 0.5, 0.0]),
+                    f32x4([3.0, 2.0, 3.0, 100.0]),
+                    f32x4([1.0, 1.0, 1.0, 1.0])
+                )
+            )
+            .unwrap()
+    );
+    assert_eq!(
+        i32x4([0, i32::MAX, i32::MIN, -3]),
+        instance
+            .invoke_named::<u128, u128>(
+                "i32x4_trunc_sat_f32x4_s",
+                f32x4([f32::NAN, 3e10, f32::NEG_INFINITY, -3.9])
+            )
+            .unwrap()
+    );
+    // Ties are rounded to even
+    assert_eq!(
+        f32x4([2.0, -2.0, 4.0, 1.0]),
+        instance
+            .invoke_named::<u128, u128>("f32x4_nearest", f32x4([2.5, -2.5, 3.5, 0.7]))
+            .unwrap()
+    );
+    assert_eq!(
+        0b1000_0000_0000_0101,
+        instance
+            .invoke_named::<u128, i32>(
+                "i8x16_bitmask",
+                i8x16([-1, 0, -128, 127, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, -2])
+            )
+            .unwrap()
+    );
+    assert_eq!(
+        1,
+        instance
+            .invoke_named::<u128, i32>("i32x4_all_true", i32x4([1, -1, 0x100, 1 << 31]))
+            .unwrap()
+    );
+    assert_eq!(
+        0,
+        instance
+            .invoke_named::<u128, i32>("i32x4_all_true", i32x4([1, -1, 0, 1]))
+            .unwrap()
+    );
+    // The shift amount is taken modulo the lane width
+    assert_eq!(
+        i16x8([-4, 4, 0, 0, 0, 0, 0, -8192]),
+        instance
+            .invoke_named::<(u128, i32), u128>(
+                "i16x8_shr_s",
+                (i16x8([-16, 16, 0, 0, 0, 0, 0, i16::MIN]), 18)
+            )
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn lanes_and_shuffles() {
+    let wasm_bytes = wat::parse_str(SIMD_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        i16x8([-2, -2, -2, -2, -2, -2, -2, 1000]),
+        instance
+            .invoke_named::<(i32, i32), u128>("splat_and_replace", (0x7_FFFE, 1000))
+            .unwrap()
+    );
+    assert_eq!(
+        (-65, 191, 0x0102_0304_0506_0708, -1.5),
+        instance
+            .invoke_named::<u128, (i32, i32, i64, f64)>(
+                "extract_lanes",
+                (u128::from((-1.5f64).to_bits()) << 64) | 0x0102_0304_0506_0708
+            )
+            .unwrap()
+    );
+    assert_eq!(
+        u128::from_le_bytes([
+            0x00, 0x10, 0x01, 0x11, 0x02, 0x12, 0x03, 0x13, 0x04, 0x14, 0x05, 0x15, 0x06, 0x16,
+            0x07, 0x17
+        ]),
+        instance
+            .invoke_named::<(u128, u128), u128>(
+                "shuffle",
+                (
+                    u128::from_le_bytes(core::array::from_fn(|i| i as u8)),
+                    u128::from_le_bytes(core::array::from_fn(|i| 0x10 + i as u8))
+                )
+            )
+            .unwrap()
+    );
+    // Indices that are out of range select zero
+    assert_eq!(
+        u128::from_le_bytes([0x0F, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        instance
+            .invoke_named::<(u128, u128), u128>(
+                "swizzle",
+                (
+                    u128::from_le_bytes(core::array::from_fn(|i| i as u8)),
+                    u128::from_le_bytes([
+                        15, 16, 255, 1, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80,
+                        0x80, 0x80
+                    ])
+                )
+            )
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn vector_memory_access() {
+    let wasm_bytes = wat::parse_str(SIMD_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let data = [
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+        0x00,
+    ];
+    assert_eq!(
+        u128::from_le_bytes(data),
+        instance.invoke_named::<i32, u128>("load", 0).unwrap()
+    );
+    assert_eq!(
+        i16x8([-7, -6, -5, -4, -3, -2, -1, 0]),
+        instance.invoke_named::<i32, u128>("load8x8_s", 8).unwrap()
+    );
+    assert_eq!(
+        i16x8([0x0302; 8]),
+        instance
+            .invoke_named::<i32, u128>("load16_splat", 1)
+            .unwrap()
+    );
+    assert_eq!(
+        0x0807_0605,
+        instance
+            .invoke_named::<i32, u128>("load32_zero", 4)
+            .unwrap()
+    );
+    assert_eq!(
+        !(0xFF << 24) | (0x04 << 24),
+        instance
+            .invoke_named::<(i32, u128), u128>("load8_lane", (3, u128::MAX))
+            .unwrap()
+    );
+
+    instance
+        .invoke_named::<(i32, u128), ()>("store", (32, i32x4([1, 2, 3, 4])))
+        .unwrap();
+    assert_eq!(
+        3,
+        instance.invoke_named::<i32, i32>("load_i32", 40).unwrap()
+    );
+    instance
+        .invoke_named::<(i32, u128), ()>("store32_lane", (64, i32x4([1, 2, 3, 4])))
+        .unwrap();
+    assert_eq!(
+        3,
+        instance.invoke_named::<i32, i32>("load_i32", 64).unwrap()
+    );
+    assert_eq!(
+        0,
+        instance.invoke_named::<i32, i32>("load_i32", 68).unwrap()
+    );
+
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<i32, u128>("load", 65536 - 15)
+    );
+    assert_eq!(
+        Err(RuntimeError::MemoryOutOfBounds),
+        instance.invoke_named::<(i32, u128), ()>("store", (65536 - 8, 0))
+    );
+
+    // Sum a buffer of floats written by the host as a single vector store each
+    for (i, lanes) in [[1.0, 2.0, 3.0, 4.0], [0.5, 0.25, 0.125, 0.125]]
+        .into_iter()
+        .enumerate()
+    {
+        instance
+            .invoke_named::<(i32, u128), ()>("store", (128 + 16 * i as i32, f32x4(lanes)))
+            .unwrap();
+    }
+    assert_eq!(
+        11.0,
+        instance
+            .invoke_named::<(i32, i32), f32>("sum_f32", (128, 8))
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn vector_globals_locals_and_values() {
+    let wasm_bytes = wat::parse_str(SIMD_WAT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        i32x4([1; 4]),
+        instance
+            .invoke_named::<i32, u128>("global_and_local", 1)
+            .unwrap()
+    );
+    // Locals of type v128 are initialized with zeros
+    assert_eq!(
+        0,
+        instance
+            .invoke_named::<i32, u128>("global_and_local", 0)
+            .unwrap()
+    );
+
+    let bytes: [u8; 16] = core::array::from_fn(|i| i as u8);
+    // function 0 is `i32x4_add`
+    assert_eq!(
+        vec![Value::V128(bytes)],
+        instance
+            .invoke_dynamic(
+                0,
+                vec![Value::V128(bytes), Value::V128([0; 16])],
+                &[ValType::VecType]
+            )
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+fn invalid_vector_instructions() {
+    let validate_wat = |wat: &str| {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        validate(&wasm_bytes).err()
+    };
+
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (param v128) (result i32) (i32x4.extract_lane 4 (local.get 0))))"#
+        ),
+        Some(Error::InvalidLaneIdx(4))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (param v128) (result v128)
+                (i8x16.shuffle 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 32 (local.get 0) (local.get 0))))"#
+        ),
+        Some(Error::InvalidLaneIdx(32))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (memory 1) (func (result v128) (v128.load align=32 (i32.const 0))))"#
+        ),
+        Some(Error::InvalidAlignment(5))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (memory 1) (func (result v128) (v128.load32_splat align=8 (i32.const 0))))"#
+        ),
+        Some(Error::InvalidAlignment(3))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (param i32) (result v128) (i32x4.add (local.get 0) (local.get 0))))"#
+        ),
+        Some(Error::InvalidValueStackType(Some(ValType::NumType(
+            wasm::NumType::I32
+        ))))
+    );
+    assert_eq!(
+        validate_wat(
+            r#"(module (func (param v128) (result v128) (i32x4.shl (local.get 0) (local.get 0))))"#
+        ),
+        Some(Error::InvalidValueStackType(Some(ValType::VecType)))
+    );
+}
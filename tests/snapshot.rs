@@ -0,0 +1,445 @@
+use wasm::fuel::ExecutionOutcome;
+use wasm::snapshot::SNAPSHOT_VERSION;
+use wasm::{validate, RuntimeError, RuntimeInstance, SnapshotError};
+
+const FIBONACCI: &str = r#"
+    (module
+        (func $fib (export "fib") (param $n i32) (result i32)
+            local.get $n
+            i32.const 2
+            i32.lt_s
+            (if (result i32)
+                (then
+                    local.get $n
+                )
+                (else
+                    local.get $n
+                    i32.const 1
+                    i32.sub
+                    call $fib
+                    local.get $n
+                    i32.const 2
+                    i32.sub
+                    call $fib
+                    i32.add
+                )
+            )
+        )
+    )
+"#;
+
+/// Sums the numbers from 1 to n in memory, returning the sum read back from memory
+const MEMORY_SUM: &str = r#"
+    (module
+        (memory 1)
+        (func (export "sum") (param $n i32) (result i32)
+            (block $done
+                (loop $continue
+                    local.get $n
+                    i32.eqz
+                    br_if $done
+
+                    i32.const 16
+                    i32.const 16
+                    i32.load
+                    local.get $n
+                    i32.add
+                    i32.store
+
+                    local.get $n
+                    i32.const 1
+                    i32.sub
+                    local.set $n
+                    br $continue
+                )
+            )
+            i32.const 16
+            i32.load
+        )
+    )
+"#;
+
+/// Appends n references to a table, returning the table's size and the result of calling through
+/// its first element
+const TABLE_APPEND: &str = r#"
+    (module
+        (type $get (func (result i32)))
+        (table 0 funcref)
+        (elem declare func $seven)
+        (func $seven (result i32) i32.const 7)
+        (func (export "append") (param $n i32) (result i32)
+            (block $done
+                (loop $continue
+                    local.get $n
+                    i32.eqz
+                    br_if $done
+
+                    (drop (table.grow (ref.func $seven) (i32.const 1)))
+
+                    local.get $n
+                    i32.const 1
+                    i32.sub
+                    local.set $n
+                    br $continue
+                )
+            )
+            table.size
+            (call_indirect (type $get) (i32.const 0))
+            i32.add
+        )
+    )
+"#;
+
+/// Sums the numbers from 1 to n in each lane of a vector local, returning the sum of all lanes
+const VECTOR_SUM: &str = r#"
+    (module
+        (func (export "sum") (param $n i32) (result i32)
+            (local $acc v128)
+            (block $done
+                (loop $continue
+                    local.get $n
+                    i32.eqz
+                    br_if $done
+
+                    (local.set $acc (i32x4.add (local.get $acc) (i32x4.splat (local.get $n))))
+
+                    local.get $n
+                    i32.const 1
+                    i32.sub
+                    local.set $n
+                    br $continue
+                )
+            )
+            (i32.add
+                (i32.add (i32x4.extract_lane 0 (local.get $acc)) (i32x4.extract_lane 1 (local.get $acc)))
+                (i32.add (i32x4.extract_lane 2 (local.get $acc)) (i32x4.extract_lane 3 (local.get $acc))))
+        )
+    )
+"#;
+
+/// Drops a data segment, loops n times and then initializes memory from the dropped segment, which
+/// traps
+const DATA_DROP: &str = r#"
+    (module
+        (memory 1)
+        (data $d "\2a")
+        (func (export "init_after_drop") (param $n i32) (result i32)
+            data.drop $d
+            (block $done
+                (loop $continue
+                    local.get $n
+                    i32.eqz
+                    br_if $done
+
+                    local.get $n
+                    i32.const 1
+                    i32.sub
+                    local.set $n
+                    br $continue
+                )
+            )
+            (memory.init $d (i32.const 0) (i32.const 0) (i32.const 1))
+            (i32.load8_u (i32.const 0))
+        )
+    )
+"#;
+
+/// An invocation paused on one instance finishes with the same result on another instance
+#[test_log::test]
+fn migrate_paused_invocation() {
+    let wasm_bytes = wat::parse_str(FIBONACCI).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("fib", 15)
+        .unwrap();
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(1000).unwrap());
+    let snapshot = resumable.serialize();
+
+    // The encoding is canonical
+    assert_eq!(snapshot, resumable.serialize());
+
+    let mut other_instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut restored = other_instance
+        .restore_resumable::<i32>(&snapshot)
+        .expect("restoring the snapshot failed");
+    assert_eq!(resumable.pc(), restored.pc());
+    assert_eq!(resumable.call_depth(), restored.call_depth());
+    assert_eq!(resumable.values(), restored.values());
+
+    assert_eq!(
+        ExecutionOutcome::Finished(610),
+        restored.resume(1_000_000).unwrap()
+    );
+    assert_eq!(
+        ExecutionOutcome::Finished(610),
+        resumable.resume(1_000_000).unwrap()
+    );
+}
+
+/// The store, e.g. the linear memory, is part of the snapshot
+#[test_log::test]
+fn memory_is_restored() {
+    let wasm_bytes = wat::parse_str(MEMORY_SUM).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("sum", 100)
+        .unwrap();
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(500).unwrap());
+    let snapshot = resumable.serialize();
+    resumable.cancel();
+
+    let mut other_instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut restored = other_instance
+        .restore_resumable::<i32>(&snapshot)
+        .expect("restoring the snapshot failed");
+
+    assert_eq!(
+        ExecutionOutcome::Finished(5050),
+        restored.resume(1_000_000).unwrap()
+    );
+}
+
+#[test_log::test]
+fn tables_are_restored() {
+    let wasm_bytes = wat::parse_str(TABLE_APPEND).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("append", 50)
+        .unwrap();
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(300).unwrap());
+    let snapshot = resumable.serialize();
+    resumable.cancel();
+
+    let mut other_instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut restored = other_instance
+        .restore_resumable::<i32>(&snapshot)
+        .expect("restoring the snapshot failed");
+
+    assert_eq!(
+        ExecutionOutcome::Finished(57),
+        restored.resume(1_000_000).unwrap()
+    );
+}
+
+#[test_log::test]
+fn vector_values_are_restored() {
+    let wasm_bytes = wat::parse_str(VECTOR_SUM).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("sum", 100)
+        .unwrap();
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(500).unwrap());
+    let snapshot = resumable.serialize();
+    resumable.cancel();
+
+    let mut other_instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut restored = other_instance
+        .restore_resumable::<i32>(&snapshot)
+        .expect("restoring the snapshot failed");
+
+    assert_eq!(
+        ExecutionOutcome::Finished(4 * 5050),
+        restored.resume(1_000_000).unwrap()
+    );
+}
+
+#[test_log::test]
+fn refuse_snapshot_of_other_module() {
+    let wasm_bytes = wat::parse_str(FIBONACCI).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("fib", 15)
+        .unwrap();
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(100).unwrap());
+    let snapshot = resumable.serialize();
+
+    let other_wasm_bytes = wat::parse_str(MEMORY_SUM).unwrap();
+    let other_validation_info = validate(&other_wasm_bytes).expect("validation failed");
+    let mut other_instance =
+        RuntimeInstance::new(&other_validation_info).expect("instantiation failed");
+
+    assert_eq!(
+        other_instance.restore_resumable::<i32>(&snapshot).err(),
+        Some(SnapshotError::ModuleMismatch)
+    );
+}
+
+#[test_log::test]
+fn refuse_malformed_snapshots() {
+    let wasm_bytes = wat::parse_str(FIBONACCI).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("fib", 15)
+        .unwrap();
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(100).unwrap());
+    let snapshot = resumable.serialize();
+    resumable.cancel();
+
+    let mut invalid_magic = snapshot.clone();
+    invalid_magic[0] ^= 0xFF;
+    assert_eq!(
+        instance.restore_resumable::<i32>(&invalid_magic).err(),
+        Some(SnapshotError::InvalidMagic)
+    );
+
+    let mut other_version = snapshot.clone();
+    other_version[8..12].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+    assert_eq!(
+        instance.restore_resumable::<i32>(&other_version).err(),
+        Some(SnapshotError::UnsupportedVersion(SNAPSHOT_VERSION + 1))
+    );
+
+    assert_eq!(
+        instance
+            .restore_resumable::<i32>(&snapshot[..snapshot.len() - 1])
+            .err(),
+        Some(SnapshotError::Eof)
+    );
+
+    let mut trailing_bytes = snapshot.clone();
+    trailing_bytes.push(0);
+    assert_eq!(
+        instance.restore_resumable::<i32>(&trailing_bytes).err(),
+        Some(SnapshotError::TrailingBytes)
+    );
+
+    // pc, directly behind the module hash, pointing outside of the function
+    let mut invalid_pc = snapshot.clone();
+    invalid_pc[44..52].copy_from_slice(&0_u64.to_le_bytes());
+    assert_eq!(
+        instance.restore_resumable::<i32>(&invalid_pc).err(),
+        Some(SnapshotError::InvalidState)
+    );
+
+    let mut restored = instance
+        .restore_resumable::<i32>(&snapshot)
+        .expect("restoring the snapshot failed");
+    assert_eq!(
+        ExecutionOutcome::Finished(610),
+        restored.resume(1_000_000).unwrap()
+    );
+}
+
+/// Snapshots whose operands or PC could not occur during execution are refused
+#[test_log::test]
+fn refuse_inconsistent_snapshots() {
+    let wat = r#"
+    (module
+        (func (export "add") (result i32)
+            i32.const 1
+            i32.const 2
+            i32.add
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let mut resumable = instance
+        .invoke_named_resumable::<(), i32>("add", ())
+        .unwrap();
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(1).unwrap());
+    let snapshot = resumable.serialize();
+    resumable.cancel();
+
+    // pc in the middle of `i32.const 2`
+    let mut pc_within_instruction = snapshot.clone();
+    pc_within_instruction[44] += 1;
+    assert_eq!(
+        instance
+            .restore_resumable::<i32>(&pc_within_instruction)
+            .err(),
+        Some(SnapshotError::InvalidState)
+    );
+
+    let mut invalid_stp = snapshot.clone();
+    invalid_stp[52] += 1;
+    assert_eq!(
+        instance.restore_resumable::<i32>(&invalid_stp).err(),
+        Some(SnapshotError::InvalidState)
+    );
+
+    // The only operand is followed by the call frame, which is 56 bytes long. Its type is changed
+    // from i32 to f32.
+    let operand = snapshot.len() - 56 - 5;
+    assert_eq!(0x7F, snapshot[operand]);
+    let mut invalid_operand_type = snapshot.clone();
+    invalid_operand_type[operand] = 0x7D;
+    assert_eq!(
+        instance
+            .restore_resumable::<i32>(&invalid_operand_type)
+            .err(),
+        Some(SnapshotError::InvalidState)
+    );
+
+    let mut missing_operand = snapshot[..operand - 8].to_vec();
+    missing_operand.extend(0_u64.to_le_bytes());
+    missing_operand.extend(&snapshot[operand + 5..]);
+    assert_eq!(
+        instance.restore_resumable::<i32>(&missing_operand).err(),
+        Some(SnapshotError::InvalidState)
+    );
+
+    assert_eq!(
+        instance.restore_resumable::<i64>(&snapshot).err(),
+        Some(SnapshotError::FunctionTypeMismatch)
+    );
+
+    let mut restored = instance
+        .restore_resumable::<i32>(&snapshot)
+        .expect("restoring the snapshot failed");
+    assert_eq!(ExecutionOutcome::Finished(3), restored.resume(100).unwrap());
+}
+
+/// Dropped segments stay dropped after restoring a snapshot
+#[test_log::test]
+fn dropped_segments_are_restored() {
+    let wasm_bytes = wat::parse_str(DATA_DROP).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    assert_eq!(
+        Some(RuntimeError::MemoryOutOfBounds),
+        instance
+            .invoke_named::<i32, i32>("init_after_drop", 100)
+            .err()
+    );
+
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut resumable = instance
+        .invoke_named_resumable::<i32, i32>("init_after_drop", 100)
+        .unwrap();
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(0).unwrap());
+    let before_drop = resumable.serialize();
+    assert_eq!(ExecutionOutcome::OutOfFuel, resumable.resume(50).unwrap());
+    let after_drop = resumable.serialize();
+    resumable.cancel();
+
+    let mut other_instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut restored = other_instance
+        .restore_resumable::<i32>(&after_drop)
+        .expect("restoring the snapshot failed");
+    assert_eq!(
+        Some(RuntimeError::MemoryOutOfBounds),
+        restored.resume(1_000_000).err()
+    );
+
+    // The segment's contents can not be restored on an instance that already dropped it
+    assert_eq!(
+        Some(SnapshotError::InvalidState),
+        instance.restore_resumable::<i32>(&before_drop).err()
+    );
+}
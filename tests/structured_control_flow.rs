@@ -0,0 +1,508 @@
+use wasm::{validate, Error, RuntimeInstance};
+
+/// A simple function that uses a block with a result and an early `br_if` out of it
+#[test_log::test]
+fn block_with_br_if() {
+    let wat = r#"
+    (module
+        (func (export "clamp_to_ten") (param $x i32) (result i32) (local $discarded i32)
+            (block $done (result i32)
+                i32.const 10
+                local.get $x
+                i32.const 10
+                i32.gt_s
+                br_if $done
+
+                local.set $discarded
+                local.get $x
+            )
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(10, instance.invoke_named("clamp_to_ten", 42).unwrap());
+    assert_eq!(10, instance.invoke_named("clamp_to_ten", 11).unwrap());
+    assert_eq!(10, instance.invoke_named("clamp_to_ten", 10).unwrap());
+    assert_eq!(-3, instance.invoke_named("clamp_to_ten", -3).unwrap());
+}
+
+/// Computes the factorial of a number iteratively using a `loop`
+#[test_log::test]
+fn loop_factorial() {
+    let wat = r#"
+    (module
+        (func (export "factorial") (param $n i64) (result i64) (local $acc i64)
+            i64.const 1
+            local.set $acc
+            (block $exit
+                (loop $continue
+                    local.get $n
+                    i64.const 1
+                    i64.le_s
+                    br_if $exit
+
+                    local.get $acc
+                    local.get $n
+                    i64.mul
+                    local.set $acc
+
+                    local.get $n
+                    i64.const 1
+                    i64.sub
+                    local.set $n
+                    br $continue
+                )
+            )
+            local.get $acc
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(1_i64, instance.invoke_named("factorial", 0_i64).unwrap());
+    assert_eq!(1_i64, instance.invoke_named("factorial", 1_i64).unwrap());
+    assert_eq!(120_i64, instance.invoke_named("factorial", 5_i64).unwrap());
+    assert_eq!(
+        2_432_902_008_176_640_000_i64,
+        instance.invoke_named("factorial", 20_i64).unwrap()
+    );
+}
+
+/// Recursive fibonacci using `if`/`else` with results
+#[test_log::test]
+fn if_else_recursive_fibonacci() {
+    let wat = r#"
+    (module
+        (func $fib (export "fib") (param $n i32) (result i32)
+            local.get $n
+            i32.const 2
+            i32.lt_s
+            (if (result i32)
+                (then
+                    local.get $n
+                )
+                (else
+                    local.get $n
+                    i32.const 1
+                    i32.sub
+                    call $fib
+                    local.get $n
+                    i32.const 2
+                    i32.sub
+                    call $fib
+                    i32.add
+                )
+            )
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(0, instance.invoke_named("fib", 0).unwrap());
+    assert_eq!(1, instance.invoke_named("fib", 1).unwrap());
+    assert_eq!(55, instance.invoke_named("fib", 10).unwrap());
+    assert_eq!(6765, instance.invoke_named("fib", 20).unwrap());
+}
+
+/// An `if` without an `else` branch, which is only executed for odd numbers
+#[test_log::test]
+fn if_without_else() {
+    let wat = r#"
+    (module
+        (func (export "round_up_to_even") (param $x i32) (result i32)
+            local.get $x
+            i32.const 1
+            i32.and
+            (if
+                (then
+                    local.get $x
+                    i32.const 1
+                    i32.add
+                    local.set $x
+                )
+            )
+            local.get $x
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(4, instance.invoke_named("round_up_to_even", 3).unwrap());
+    assert_eq!(4, instance.invoke_named("round_up_to_even", 4).unwrap());
+    assert_eq!(0, instance.invoke_named("round_up_to_even", 0).unwrap());
+}
+
+/// A switch-like construct using `br_table`, with values carried by the branch
+#[test_log::test]
+fn br_table_switch() {
+    let wat = r#"
+    (module
+        (func (export "switch") (param $case i32) (result i32)
+            (block $default (result i32)
+                (block $two (result i32)
+                    (block $one (result i32)
+                        (block $zero (result i32)
+                            i32.const 100
+                            local.get $case
+                            br_table $zero $one $two $default
+                        )
+                        i32.const 1
+                        i32.add
+                        return
+                    )
+                    i32.const 2
+                    i32.add
+                    return
+                )
+                i32.const 3
+                i32.add
+                return
+            )
+            i32.const 4
+            i32.add
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(101, instance.invoke_named("switch", 0).unwrap());
+    assert_eq!(102, instance.invoke_named("switch", 1).unwrap());
+    assert_eq!(103, instance.invoke_named("switch", 2).unwrap());
+    assert_eq!(104, instance.invoke_named("switch", 3).unwrap());
+    assert_eq!(104, instance.invoke_named("switch", 1337).unwrap());
+    assert_eq!(104, instance.invoke_named("switch", -1).unwrap());
+}
+
+/// Branches must unwind all operands of the blocks they leave, except for the branch's values
+#[test_log::test]
+fn br_unwinds_operand_stack() {
+    let wat = r#"
+    (module
+        (func (export "unwind") (param $x i32) (result i32)
+            i32.const 1
+            (block $outer (result i32)
+                i32.const 2
+                i32.const 3
+                (block $inner
+                    i32.const 4
+                    i32.const 5
+                    local.get $x
+                    br $outer
+                )
+                i32.const 6
+                br $outer
+            )
+            i32.add
+        )
+        (func (export "branch_to_function_body") (param $x i32) (result i32)
+            (block
+                (loop
+                    i32.const 7
+                    local.get $x
+                    br 2
+                )
+            )
+            i32.const 8
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(43, instance.invoke_named("unwind", 42).unwrap());
+    assert_eq!(
+        -5,
+        instance
+            .invoke_named("branch_to_function_body", -5)
+            .unwrap()
+    );
+}
+
+/// Nested loops with branches to the outer loop from within the inner one
+#[test_log::test]
+fn nested_loops() {
+    let wat = r#"
+    (module
+        ;; counts the pairs (i, j) with 0 <= j <= i < n
+        (func (export "triangle") (param $n i32) (result i32)
+            (local $i i32) (local $j i32) (local $count i32)
+            (block $outer_exit
+                (loop $outer
+                    local.get $i
+                    local.get $n
+                    i32.ge_s
+                    br_if $outer_exit
+
+                    i32.const 0
+                    local.set $j
+                    (loop $inner
+                        local.get $count
+                        i32.const 1
+                        i32.add
+                        local.set $count
+
+                        local.get $j
+                        i32.const 1
+                        i32.add
+                        local.tee $j
+                        local.get $i
+                        i32.gt_s
+                        (if
+                            (then
+                                local.get $i
+                                i32.const 1
+                                i32.add
+                                local.set $i
+                                br $outer
+                            )
+                        )
+                        br $inner
+                    )
+                )
+            )
+            local.get $count
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(0, instance.invoke_named("triangle", 0).unwrap());
+    assert_eq!(1, instance.invoke_named("triangle", 1).unwrap());
+    assert_eq!(55, instance.invoke_named("triangle", 10).unwrap());
+}
+
+/// Code after an unconditional branch is unreachable, so its operand stack is polymorphic
+#[test_log::test]
+fn unreachable_code_after_branch_validates() {
+    let wat = r#"
+    (module
+        (func (export "polymorphic") (result i32)
+            (block $b (result i32)
+                i32.const 1
+                br $b
+                f32.const 1
+                f32.neg
+                f32.ne
+                i32.add
+                br_table $b $b
+            )
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(1, instance.invoke_named("polymorphic", ()).unwrap());
+}
+
+/// Branches after returning from a call must continue with the caller's side-table entries
+#[test_log::test]
+fn branches_around_calls() {
+    let wat = r#"
+    (module
+        (func $is_even (param $x i32) (result i32)
+            (block $odd
+                local.get $x
+                i32.const 1
+                i32.and
+                br_if $odd
+                i32.const 1
+                return
+            )
+            i32.const 0
+        )
+        ;; sums all even numbers below n
+        (func (export "sum_of_evens") (param $n i32) (result i32) (local $sum i32)
+            (loop $continue
+                local.get $n
+                i32.eqz
+                (if
+                    (then
+                        local.get $sum
+                        return
+                    )
+                )
+
+                local.get $n
+                i32.const 1
+                i32.sub
+                local.tee $n
+                call $is_even
+                (if
+                    (then
+                        local.get $sum
+                        local.get $n
+                        i32.add
+                        local.set $sum
+                    )
+                    (else
+                        br $continue
+                    )
+                )
+                br $continue
+            )
+            local.get $sum
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(0, instance.invoke_named("sum_of_evens", 0).unwrap());
+    assert_eq!(0, instance.invoke_named("sum_of_evens", 1).unwrap());
+    assert_eq!(2, instance.invoke_named("sum_of_evens", 3).unwrap());
+    assert_eq!(20, instance.invoke_named("sum_of_evens", 10).unwrap());
+}
+
+/// `if`/`else` with results nested in other blocks, with `br_if` carrying values out of them
+#[test_log::test]
+fn nested_if_else_with_results() {
+    let wat = r#"
+    (module
+        (func (export "sign") (param $x i32) (result i32) (local $discarded i32)
+            (block $done (result i32)
+                i32.const 0
+                local.get $x
+                i32.eqz
+                br_if $done
+                local.set $discarded
+
+                local.get $x
+                i32.const 0
+                i32.lt_s
+                (if (result i32)
+                    (then
+                        i32.const -1
+                    )
+                    (else
+                        i32.const 100
+                        (if (result i32) (i32.const 1)
+                            (then
+                                i32.const 1
+                            )
+                            (else
+                                i32.const 2
+                            )
+                        )
+                        br $done
+                    )
+                )
+            )
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    assert_eq!(0, instance.invoke_named("sign", 0).unwrap());
+    assert_eq!(-1, instance.invoke_named("sign", -42).unwrap());
+    assert_eq!(1, instance.invoke_named("sign", 42).unwrap());
+}
+
+#[test_log::test]
+fn block_result_type_mismatch() {
+    let wat = r#"
+    (module
+        (func (result i32)
+            (block (result i32)
+                i64.const 1
+            )
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    assert_eq!(
+        validate(&wasm_bytes).err(),
+        Some(Error::EndInvalidValueStack)
+    );
+}
+
+#[test_log::test]
+fn br_to_invalid_label() {
+    let wat = r#"
+    (module
+        (func
+            (block
+                br 2
+            )
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    assert_eq!(validate(&wasm_bytes).err(), Some(Error::InvalidLabelIdx(2)));
+}
+
+#[test_log::test]
+fn if_with_result_but_without_else() {
+    let wat = r#"
+    (module
+        (func (param $x i32) (result i32)
+            local.get $x
+            (if (result i32)
+                (then
+                    i32.const 1
+                )
+            )
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    assert_eq!(
+        validate(&wasm_bytes).err(),
+        Some(Error::IfWithoutMatchingElse)
+    );
+}
+
+#[test_log::test]
+fn br_table_arity_mismatch() {
+    let wat = r#"
+    (module
+        (func (param $x i32) (result i32)
+            (block $a (result i32)
+                (block $b
+                    i32.const 0
+                    local.get $x
+                    br_table $a $b
+                )
+                i32.const 1
+            )
+        )
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    assert_eq!(
+        validate(&wasm_bytes).err(),
+        Some(Error::InvalidBrTableArity)
+    );
+}